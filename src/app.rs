@@ -1,15 +1,25 @@
+use std::sync::Arc;
+
 use eframe::{CreationContext, Frame};
-use egui::{Context, Theme, ViewportCommand};
+use egui::{Context, RichText, Theme, TopBottomPanel, ViewportCommand};
 use log::debug;
 
 use crate::{
     file::ParkhayFile,
-    views::{LayoutView, View},
+    views::{LayoutView, RowsView, View},
 };
 use anyhow::Result;
 
+#[derive(Clone, Copy, PartialEq)]
+enum ActiveView {
+    Layout,
+    Rows,
+}
+
 pub struct ParkhayApp {
-    active_view: Box<dyn View>,
+    layout_view: LayoutView,
+    rows_view: RowsView,
+    active_view: ActiveView,
 }
 
 impl ParkhayApp {
@@ -25,19 +35,49 @@ impl ParkhayApp {
             cloned_ctx.request_repaint();
         })?;
 
-        let active_view = Box::new(LayoutView::new(parkhay_file, data_reader_tx));
+        let parkhay_file = Arc::new(parkhay_file);
+        let rows_view = RowsView::new(parkhay_file.clone());
+        let layout_view = LayoutView::new(parkhay_file, data_reader_tx);
 
         // Update window title
         cc.egui_ctx
             .send_viewport_cmd(ViewportCommand::Title(parquet_path));
 
-        Ok(Self { active_view })
+        Ok(Self {
+            layout_view,
+            rows_view,
+            active_view: ActiveView::Layout,
+        })
     }
 }
 
 impl eframe::App for ParkhayApp {
     fn update(&mut self, ctx: &Context, frame: &mut Frame) {
         ctx.set_theme(Theme::Light); // Force light theme for now
-        self.active_view.as_mut().update(ctx, frame);
+
+        TopBottomPanel::top("views").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                for (view, name) in [(ActiveView::Layout, "Layout"), (ActiveView::Rows, "Rows")] {
+                    ui.selectable_value(
+                        &mut self.active_view,
+                        view,
+                        RichText::new(name).monospace().strong(),
+                    );
+                }
+            });
+        });
+
+        match self.active_view {
+            ActiveView::Layout => {
+                self.layout_view.update(ctx, frame);
+                // Rows requested from links in the layout
+                if let Some(request) = self.layout_view.take_rows_request() {
+                    self.rows_view.show_rows(request, ctx);
+                    self.active_view = ActiveView::Rows;
+                    ctx.request_repaint();
+                }
+            }
+            ActiveView::Rows => self.rows_view.update(ctx, frame),
+        }
     }
 }
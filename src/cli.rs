@@ -5,4 +5,20 @@ use clap::Parser;
 pub struct ParkhayCli {
     /// Path to the parquet file
     pub path: String,
+
+    /// Export the pages recovered from a file with a missing or corrupt footer to this directory, then exit
+    #[arg(long, value_name = "DIR")]
+    pub salvage: Option<std::path::PathBuf>,
+
+    /// Print the byte ranges a reader would fetch for these comma-separated columns (or `*` for all of them), then exit
+    #[arg(long, value_name = "COLUMNS", value_delimiter = ',')]
+    pub io_plan: Option<Vec<String>>,
+
+    /// Merge I/O plan requests separated by at most this many bytes
+    #[arg(long, value_name = "BYTES", requires = "io_plan")]
+    pub coalesce_gap: Option<u64>,
+
+    /// Largest request to issue when coalescing the I/O plan
+    #[arg(long, value_name = "BYTES", requires = "io_plan")]
+    pub max_request_size: Option<u64>,
 }
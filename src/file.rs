@@ -1,5 +1,6 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    collections::BTreeMap,
+    fmt::Display,
     fs::File,
     io::{Read, Seek, SeekFrom},
     panic,
@@ -10,9 +11,48 @@ use std::{
     thread,
 };
 
-use anyhow::{Context, Error, Result};
-use parquet::{file::reader::ChunkReader, thrift::TSerializable};
+use anyhow::{Context, Error, Result, bail};
+use log::warn;
+use parquet::{
+    file::reader::ChunkReader,
+    schema::types::{ColumnDescPtr, ColumnPath, SchemaDescriptor},
+    thrift::TSerializable,
+};
+use recovery::RecoveryScanner;
 use thrift::protocol::TCompactInputProtocol;
+use thrift_trace::RecordingReader;
+
+pub use bloom_filter::{
+    BLOCK_SIZE, BloomFilterProbe, BloomFilterSaturation, recommended_num_bytes,
+};
+pub use footer_size::{ColumnFooterSize, FooterComponent, FooterSize};
+pub use io_plan::{Coalescing, IoPlan};
+pub use page_body::{
+    ByteStreamSplit, DeltaBinaryPacked, DeltaValues, HybridStream, PageBody, PageSegment, RunKind,
+};
+pub use page_header::{EncodedPageHeader, HeaderOverhead};
+pub use plain::{
+    compare_plain_values, format_bytes, format_plain_value, parse_plain_value, parse_raw_bytes,
+    plain_value_sort_key,
+};
+pub use pruning::{Predicate, PruningReport, ReadKind};
+pub use recovery::RecoveryReport;
+pub use shredding::{RecordShredding, ShreddedColumn};
+pub use thrift_trace::{
+    BLOOM_FILTER_HEADER, COLUMN_INDEX, FILE_METADATA, OFFSET_INDEX, PAGE_HEADER, ThriftField,
+    ThriftNode, ThriftSchema, ThriftTrace, ThriftValue, UnknownField,
+};
+
+mod bloom_filter;
+mod footer_size;
+mod io_plan;
+mod page_body;
+mod page_header;
+mod plain;
+mod pruning;
+mod recovery;
+mod shredding;
+mod thrift_trace;
 
 pub type ByteInterval = (u64, u64);
 pub type Field = parquet::schema::types::TypePtr;
@@ -25,10 +65,13 @@ pub struct ReadRequest(pub ByteInterval, pub Arc<Mutex<Option<Vec<u8>>>>);
 #[derive(Debug)]
 pub struct ParkhayFile {
     pub path: String,
+    pub file_size: u64,
     pub start_magic: [u8; 4],
     pub footer_length: u32,
     pub data: ParkhayDataSection,
-    pub footer: ParkhayFooter,
+    /// Missing if the footer couldn't be decoded, in which case `recovery` describes how the data section was rebuilt
+    pub footer: Option<ParkhayFooter>,
+    pub recovery: Option<RecoveryReport>,
     pub end_magic: [u8; 4],
 }
 
@@ -39,47 +82,132 @@ impl ParkhayFile {
     pub fn new(path: &str) -> Result<Self> {
         let mut file =
             File::open(path).context(format!("Couldn't open parquet file at `{path}`"))?;
+        let file_size = file
+            .metadata()
+            .context("Failed to read file metadata")?
+            .len();
 
         // Read start magic
         let mut start_magic = [0u8; Self::MAGIC_LENGTH as usize];
         file.read_exact(&mut start_magic)
             .context("Failed to read start magic bytes")?;
 
+        match Self::read_footer(&mut file, file_size) {
+            Ok((footer_length, end_magic, footer)) => Ok(Self {
+                path: path.to_string(),
+                file_size,
+                start_magic,
+                end_magic,
+                footer_length,
+                data: ParkhayDataSection::new(
+                    &footer.row_groups,
+                    footer.leaves(),
+                    footer.column_paths(),
+                    file,
+                )?,
+                footer: Some(footer),
+                recovery: None,
+            }),
+            Err(e) => {
+                // Fall back to rebuilding the data section from the page headers alone
+                let reason = format!("{e:#}");
+                warn!("Couldn't read footer ({reason}), scanning for pages instead");
+
+                let (data, report) = RecoveryScanner::new(&file, file_size)
+                    .scan(Self::MAGIC_LENGTH as u64, reason)?;
+                Ok(Self {
+                    path: path.to_string(),
+                    file_size,
+                    start_magic,
+                    end_magic: [0u8; 4],
+                    footer_length: 0,
+                    data,
+                    footer: None,
+                    recovery: Some(report),
+                })
+            }
+        }
+    }
+
+    fn read_footer(file: &mut File, file_size: u64) -> Result<(u32, [u8; 4], ParkhayFooter)> {
+        let footer_trailer_length = Self::FOOTER_LENGTH_LENGTH as u64 + Self::MAGIC_LENGTH as u64;
+        if file_size < Self::MAGIC_LENGTH as u64 + footer_trailer_length {
+            bail!("File is too small to contain a footer");
+        }
+
         // Read footer length + end magic
-        file.seek(SeekFrom::End(
-            -(Self::FOOTER_LENGTH_LENGTH as i64 + Self::MAGIC_LENGTH as i64),
-        ))
-        .context("Failed to seek to footer length offset")?;
+        file.seek(SeekFrom::End(-(footer_trailer_length as i64)))
+            .context("Failed to seek to footer length offset")?;
 
         let footer_length = {
             let mut bytes = [0u8; 4];
-            file.read_exact(&mut bytes).unwrap();
+            file.read_exact(&mut bytes)
+                .context("Failed to read footer length")?;
             u32::from_le_bytes(bytes)
         };
 
         let mut end_magic = [0u8; 4];
         file.read_exact(&mut end_magic)
             .context("Failed to read end magic bytes")?;
+        if &end_magic != b"PAR1" {
+            bail!("End magic bytes are missing");
+        }
+        if footer_length as u64 + footer_trailer_length + Self::MAGIC_LENGTH as u64 > file_size {
+            bail!("Footer length {footer_length} exceeds the file size");
+        }
 
         // Read footer content
         file.seek(SeekFrom::End(
-            -(footer_length as i64 + Self::FOOTER_LENGTH_LENGTH as i64 + Self::MAGIC_LENGTH as i64),
+            -(footer_length as i64 + footer_trailer_length as i64),
         ))
         .context("Failed to seek to footer content offset")?;
 
-        let mut blob = TCompactInputProtocol::new(&file);
-        let file_metadata =
-            parquet::format::FileMetaData::read_from_in_protocol(&mut blob).unwrap();
+        let mut footer_reader = RecordingReader::new(&*file);
+        let mut blob = TCompactInputProtocol::new(&mut footer_reader);
+        let file_metadata = parquet::format::FileMetaData::read_from_in_protocol(&mut blob)
+            .context("Could not decode footer")?;
 
-        let footer = ParkhayFooter::try_from(file_metadata)?;
-        Ok(Self {
-            path: path.to_string(),
-            start_magic,
-            end_magic,
-            footer_length,
-            data: ParkhayDataSection::new(&footer.row_groups, footer.leaves(), file)?,
-            footer,
-        })
+        let mut footer = ParkhayFooter::try_from(file_metadata)?;
+        let footer_start = file_size - footer_length as u64 - footer_trailer_length;
+        footer.unknown_fields = ThriftTrace::decode(&footer_reader.bytes, Some(&FILE_METADATA))
+            .unknown_fields(footer_start);
+        Ok((footer_length, end_magic, footer))
+    }
+
+    /// Where the thrift-encoded footer is stored, right before its length and the end magic.
+    pub fn footer_interval(&self) -> ByteInterval {
+        let footer_end =
+            self.file_size - Self::FOOTER_LENGTH_LENGTH as u64 - Self::MAGIC_LENGTH as u64;
+        (footer_end - self.footer_length as u64, footer_end - 1)
+    }
+
+    /// Write the raw bytes of each column chunk inferred by the recovery scan to `dir`.
+    pub fn export_recovered_pages(&self, dir: &std::path::Path) -> Result<Vec<std::path::PathBuf>> {
+        if self.recovery.is_none() {
+            bail!(
+                "The footer of `{}` is intact, there is nothing to recover",
+                self.path
+            );
+        }
+        let file = File::open(&self.path)?;
+        recovery::export_recovered_column_chunks(&file, &self.data, dir)
+    }
+
+    /// Plan the requests a reader would issue to read the named columns, or every column for `*`.
+    pub fn io_plan(&self, columns: &[String], coalescing: Option<Coalescing>) -> Result<IoPlan> {
+        let footer = self
+            .footer
+            .as_ref()
+            .context("Planning reads needs the footer's metadata")?;
+        let columns = if columns.iter().any(|column| column == "*") {
+            (0..footer.leaves().len()).collect()
+        } else {
+            columns
+                .iter()
+                .map(|column| footer.find_column(column.trim()))
+                .collect::<Result<Vec<_>>>()?
+        };
+        Ok(IoPlan::new(footer, &columns, coalescing, self.file_size))
     }
 
     pub fn spawn_data_reader(
@@ -115,24 +243,64 @@ impl ParkhayFile {
     }
 }
 
-// TODO BloomFilter, CustomIndex
+/// Identifies the column chunk that a section belongs to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnChunkLocation {
+    pub row_group: SectionIndex,
+    pub column: SectionIndex,
+    pub path: ColumnPath,
+}
+
+impl Display for ColumnChunkLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Row Group {}, Column {} ({})",
+            self.row_group, self.column, self.path
+        )
+    }
+}
+
+// TODO CustomIndex
 #[derive(Debug)]
 pub enum ParkhayDataSection {
+    /// The interval of a bloom filter covers its bitset, the interval of its header is stored
+    /// with it, followed by the header's unknown fields. The location is missing for bloom filters
+    /// found by a recovery scan.
     BloomFilter(
         SectionIndex,
+        Option<ColumnChunkLocation>,
         parquet::format::BloomFilterHeader,
+        ByteInterval,
+        Vec<UnknownField>,
         Arc<Mutex<Option<Vec<u8>>>>,
     ),
-    ColumnChunk(SectionIndex, SectionMap, Field),
-    ColumnIndex(SectionIndex, parquet::format::ColumnIndex),
+    ColumnChunk(ColumnChunkLocation, SectionMap, Field),
+    /// A column chunk inferred from consecutive page headers when the footer is unavailable
+    RecoveredColumnChunk(SectionIndex, SectionMap),
+    /// Followed by the fields of the index missing from the format's schema
+    ColumnIndex(
+        ColumnChunkLocation,
+        parquet::format::ColumnIndex,
+        Vec<UnknownField>,
+    ),
+    /// The interval of a page only covers its data, its header is stored right before it.
+    /// V2 data pages contain a section for each of their non-empty segments.
     Page(
         SectionIndex,
-        Box<parquet::format::PageHeader>,
+        Box<EncodedPageHeader>,
         Arc<Mutex<Option<Vec<u8>>>>,
+        SectionMap,
     ),
+    PageSegment(PageSegment, Arc<Mutex<Option<Vec<u8>>>>),
     Root(SectionMap),
     RowGroup(SectionIndex, SectionMap),
-    OffsetIndex(SectionIndex, parquet::format::OffsetIndex),
+    /// Followed by the fields of the index missing from the format's schema
+    OffsetIndex(
+        ColumnChunkLocation,
+        parquet::format::OffsetIndex,
+        Vec<UnknownField>,
+    ),
 }
 
 impl ParkhayDataSection {
@@ -144,6 +312,7 @@ impl ParkhayDataSection {
         let sections = match self {
             ParkhayDataSection::Root(sections)
             | ParkhayDataSection::ColumnChunk(_, sections, _)
+            | ParkhayDataSection::RecoveredColumnChunk(_, sections)
             | ParkhayDataSection::RowGroup(_, sections) => sections,
             _ => panic!("Cannot insert section into a non-container section"),
         };
@@ -161,9 +330,24 @@ impl ParkhayDataSection {
         sections.insert(byte_interval, section);
     }
 
+    /// Sections of the non-empty segments of a V2 data page whose data starts at `page_start`.
+    fn page_segments(header: &parquet::format::PageHeader, page_start: u64) -> SectionMap {
+        let mut segments = SectionMap::new();
+        let mut start = page_start;
+        for (segment, length) in PageSegment::lengths(header).into_iter().flatten() {
+            if length > 0 {
+                let section = Self::PageSegment(segment, Arc::new(Mutex::new(None)));
+                segments.insert((start, start + length - 1), section);
+            }
+            start += length;
+        }
+        segments
+    }
+
     fn new(
         rg_metadata: &[parquet::format::RowGroup],
-        leaves: Vec<Field>,
+        leaves: &[Field],
+        column_paths: Vec<ColumnPath>,
         file: File,
     ) -> Result<Self> {
         let mut root_section = Self::Root(SectionMap::new());
@@ -180,13 +364,19 @@ impl ParkhayDataSection {
             // Since the indexes are generally written near the end of the file, reading them
             //  after reading all the column chunk pages should minimize the cost of seeking
             //  within the file.
-            let mut offset_index_ranges = BTreeSet::new();
-            let mut column_index_ranges = BTreeSet::new();
+            let mut offset_index_ranges = BTreeMap::new();
+            let mut column_index_ranges = BTreeMap::new();
             // Bloom Filters can be either be at the end of the file or interspersed within the file
             // For now, treat them the same as column and offset indexes
-            let mut bloom_filter_header_ranges = BTreeSet::new();
+            let mut bloom_filter_header_ranges = BTreeMap::new();
 
             for (cc_idx, cc) in rg.columns.iter().enumerate() {
+                let location = ColumnChunkLocation {
+                    row_group: rg_idx as SectionIndex,
+                    column: cc_idx as SectionIndex,
+                    path: column_paths[cc_idx].clone(),
+                };
+
                 // Store optional Column Index byte range
                 if let (Some(start), Some(length)) =
                     (cc.column_index_offset, cc.column_index_length)
@@ -195,7 +385,7 @@ impl ParkhayDataSection {
                         .checked_add(length as i64 - 1)
                         .context("Column Index end exceeds bounds")?;
 
-                    column_index_ranges.insert((start, end));
+                    column_index_ranges.insert((start, end), location.clone());
                 }
 
                 // Store optional Offset Index byte range
@@ -206,21 +396,18 @@ impl ParkhayDataSection {
                         .checked_add(length as i64 - 1)
                         .context("Offset Index end exceeds bounds")?;
 
-                    offset_index_ranges.insert((start, end));
+                    offset_index_ranges.insert((start, end), location.clone());
                 }
 
                 if let Some(ref cc_metadata) = cc.meta_data {
-                    let mut cc_section = Self::ColumnChunk(
-                        cc_idx as SectionIndex,
-                        SectionMap::new(),
-                        leaves[cc_idx].clone(),
-                    );
-
                     // Store optional Bloom Filter byte offset
                     if let Some(start) = cc_metadata.bloom_filter_offset {
-                        bloom_filter_header_ranges.insert(start);
+                        bloom_filter_header_ranges.insert(start, location.clone());
                     }
 
+                    let mut cc_section =
+                        Self::ColumnChunk(location, SectionMap::new(), leaves[cc_idx].clone());
+
                     // If the column chunk has a dictionary page, read it before the first data page
                     let cc_start = cc_metadata
                         .dictionary_page_offset
@@ -235,10 +422,15 @@ impl ParkhayDataSection {
                     while page_header_reader.stream_position().unwrap()
                         != cc_start + cc_metadata.total_compressed_size as u64
                     {
-                        let mut blob = TCompactInputProtocol::new(&mut page_header_reader);
+                        let header_start = page_header_reader.stream_position().unwrap();
+                        let mut header_reader = RecordingReader::new(&mut page_header_reader);
+                        let mut blob = TCompactInputProtocol::new(&mut header_reader);
                         let page_header =
                             parquet::format::PageHeader::read_from_in_protocol(&mut blob)
                                 .context("Could not decode page header")?;
+                        let unknown_fields =
+                            ThriftTrace::decode(&header_reader.bytes, Some(&PAGE_HEADER))
+                                .unknown_fields(header_start);
                         let page_start = page_header_reader.stream_position().unwrap();
 
                         // Move reader forward by the compressed page size
@@ -254,8 +446,18 @@ impl ParkhayDataSection {
                             page_start - 1
                         };
 
-                        let page =
-                            Self::Page(page_idx, Box::new(page_header), Arc::new(Mutex::new(None)));
+                        let segments = Self::page_segments(&page_header, page_start);
+                        let page = Self::Page(
+                            page_idx,
+                            Box::new(EncodedPageHeader::new(
+                                page_header,
+                                header_start,
+                                page_start,
+                                unknown_fields,
+                            )),
+                            Arc::new(Mutex::new(None)),
+                            segments,
+                        );
                         page_idx += 1;
 
                         cc_section.insert((page_start, page_end), page); // NOTE The byte range does not include the page header
@@ -271,51 +473,64 @@ impl ParkhayDataSection {
                 }
             }
 
-            for (column_index_idx, (start, end)) in column_index_ranges.into_iter().enumerate() {
+            for ((start, end), location) in column_index_ranges {
                 let mut index_reader = file
                     .get_read(start.try_into()?)
                     .context("Could not create column index reader")?;
+                let mut index_reader = RecordingReader::new(&mut index_reader);
                 let mut blob = TCompactInputProtocol::new(&mut index_reader);
                 let column_index = parquet::format::ColumnIndex::read_from_in_protocol(&mut blob)
                     .context("Could not decode column index")?;
+                let unknown_fields = ThriftTrace::decode(&index_reader.bytes, Some(&COLUMN_INDEX))
+                    .unknown_fields(start.try_into()?);
 
                 let column_index_section =
-                    Self::ColumnIndex(column_index_idx.try_into()?, column_index);
+                    Self::ColumnIndex(location, column_index, unknown_fields);
                 root_section.insert((start.try_into()?, end.try_into()?), column_index_section);
             }
 
-            for (offset_index_idx, (start, end)) in offset_index_ranges.into_iter().enumerate() {
+            for ((start, end), location) in offset_index_ranges {
                 let mut index_reader = file
                     .get_read(start.try_into()?)
                     .context("Could not create offset index reader")?;
+                let mut index_reader = RecordingReader::new(&mut index_reader);
                 let mut blob = TCompactInputProtocol::new(&mut index_reader);
                 let offset_index = parquet::format::OffsetIndex::read_from_in_protocol(&mut blob)
                     .context("Could not decode offset index")?;
+                let unknown_fields = ThriftTrace::decode(&index_reader.bytes, Some(&OFFSET_INDEX))
+                    .unknown_fields(start.try_into()?);
 
                 let offset_index_section =
-                    Self::OffsetIndex(offset_index_idx.try_into()?, offset_index);
+                    Self::OffsetIndex(location, offset_index, unknown_fields);
                 root_section.insert((start.try_into()?, end.try_into()?), offset_index_section);
             }
 
-            for (bloom_filter_idx, header_start) in
+            for (bloom_filter_idx, (header_start, location)) in
                 bloom_filter_header_ranges.into_iter().enumerate()
             {
                 let mut bloom_filter_reader = file
                     .get_read(header_start.try_into()?)
                     .context("Could not create bloom filter reader")?;
-                let mut blob = TCompactInputProtocol::new(&mut bloom_filter_reader);
+                let mut header_reader = RecordingReader::new(&mut bloom_filter_reader);
+                let mut blob = TCompactInputProtocol::new(&mut header_reader);
                 let bloom_filter =
                     parquet::format::BloomFilterHeader::read_from_in_protocol(&mut blob)
                         .context("Could not decode bloom filter header")?;
+                let unknown_fields =
+                    ThriftTrace::decode(&header_reader.bytes, Some(&BLOOM_FILTER_HEADER))
+                        .unknown_fields(header_start.try_into()?);
 
                 let filter_num_bytes = bloom_filter.num_bytes;
+                let data_start = bloom_filter_reader.stream_position().unwrap();
                 let bloom_filter_section = Self::BloomFilter(
                     bloom_filter_idx.try_into()?,
+                    Some(location),
                     bloom_filter,
+                    (header_start.try_into()?, data_start - 1),
+                    unknown_fields,
                     Arc::new(Mutex::new(None)),
                 );
 
-                let data_start = bloom_filter_reader.stream_position().unwrap();
                 let data_end = data_start.checked_add(filter_num_bytes as u64 - 1).unwrap();
                 root_section.insert((data_start, data_end), bloom_filter_section);
             }
@@ -336,13 +551,61 @@ pub struct ParkhayFooter {
     pub schema_root: Field,
     pub column_orders: Option<Vec<parquet::format::ColumnOrder>>,
     pub row_groups: Vec<parquet::format::RowGroup>,
+    /// Fields of the footer and its nested structs missing from the format's schema
+    pub unknown_fields: Vec<UnknownField>,
+    /// Computed once from the schema, since they are looked up for every column while rendering
+    leaves: Vec<Field>,
+    column_descriptors: Vec<ColumnDescPtr>,
 }
 
 impl ParkhayFooter {
-    pub fn leaves(&self) -> Vec<Field> {
+    pub fn leaves(&self) -> &[Field] {
+        &self.leaves
+    }
+
+    /// Dot-separated paths of the leaves, in the same order as `leaves`.
+    pub fn column_paths(&self) -> Vec<ColumnPath> {
+        self.column_descriptors()
+            .iter()
+            .map(|column| column.path().clone())
+            .collect()
+    }
+
+    /// Descriptors of the leaves with their max repetition and definition levels, in the same
+    /// order as `leaves`.
+    pub fn column_descriptors(&self) -> &[ColumnDescPtr] {
+        &self.column_descriptors
+    }
+
+    /// Index of the leaf with the given dot-separated path, or else the only leaf with that name.
+    pub fn find_column(&self, name: &str) -> Result<usize> {
+        let column_paths = self.column_paths();
+        if let Some(column) = column_paths.iter().position(|path| path.string() == name) {
+            return Ok(column);
+        }
+        let candidates = column_paths
+            .iter()
+            .enumerate()
+            .filter(|(_, path)| path.parts().last().is_some_and(|leaf| leaf == name))
+            .collect::<Vec<_>>();
+        match candidates.as_slice() {
+            [(column, _)] => Ok(*column),
+            [] => bail!("Unknown column `{name}`"),
+            _ => bail!(
+                "Column `{name}` is ambiguous, use one of {}",
+                candidates
+                    .iter()
+                    .map(|(_, path)| path.string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    fn find_leaves(schema_root: &Field) -> Vec<Field> {
         let mut leaves = vec![];
-        if self.schema_root.is_group() {
-            for child_field in self.schema_root.get_fields() {
+        if schema_root.is_group() {
+            for child_field in schema_root.get_fields() {
                 Self::leaves_helper(child_field, &mut leaves);
             }
         } else {
@@ -366,14 +629,23 @@ impl ParkhayFooter {
 impl TryFrom<parquet::format::FileMetaData> for ParkhayFooter {
     type Error = Error;
     fn try_from(file_metadata: parquet::format::FileMetaData) -> Result<Self> {
+        let schema_root = parquet::schema::types::from_thrift(&file_metadata.schema)
+            .context("Could not decode schema")?;
+        let leaves = Self::find_leaves(&schema_root);
+        let column_descriptors = SchemaDescriptor::new(schema_root.clone())
+            .columns()
+            .to_vec();
         Ok(Self {
             version: file_metadata.version,
             num_rows: file_metadata.num_rows,
             created_by: file_metadata.created_by,
             key_value_metadata: file_metadata.key_value_metadata,
-            schema_root: parquet::schema::types::from_thrift(&file_metadata.schema).unwrap(),
+            schema_root,
             column_orders: file_metadata.column_orders,
             row_groups: file_metadata.row_groups,
+            unknown_fields: vec![],
+            leaves,
+            column_descriptors,
         })
     }
 }
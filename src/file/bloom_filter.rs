@@ -0,0 +1,137 @@
+use twox_hash::XxHash64;
+
+/// Bytes in a block of a split block bloom filter
+pub const BLOCK_SIZE: usize = 32;
+
+/// Multipliers that select one bit per word, as defined by the parquet-format spec
+const SALT: [u32; 8] = [
+    0x47b6137b, 0x44974d91, 0x8824ad5b, 0xa2b7289d, 0x705495c7, 0x2df1424b, 0x9efc4947, 0x5c6bfb31,
+];
+
+/// The bit checked in one of the 8 words of a block.
+pub struct ProbedWord {
+    pub salt: u32,
+    pub bit: u32,
+    pub word: u32,
+    pub is_set: bool,
+}
+
+/// Every step of checking a plain-encoded value against a split block bloom filter.
+pub struct BloomFilterProbe {
+    pub hash: u64,
+    pub block_index: usize,
+    pub words: Vec<ProbedWord>,
+}
+
+impl BloomFilterProbe {
+    /// Returns `None` if the bitset isn't made of whole blocks.
+    pub fn new(bitset: &[u8], value: &[u8]) -> Option<Self> {
+        if bitset.is_empty() || !bitset.len().is_multiple_of(BLOCK_SIZE) {
+            return None;
+        }
+
+        let hash = XxHash64::oneshot(0, value);
+        let num_blocks = (bitset.len() / BLOCK_SIZE) as u64;
+        // The upper 32 bits of the hash select the block, the lower 32 bits the bits within it
+        let block_index = (((hash >> 32) * num_blocks) >> 32) as usize;
+        let key = hash as u32;
+
+        let block = &bitset[block_index * BLOCK_SIZE..(block_index + 1) * BLOCK_SIZE];
+        let words = SALT
+            .iter()
+            .zip(block.chunks_exact(4))
+            .map(|(salt, word)| {
+                let bit = key.wrapping_mul(*salt) >> 27;
+                let word = u32::from_le_bytes(word.try_into().unwrap());
+                ProbedWord {
+                    salt: *salt,
+                    bit,
+                    word,
+                    is_set: word & (1 << bit) != 0,
+                }
+            })
+            .collect();
+
+        Some(Self {
+            hash,
+            block_index,
+            words,
+        })
+    }
+
+    /// False if the value is definitely not in the column chunk.
+    pub fn is_possibly_present(&self) -> bool {
+        self.words.iter().all(|word| word.is_set)
+    }
+}
+
+/// How full a split block bloom filter is, and how likely it is to let absent values through.
+pub struct BloomFilterSaturation {
+    /// Set bits in each block, out of 256
+    pub block_popcounts: Vec<u32>,
+    pub fill_ratio: f64,
+    /// Chance that a value that was never inserted is reported as possibly present
+    pub false_positive_probability: f64,
+    /// Distinct values inserted, estimated from how many bits are set. Missing if any word is
+    /// completely set, as the estimate would be unbounded.
+    pub estimated_distinct_values: Option<f64>,
+}
+
+impl BloomFilterSaturation {
+    /// Returns `None` if the bitset isn't made of whole blocks.
+    pub fn new(bitset: &[u8]) -> Option<Self> {
+        if bitset.is_empty() || !bitset.len().is_multiple_of(BLOCK_SIZE) {
+            return None;
+        }
+
+        let mut block_popcounts = vec![];
+        let mut total_probability = 0.;
+        let mut estimated_distinct_values = Some(0.);
+        for block in bitset.chunks_exact(BLOCK_SIZE) {
+            let word_popcounts = block
+                .chunks_exact(4)
+                .map(|word| u32::from_le_bytes(word.try_into().unwrap()).count_ones())
+                .collect::<Vec<_>>();
+            block_popcounts.push(word_popcounts.iter().sum());
+
+            // An absent value lands in a block uniformly, and is a false positive if the bit it
+            // selects in each word happens to be set
+            total_probability += word_popcounts
+                .iter()
+                .map(|popcount| f64::from(*popcount) / 32.)
+                .product::<f64>();
+
+            // Each insert sets one bit per word, so a word with k bits set after n inserts has
+            // an expected fill of 1 - (31/32)^n. Average the estimate over the words of a block.
+            estimated_distinct_values = estimated_distinct_values.and_then(|estimate: f64| {
+                let inserts = word_popcounts
+                    .iter()
+                    .map(|popcount| {
+                        (*popcount < 32)
+                            .then(|| (1. - f64::from(*popcount) / 32.).ln() / (31_f64 / 32.).ln())
+                    })
+                    .sum::<Option<f64>>()?;
+                Some(estimate + inserts / 8.)
+            });
+        }
+
+        let num_blocks = block_popcounts.len() as f64;
+        let set_bits = block_popcounts.iter().map(|v| f64::from(*v)).sum::<f64>();
+        Some(Self {
+            fill_ratio: set_bits / (num_blocks * 256.),
+            false_positive_probability: total_probability / num_blocks,
+            estimated_distinct_values,
+            block_popcounts,
+        })
+    }
+}
+
+/// Bitset size that parquet writers pick for the given distinct values and false positive
+/// probability, a power of two between 32 bytes and 128 MiB.
+pub fn recommended_num_bytes(num_distinct_values: u64, false_positive_probability: f64) -> usize {
+    let num_bits =
+        -8. * num_distinct_values as f64 / (1. - false_positive_probability.powf(1. / 8.)).ln();
+    ((num_bits / 8.) as usize)
+        .clamp(BLOCK_SIZE, 128 * 1024 * 1024)
+        .next_power_of_two()
+}
@@ -0,0 +1,305 @@
+use std::fmt::Display;
+
+use super::{ThriftField, ThriftNode, ThriftTrace, ThriftValue};
+
+/// Share of the footer above which a component gets a suggestion
+const LARGE_COMPONENT_SHARE: f64 = 0.2;
+/// Share of the footer above which encoding or size statistics get a suggestion
+const LARGE_AUXILIARY_SHARE: f64 = 0.1;
+/// Statistics per column chunk above which a column's min and max values are considered wide
+const WIDE_STATISTICS_BYTES: usize = 64;
+/// Wide columns named in a suggestion
+const MAX_SUGGESTED_COLUMNS: usize = 3;
+/// Row groups above which repeating column metadata in each of them gets a suggestion
+const MANY_ROW_GROUPS: usize = 10;
+
+/// Kinds of metadata in the footer, which don't overlap and add up to the footer's size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FooterComponent {
+    SchemaElements,
+    /// Column chunks and their metadata, other than the statistics listed separately
+    ColumnMetadata,
+    Statistics,
+    EncodingStats,
+    SizeStatistics,
+    /// Fields of row groups other than their column chunks
+    RowGroupFields,
+    KeyValueMetadata,
+    /// File level fields such as the version, column orders and `created_by`
+    Other,
+}
+
+impl Display for FooterComponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::SchemaElements => "Schema Elements",
+            Self::ColumnMetadata => "Column Metadata",
+            Self::Statistics => "Statistics",
+            Self::EncodingStats => "Encoding Stats",
+            Self::SizeStatistics => "Size Statistics",
+            Self::RowGroupFields => "Row Group Fields",
+            Self::KeyValueMetadata => "Key Value Metadata",
+            Self::Other => "Other",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Footer bytes spent on a column, across all row groups.
+#[derive(Clone, Debug, Default)]
+pub struct ColumnFooterSize {
+    pub path: String,
+    pub num_chunks: usize,
+    /// Bytes of the column chunks other than their statistics, encoding stats and size statistics
+    pub metadata: usize,
+    pub statistics: usize,
+    pub encoding_stats: usize,
+    pub size_statistics: usize,
+}
+
+impl ColumnFooterSize {
+    pub fn total(&self) -> usize {
+        self.metadata + self.statistics + self.encoding_stats + self.size_statistics
+    }
+
+    /// Add a column chunk of the column, splitting its bytes into the kinds of metadata.
+    fn add_chunk(&mut self, chunk: &ThriftValue, bytes: usize) {
+        self.num_chunks += 1;
+        self.metadata += bytes;
+        let ThriftValue::Struct(fields) = chunk else {
+            return;
+        };
+        let Some(ThriftValue::Struct(metadata)) =
+            FooterSize::field(fields, 3).map(|field| &field.value.value)
+        else {
+            return;
+        };
+        if let Some(ThriftValue::List(_, path)) =
+            FooterSize::field(metadata, 3).map(|field| &field.value.value)
+        {
+            self.path = path
+                .iter()
+                .map(|element| match &element.value {
+                    ThriftValue::Binary(name) => String::from_utf8_lossy(name).into_owned(),
+                    _ => String::from("?"),
+                })
+                .collect::<Vec<_>>()
+                .join(".");
+        }
+        for (id, bytes) in [
+            (12, &mut self.statistics),
+            (13, &mut self.encoding_stats),
+            (16, &mut self.size_statistics),
+        ] {
+            if let Some(field) = FooterSize::field(metadata, id) {
+                *bytes += field.bytes().len();
+                self.metadata -= field.bytes().len();
+            }
+        }
+    }
+}
+
+/// Where the bytes of the footer go, found from the byte spans of its thrift encoding.
+#[derive(Debug)]
+pub struct FooterSize {
+    pub total: usize,
+    pub components: Vec<(FooterComponent, usize)>,
+    /// Columns in the order of the column chunks of a row group
+    pub columns: Vec<ColumnFooterSize>,
+    /// Bytes of each row group, column chunks included
+    pub row_groups: Vec<usize>,
+    pub key_values: Vec<(String, usize)>,
+    pub num_schema_elements: usize,
+    pub suggestions: Vec<String>,
+}
+
+impl FooterSize {
+    pub fn new(trace: &ThriftTrace) -> Self {
+        let mut size = Self {
+            total: trace.bytes.len(),
+            components: vec![],
+            columns: vec![],
+            row_groups: vec![],
+            key_values: vec![],
+            num_schema_elements: 0,
+            suggestions: vec![],
+        };
+
+        let schema = Self::field(&trace.fields, 2);
+        size.num_schema_elements = schema.map_or(0, |field| Self::elements(field).len());
+        let mut row_group_fields = 0;
+        for row_group in Self::field(&trace.fields, 4).map_or(&[][..], Self::elements) {
+            size.row_groups.push(row_group.bytes.len());
+            row_group_fields += row_group.bytes.len();
+            let ThriftValue::Struct(fields) = &row_group.value else {
+                continue;
+            };
+            let chunks = Self::field(fields, 1).map_or(&[][..], Self::elements);
+            for (idx, chunk) in chunks.iter().enumerate() {
+                row_group_fields -= chunk.bytes.len();
+                if size.columns.len() <= idx {
+                    size.columns.push(ColumnFooterSize {
+                        path: format!("Column {idx}"),
+                        ..Default::default()
+                    });
+                }
+                size.columns[idx].add_chunk(&chunk.value, chunk.bytes.len());
+            }
+        }
+        if let Some(key_values) = Self::field(&trace.fields, 5) {
+            for key_value in Self::elements(key_values) {
+                let key = match &key_value.value {
+                    ThriftValue::Struct(fields) => Self::field(fields, 1)
+                        .and_then(|key| match &key.value.value {
+                            ThriftValue::Binary(key) => Some(String::from_utf8_lossy(key)),
+                            _ => None,
+                        })
+                        .map_or(String::from("N/A"), |key| key.into_owned()),
+                    _ => String::from("N/A"),
+                };
+                size.key_values.push((key, key_value.bytes.len()));
+            }
+        }
+
+        let sum = |bytes: fn(&ColumnFooterSize) -> usize| size.columns.iter().map(bytes).sum();
+        let mut components = vec![
+            (
+                FooterComponent::SchemaElements,
+                schema.map_or(0, |field| field.bytes().len()),
+            ),
+            (FooterComponent::ColumnMetadata, sum(|c| c.metadata)),
+            (FooterComponent::Statistics, sum(|c| c.statistics)),
+            (FooterComponent::EncodingStats, sum(|c| c.encoding_stats)),
+            (FooterComponent::SizeStatistics, sum(|c| c.size_statistics)),
+            (FooterComponent::RowGroupFields, row_group_fields),
+            (
+                FooterComponent::KeyValueMetadata,
+                Self::field(&trace.fields, 5).map_or(0, |field| field.bytes().len()),
+            ),
+        ];
+        let other = size.total - components.iter().map(|(_, bytes)| bytes).sum::<usize>();
+        components.push((FooterComponent::Other, other));
+        size.components = components;
+        size.suggestions = size.suggest();
+        size
+    }
+
+    pub fn bytes(&self, component: FooterComponent) -> usize {
+        self.components
+            .iter()
+            .find(|(c, _)| *c == component)
+            .map_or(0, |(_, bytes)| *bytes)
+    }
+
+    pub fn share(&self, bytes: usize) -> f64 {
+        bytes as f64 / self.total.max(1) as f64
+    }
+
+    /// Ways to make the footer smaller, for components that take up a large part of it.
+    fn suggest(&self) -> Vec<String> {
+        let mut suggestions = vec![];
+        let share = |component| self.share(self.bytes(component));
+
+        let statistics = share(FooterComponent::Statistics);
+        if statistics > LARGE_COMPONENT_SHARE {
+            let mut wide: Vec<_> = self
+                .columns
+                .iter()
+                .filter(|c| c.statistics / c.num_chunks.max(1) >= WIDE_STATISTICS_BYTES)
+                .collect();
+            wide.sort_by_key(|c| std::cmp::Reverse(c.statistics));
+            let mut suggestion =
+                format!("Statistics take {:.1}% of the footer.", 100. * statistics);
+            if wide.is_empty() {
+                suggestion += " Writing them only for the columns used in filters makes the \
+                               footer smaller.";
+            } else {
+                let names = wide
+                    .iter()
+                    .take(MAX_SUGGESTED_COLUMNS)
+                    .map(|c| {
+                        format!(
+                            "`{}` ({} bytes per chunk)",
+                            c.path,
+                            c.statistics / c.num_chunks.max(1)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                suggestion += &format!(
+                    " Wide columns such as {names} could be written without statistics, or \
+                     with truncated min and max values."
+                );
+            }
+            suggestions.push(suggestion);
+        }
+
+        let encoding_stats = share(FooterComponent::EncodingStats);
+        if encoding_stats > LARGE_AUXILIARY_SHARE {
+            suggestions.push(format!(
+                "Encoding stats take {:.1}% of the footer. Readers only use them to tell whether \
+                 every page is dictionary encoded, so they can be left out.",
+                100. * encoding_stats
+            ));
+        }
+
+        let size_statistics = share(FooterComponent::SizeStatistics);
+        if size_statistics > LARGE_AUXILIARY_SHARE {
+            suggestions.push(format!(
+                "Size statistics take {:.1}% of the footer. Their level histograms grow with the \
+                 nesting of each column, so they can be left out if readers don't use them.",
+                100. * size_statistics
+            ));
+        }
+
+        let key_values = share(FooterComponent::KeyValueMetadata);
+        if key_values > LARGE_COMPONENT_SHARE {
+            let mut suggestion = format!(
+                "Key value metadata takes {:.1}% of the footer.",
+                100. * key_values
+            );
+            if let Some((key, bytes)) = self.key_values.iter().max_by_key(|(_, bytes)| *bytes) {
+                suggestion += &format!(
+                    " `{key}` takes {bytes} bytes, metadata like an embedded Arrow schema can \
+                     often be left out and rebuilt from the parquet schema."
+                );
+            }
+            suggestions.push(suggestion);
+        }
+
+        let schema = share(FooterComponent::SchemaElements);
+        if schema > LARGE_COMPONENT_SHARE {
+            suggestions.push(format!(
+                "The {} schema elements take {:.1}% of the footer. Splitting very wide tables \
+                 into several files keeps each footer small.",
+                self.num_schema_elements,
+                100. * schema
+            ));
+        }
+
+        let column_chunks =
+            share(FooterComponent::ColumnMetadata) + statistics + encoding_stats + size_statistics;
+        if self.row_groups.len() > MANY_ROW_GROUPS && column_chunks > 2. * LARGE_COMPONENT_SHARE {
+            suggestions.push(format!(
+                "Each of the {} row groups repeats the metadata of {} columns, which takes \
+                 {:.1}% of the footer. Fewer, larger row groups make the footer smaller.",
+                self.row_groups.len(),
+                self.columns.len(),
+                100. * column_chunks
+            ));
+        }
+        suggestions
+    }
+
+    fn field(fields: &[ThriftField], id: i16) -> Option<&ThriftField> {
+        fields.iter().find(|field| field.id == id)
+    }
+
+    /// Elements of a list field, which are empty if the field isn't a list.
+    fn elements(field: &ThriftField) -> &[ThriftNode] {
+        match &field.value.value {
+            ThriftValue::List(_, elements) => elements,
+            _ => &[],
+        }
+    }
+}
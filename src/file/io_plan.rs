@@ -0,0 +1,178 @@
+use std::fmt::Display;
+
+use super::{ByteInterval, ParkhayFooter};
+
+/// How a reader merges nearby byte ranges into fewer, larger requests.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coalescing {
+    /// Ranges separated by at most this many bytes are fetched in a single request
+    pub max_gap: u64,
+    /// Requests are never merged past this size, and larger ranges are split
+    pub max_request_size: u64,
+}
+
+impl Default for Coalescing {
+    /// The defaults used by the `object_store` integration of the `parquet` crate.
+    fn default() -> Self {
+        Self {
+            max_gap: 1024 * 1024,
+            max_request_size: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// The byte ranges a reader would fetch to read every column chunk of a set of columns.
+#[derive(Debug)]
+pub struct IoPlan {
+    /// Paths of the selected columns
+    pub columns: Vec<String>,
+    /// Sorted byte ranges of the selected column chunks
+    pub column_chunks: Vec<ByteInterval>,
+    pub coalescing: Option<Coalescing>,
+    /// Sorted byte ranges of the requests the reader would issue
+    pub requests: Vec<ByteInterval>,
+    pub file_size: u64,
+}
+
+impl IoPlan {
+    pub fn new(
+        footer: &ParkhayFooter,
+        columns: &[usize],
+        coalescing: Option<Coalescing>,
+        file_size: u64,
+    ) -> Self {
+        let column_paths = footer.column_paths();
+        let mut column_chunks = footer
+            .row_groups
+            .iter()
+            .flat_map(|rg| {
+                columns
+                    .iter()
+                    .filter_map(|column| rg.columns.get(*column)?.meta_data.as_ref())
+            })
+            .filter(|metadata| metadata.total_compressed_size > 0)
+            .map(|metadata| {
+                let start = metadata
+                    .dictionary_page_offset
+                    .unwrap_or(metadata.data_page_offset) as u64;
+                (start, start + metadata.total_compressed_size as u64 - 1)
+            })
+            .collect::<Vec<_>>();
+        column_chunks.sort();
+
+        let requests = match coalescing {
+            Some(coalescing) => Self::coalesce(&column_chunks, coalescing),
+            // One request per column chunk
+            None => column_chunks.clone(),
+        };
+
+        Self {
+            columns: columns
+                .iter()
+                .filter_map(|column| column_paths.get(*column))
+                .map(|path| path.string())
+                .collect(),
+            column_chunks,
+            coalescing,
+            requests,
+            file_size,
+        }
+    }
+
+    fn coalesce(ranges: &[ByteInterval], coalescing: Coalescing) -> Vec<ByteInterval> {
+        let max_request_size = coalescing.max_request_size.max(1);
+        let mut merged: Vec<ByteInterval> = vec![];
+        for &(start, end) in ranges {
+            match merged.last_mut() {
+                Some((last_start, last_end))
+                    if start <= last_end.saturating_add(coalescing.max_gap.saturating_add(1))
+                        && end.max(*last_end) - *last_start < max_request_size =>
+                {
+                    *last_end = end.max(*last_end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+
+        // Ranges that are larger than a request on their own are split
+        merged
+            .into_iter()
+            .flat_map(|(start, end)| {
+                (start..=end)
+                    .step_by(max_request_size as usize)
+                    .map(move |request_start| {
+                        (request_start, end.min(request_start + max_request_size - 1))
+                    })
+            })
+            .collect()
+    }
+
+    /// Bytes of the selected column chunks.
+    pub fn needed_bytes(&self) -> u64 {
+        self.column_chunks
+            .iter()
+            .map(|(start, end)| end - start + 1)
+            .sum()
+    }
+
+    /// Bytes of all requests, including the gaps that coalescing merged into them.
+    pub fn fetched_bytes(&self) -> u64 {
+        self.requests
+            .iter()
+            .map(|(start, end)| end - start + 1)
+            .sum()
+    }
+
+    /// Bytes fetched per byte of the selected column chunks.
+    pub fn read_amplification(&self) -> f64 {
+        match self.needed_bytes() {
+            0 => 0.,
+            needed => self.fetched_bytes() as f64 / needed as f64,
+        }
+    }
+
+    /// Bytes fetched, as a fraction of the file size.
+    pub fn file_fraction(&self) -> f64 {
+        match self.file_size {
+            0 => 0.,
+            file_size => self.fetched_bytes() as f64 / file_size as f64,
+        }
+    }
+}
+
+impl Display for IoPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Columns: {}", self.columns.join(", "))?;
+        writeln!(
+            f,
+            "Column chunks: {} ({} bytes)",
+            self.column_chunks.len(),
+            self.needed_bytes()
+        )?;
+        match self.coalescing {
+            Some(coalescing) => writeln!(
+                f,
+                "Coalescing: max gap {} bytes, max request size {} bytes",
+                coalescing.max_gap, coalescing.max_request_size
+            )?,
+            None => writeln!(f, "Coalescing: none")?,
+        }
+        writeln!(f, "Requests: {}", self.requests.len())?;
+        writeln!(f, "Bytes read: {}", self.fetched_bytes())?;
+        writeln!(
+            f,
+            "Read amplification: {:.2}x the selected column chunks",
+            self.read_amplification()
+        )?;
+        writeln!(
+            f,
+            "File fraction: {:.1}% of {} bytes",
+            self.file_fraction() * 100.,
+            self.file_size
+        )?;
+        for (idx, (start, end)) in self.requests.iter().enumerate() {
+            writeln!(f, "  {idx}: {start}..={end} ({} bytes)", end - start + 1)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,404 @@
+use std::{fmt::Display, ops::Range};
+
+use anyhow::{Context, Result, bail, ensure};
+use parquet::{
+    basic::{Compression, Encoding, Type as PhysicalType},
+    compression::{CodecOptions, create_codec},
+    format::{CompressionCodec, DataPageHeaderV2, PageHeader},
+    schema::types::ColumnDescriptor,
+};
+
+pub use byte_stream_split::ByteStreamSplit;
+pub use delta::{DeltaBinaryPacked, DeltaValues};
+pub use rle::{HybridStream, RunKind};
+
+mod byte_stream_split;
+mod delta;
+mod rle;
+
+/// One of the parts a V2 data page is split into, stored one after the other in this order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PageSegment {
+    RepetitionLevels,
+    DefinitionLevels,
+    Values,
+}
+
+impl PageSegment {
+    /// Lengths of the segments of a V2 data page, or `None` for other pages.
+    pub fn lengths(header: &PageHeader) -> Option<[(Self, u64); 3]> {
+        let header_v2 = header.data_page_header_v2.as_ref()?;
+        let repetition_length = u64::try_from(header_v2.repetition_levels_byte_length).ok()?;
+        let definition_length = u64::try_from(header_v2.definition_levels_byte_length).ok()?;
+        let values_length = u64::try_from(header.compressed_page_size)
+            .ok()?
+            .checked_sub(repetition_length + definition_length)?;
+        Some([
+            (Self::RepetitionLevels, repetition_length),
+            (Self::DefinitionLevels, definition_length),
+            (Self::Values, values_length),
+        ])
+    }
+}
+
+impl Display for PageSegment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::RepetitionLevels => write!(f, "Repetition Levels"),
+            Self::DefinitionLevels => write!(f, "Definition Levels"),
+            Self::Values => write!(f, "Values"),
+        }
+    }
+}
+
+/// The body of a data page after decompression, split into its level and value streams.
+#[derive(Clone, Debug)]
+pub struct PageBody {
+    /// Decompressed bytes of the page, levels followed by values
+    pub bytes: Vec<u8>,
+    pub codec: Compression,
+    /// Length of the prefix of `bytes` that is stored as is in the file, and can be mapped onto
+    /// the page's raw bytes
+    pub file_mapped_length: usize,
+    pub encoding: Encoding,
+    /// Bytes of the encoded values
+    pub values: Range<usize>,
+    /// Number of values that aren't null
+    pub num_non_null: usize,
+    /// Repetition levels, definition levels, and dictionary indices or booleans in that order,
+    /// for the ones the page has
+    pub streams: Vec<HybridStream>,
+    /// Structure of the values, if they use one of the delta encodings
+    pub delta: Option<DeltaValues>,
+    /// Byte streams of the values, if they use the BYTE_STREAM_SPLIT encoding
+    pub byte_stream_split: Option<ByteStreamSplit>,
+}
+
+impl PageBody {
+    pub fn new(
+        header: &PageHeader,
+        codec: CompressionCodec,
+        column: &ColumnDescriptor,
+        raw: &[u8],
+    ) -> Result<Self> {
+        let codec = Compression::try_from(codec).context("Unknown compression codec")?;
+        let max_repetition_level = column.max_rep_level() as u64;
+        let max_definition_level = column.max_def_level() as u64;
+        let mut streams = vec![];
+
+        let mut body = if let Some(header_v2) = &header.data_page_header_v2 {
+            // Levels are never compressed in V2 pages, and have no length prefix
+            let repetition_length = usize::try_from(header_v2.repetition_levels_byte_length)
+                .context("Negative repetition levels length")?;
+            let definition_length = usize::try_from(header_v2.definition_levels_byte_length)
+                .context("Negative definition levels length")?;
+            let levels_length = repetition_length
+                .checked_add(definition_length)
+                .context("Levels lengths overflow")?;
+            ensure!(
+                levels_length <= raw.len(),
+                "Levels are longer than the page ({levels_length} bytes)"
+            );
+            let is_compressed = header_v2.is_compressed.unwrap_or(true);
+            let mut bytes = raw[..levels_length].to_vec();
+            let file_mapped_length = if is_compressed && codec != Compression::UNCOMPRESSED {
+                let values_length =
+                    (header.uncompressed_page_size as usize).saturating_sub(levels_length);
+                Self::decompress(codec, &raw[levels_length..], values_length, &mut bytes)?;
+                levels_length
+            } else {
+                bytes.extend_from_slice(&raw[levels_length..]);
+                bytes.len()
+            };
+
+            let num_non_null = Self::num_non_null(header_v2)?;
+            let num_values = header_v2.num_values as usize;
+            if max_repetition_level > 0 {
+                streams.push(HybridStream::parse(
+                    "Repetition Levels",
+                    &bytes,
+                    0,
+                    0..repetition_length,
+                    rle::bit_width(max_repetition_level),
+                    num_values,
+                ));
+            }
+            if max_definition_level > 0 {
+                streams.push(HybridStream::parse(
+                    "Definition Levels",
+                    &bytes,
+                    repetition_length,
+                    repetition_length..levels_length,
+                    rle::bit_width(max_definition_level),
+                    num_values,
+                ));
+            }
+            Self {
+                values: levels_length..bytes.len(),
+                bytes,
+                codec,
+                file_mapped_length,
+                encoding: Encoding::try_from(header_v2.encoding)?,
+                num_non_null,
+                streams,
+                delta: None,
+                byte_stream_split: None,
+            }
+        } else if let Some(header_v1) = &header.data_page_header {
+            let mut bytes = vec![];
+            let file_mapped_length = if codec == Compression::UNCOMPRESSED {
+                bytes.extend_from_slice(raw);
+                bytes.len()
+            } else {
+                let length = header.uncompressed_page_size as usize;
+                Self::decompress(codec, raw, length, &mut bytes)?;
+                0
+            };
+
+            // Levels are prefixed by their length in V1 pages
+            let num_values =
+                usize::try_from(header_v1.num_values).context("Negative number of values")?;
+            let mut offset = 0;
+            for (name, max_level, encoding) in [
+                (
+                    "Repetition Levels",
+                    max_repetition_level,
+                    header_v1.repetition_level_encoding,
+                ),
+                (
+                    "Definition Levels",
+                    max_definition_level,
+                    header_v1.definition_level_encoding,
+                ),
+            ] {
+                if max_level == 0 {
+                    continue;
+                }
+                if Encoding::try_from(encoding)? != Encoding::RLE {
+                    bail!("{name} use the deprecated BIT_PACKED encoding");
+                }
+                let stream = Self::length_prefixed(&bytes, offset, name)?;
+                streams.push(HybridStream::parse(
+                    name,
+                    &bytes,
+                    offset,
+                    stream.clone(),
+                    rle::bit_width(max_level),
+                    num_values,
+                ));
+                offset = stream.end;
+            }
+
+            let num_non_null = match streams.last() {
+                Some(stream) if max_definition_level > 0 => stream.count(max_definition_level),
+                _ => num_values,
+            };
+            Self {
+                values: offset..bytes.len(),
+                bytes,
+                codec,
+                file_mapped_length,
+                encoding: Encoding::try_from(header_v1.encoding)?,
+                num_non_null,
+                streams,
+                delta: None,
+                byte_stream_split: None,
+            }
+        } else {
+            bail!("Only data pages have levels and values");
+        };
+        body.parse_values(column)?;
+        Ok(body)
+    }
+
+    /// Decode a single segment of a V2 data page from its raw bytes.
+    pub fn from_segment(
+        header: &PageHeader,
+        codec: CompressionCodec,
+        column: &ColumnDescriptor,
+        segment: PageSegment,
+        raw: &[u8],
+    ) -> Result<Self> {
+        let header_v2 = header
+            .data_page_header_v2
+            .as_ref()
+            .context("Only V2 data pages are split into segments")?;
+        let num_non_null = Self::num_non_null(header_v2)?;
+        let num_values = header_v2.num_values as usize;
+        let max_level = match segment {
+            PageSegment::RepetitionLevels => column.max_rep_level(),
+            PageSegment::DefinitionLevels => column.max_def_level(),
+            PageSegment::Values => {
+                let codec = Compression::try_from(codec).context("Unknown compression codec")?;
+                let is_compressed = header_v2.is_compressed.unwrap_or(true);
+                let mut bytes = vec![];
+                let file_mapped_length = if is_compressed && codec != Compression::UNCOMPRESSED {
+                    let levels_length = header_v2
+                        .repetition_levels_byte_length
+                        .checked_add(header_v2.definition_levels_byte_length)
+                        .and_then(|length| usize::try_from(length).ok())
+                        .context("Invalid levels lengths")?;
+                    let length =
+                        (header.uncompressed_page_size as usize).saturating_sub(levels_length);
+                    Self::decompress(codec, raw, length, &mut bytes)?;
+                    0
+                } else {
+                    bytes.extend_from_slice(raw);
+                    bytes.len()
+                };
+                let mut body = Self {
+                    values: 0..bytes.len(),
+                    bytes,
+                    codec,
+                    file_mapped_length,
+                    encoding: Encoding::try_from(header_v2.encoding)?,
+                    num_non_null,
+                    streams: vec![],
+                    delta: None,
+                    byte_stream_split: None,
+                };
+                body.parse_values(column)?;
+                return Ok(body);
+            }
+        };
+
+        // Levels are never compressed in V2 pages, and have no length prefix
+        let stream = HybridStream::parse(
+            match segment {
+                PageSegment::RepetitionLevels => "Repetition Levels",
+                _ => "Definition Levels",
+            },
+            raw,
+            0,
+            0..raw.len(),
+            rle::bit_width(max_level as u64),
+            num_values,
+        );
+        Ok(Self {
+            bytes: raw.to_vec(),
+            codec: Compression::UNCOMPRESSED,
+            file_mapped_length: raw.len(),
+            encoding: Encoding::RLE,
+            values: raw.len()..raw.len(),
+            num_non_null,
+            streams: vec![stream],
+            delta: None,
+            byte_stream_split: None,
+        })
+    }
+
+    /// Parse the structure of the encoded values, for the encodings that have one.
+    fn parse_values(&mut self, column: &ColumnDescriptor) -> Result<()> {
+        match self.encoding {
+            Encoding::RLE_DICTIONARY | Encoding::PLAIN_DICTIONARY if !self.values.is_empty() => {
+                // Dictionary indices are prefixed by their bit width
+                let start = self.values.start;
+                self.streams.push(HybridStream::parse(
+                    "Dictionary Indices",
+                    &self.bytes,
+                    start,
+                    start + 1..self.values.end,
+                    self.bytes[start],
+                    self.num_non_null,
+                ));
+            }
+            Encoding::RLE if column.physical_type() == PhysicalType::BOOLEAN => {
+                let stream = Self::length_prefixed(&self.bytes, self.values.start, "Booleans")?;
+                self.streams.push(HybridStream::parse(
+                    "Booleans",
+                    &self.bytes,
+                    self.values.start,
+                    stream,
+                    1,
+                    self.num_non_null,
+                ));
+            }
+            Encoding::DELTA_BINARY_PACKED
+            | Encoding::DELTA_LENGTH_BYTE_ARRAY
+            | Encoding::DELTA_BYTE_ARRAY => {
+                self.delta = DeltaValues::parse(
+                    self.encoding,
+                    &self.bytes,
+                    self.values.clone(),
+                    &column.self_type_ptr(),
+                );
+            }
+            Encoding::BYTE_STREAM_SPLIT => {
+                self.byte_stream_split = ByteStreamSplit::parse(
+                    &self.bytes,
+                    self.values.clone(),
+                    &column.self_type_ptr(),
+                );
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Number of values of a V2 data page that aren't null, once the counts are checked.
+    fn num_non_null(header_v2: &DataPageHeaderV2) -> Result<usize> {
+        let (num_values, num_nulls) = (header_v2.num_values, header_v2.num_nulls);
+        ensure!(
+            0 <= num_nulls && num_nulls <= num_values,
+            "Page has {num_nulls} nulls out of {num_values} values"
+        );
+        Ok((num_values - num_nulls) as usize)
+    }
+
+    fn decompress(
+        codec: Compression,
+        compressed: &[u8],
+        uncompressed_length: usize,
+        bytes: &mut Vec<u8>,
+    ) -> Result<()> {
+        let mut decompressor = create_codec(codec, &CodecOptions::default())?
+            .with_context(|| format!("No decompressor for {codec}"))?;
+        decompressor
+            .decompress(compressed, bytes, Some(uncompressed_length))
+            .with_context(|| format!("Couldn't decompress the page with {codec}"))?;
+        Ok(())
+    }
+
+    /// Bytes of a stream prefixed by its 4 byte little endian length.
+    fn length_prefixed(bytes: &[u8], offset: usize, name: &str) -> Result<Range<usize>> {
+        let length = bytes
+            .get(offset..offset + 4)
+            .with_context(|| format!("Missing the length of the {}", name.to_lowercase()))?;
+        let start = offset + 4;
+        let end = start + u32::from_le_bytes(length.try_into()?) as usize;
+        ensure!(
+            end <= bytes.len(),
+            "{name} end at byte {end}, past the end of the page ({} bytes)",
+            bytes.len()
+        );
+        Ok(start..end)
+    }
+}
+
+/// Read an unsigned LEB128 varint, without reading past `end`.
+fn read_uleb128(body: &[u8], pos: &mut usize, end: usize) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *body.get(*pos).filter(|_| *pos < end)?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Unpack the first `count` values, packed starting from the least significant bit.
+fn unpack(bytes: &[u8], bit_width: u8, count: usize) -> Vec<u64> {
+    let bit_width = bit_width as usize;
+    (0..count)
+        .map(|idx| {
+            (0..bit_width).fold(0u64, |value, bit| {
+                let position = idx * bit_width + bit;
+                let is_set = bytes[position / 8] >> (position % 8) & 1 == 1;
+                value | (is_set as u64) << bit
+            })
+        })
+        .collect()
+}
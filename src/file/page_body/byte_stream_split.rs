@@ -0,0 +1,105 @@
+use std::ops::Range;
+
+use parquet::{basic::Type as PhysicalType, schema::types::Type};
+
+use crate::file::{Field, format_bytes, format_plain_value};
+
+/// One of the streams holding the k-th byte of every value.
+#[derive(Clone, Debug)]
+pub struct ByteStream {
+    /// Bytes of the stream within the page body
+    pub bytes: Range<usize>,
+    /// Shannon entropy of the stream's bytes, in bits per byte
+    pub entropy: f64,
+}
+
+/// The values of a BYTE_STREAM_SPLIT encoded page, scattered into one stream per byte of the
+/// value width.
+#[derive(Clone, Debug)]
+pub struct ByteStreamSplit {
+    pub value_width: usize,
+    pub num_values: usize,
+    pub streams: Vec<ByteStream>,
+    /// Entropy of all the values' bytes taken together, in bits per byte
+    pub entropy: f64,
+    pub values: Vec<String>,
+    /// Why the values don't split evenly into streams
+    pub error: Option<String>,
+}
+
+impl ByteStreamSplit {
+    pub fn parse(body: &[u8], range: Range<usize>, field: &Field) -> Option<Self> {
+        let value_width = match field.as_ref() {
+            Type::PrimitiveType {
+                physical_type: PhysicalType::FLOAT | PhysicalType::INT32,
+                ..
+            } => 4,
+            Type::PrimitiveType {
+                physical_type: PhysicalType::DOUBLE | PhysicalType::INT64,
+                ..
+            } => 8,
+            Type::PrimitiveType {
+                physical_type: PhysicalType::FIXED_LEN_BYTE_ARRAY,
+                type_length,
+                ..
+            } => usize::try_from(*type_length).ok()?,
+            _ => return None,
+        };
+        if value_width == 0 {
+            return None;
+        }
+        let num_values = range.len() / value_width;
+        let error = (!range.len().is_multiple_of(value_width)).then(|| {
+            format!(
+                "{} bytes don't split into values of {value_width} bytes, {} are left over",
+                range.len(),
+                range.len() % value_width
+            )
+        });
+
+        let streams = (0..value_width)
+            .map(|idx| {
+                let start = range.start + idx * num_values;
+                let bytes = start..start + num_values;
+                ByteStream {
+                    entropy: entropy(&body[bytes.clone()]),
+                    bytes,
+                }
+            })
+            .collect::<Vec<_>>();
+        let values = (0..num_values)
+            .map(|idx| {
+                let value = streams
+                    .iter()
+                    .map(|stream| body[stream.bytes.start + idx])
+                    .collect::<Vec<_>>();
+                format_plain_value(field, &value).unwrap_or_else(|| format_bytes(&value))
+            })
+            .collect();
+        Some(Self {
+            value_width,
+            num_values,
+            entropy: entropy(&body[range.start..range.start + num_values * value_width]),
+            streams,
+            values,
+            error,
+        })
+    }
+}
+
+/// Shannon entropy of the bytes, in bits per byte.
+fn entropy(bytes: &[u8]) -> f64 {
+    let mut counts = [0usize; 256];
+    for byte in bytes {
+        counts[*byte as usize] += 1;
+    }
+    let total = bytes.len() as f64;
+    counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / total;
+            p * (1. / p).log2()
+        })
+        .sum()
+}
@@ -0,0 +1,289 @@
+use std::ops::Range;
+
+use parquet::basic::{Encoding, Type as PhysicalType};
+
+use super::{read_uleb128, unpack};
+use crate::file::{Field, format_bytes, format_plain_value};
+
+/// A miniblock of bit-packed deltas, relative to the block's min delta.
+#[derive(Clone, Debug)]
+pub struct DeltaMiniblock {
+    pub bit_width: u8,
+    /// Empty for the miniblocks after the last value, which aren't stored
+    pub bytes: Range<usize>,
+    pub num_values: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct DeltaBlock {
+    pub min_delta: i64,
+    /// Bytes of the whole block, starting with the min delta and miniblock bit widths
+    pub bytes: Range<usize>,
+    pub header_length: usize,
+    pub miniblocks: Vec<DeltaMiniblock>,
+}
+
+/// A DELTA_BINARY_PACKED stream of integers.
+#[derive(Clone, Debug)]
+pub struct DeltaBinaryPacked {
+    pub name: &'static str,
+    pub block_size: u64,
+    pub miniblocks_per_block: u64,
+    pub total_values: u64,
+    pub first_value: i64,
+    /// Bytes of the stream's header, which holds the fields above
+    pub header: Range<usize>,
+    pub blocks: Vec<DeltaBlock>,
+    pub values: Vec<i64>,
+    /// Bytes of the whole stream within the page body
+    pub bytes: Range<usize>,
+    /// Why the stream couldn't be parsed up to its total number of values
+    pub error: Option<String>,
+}
+
+impl DeltaBinaryPacked {
+    pub fn parse(name: &'static str, body: &[u8], range: Range<usize>) -> Self {
+        let mut stream = Self {
+            name,
+            block_size: 0,
+            miniblocks_per_block: 0,
+            total_values: 0,
+            first_value: 0,
+            header: range.start..range.start,
+            blocks: vec![],
+            values: vec![],
+            bytes: range.clone(),
+            error: None,
+        };
+        let mut pos = range.start;
+        if let Err(e) = stream.parse_blocks(body, &mut pos, range.end) {
+            stream.error = Some(e);
+        }
+        stream.bytes = range.start..pos;
+        stream
+    }
+
+    fn parse_blocks(&mut self, body: &[u8], pos: &mut usize, end: usize) -> Result<(), String> {
+        let read = |name: &str, pos: &mut usize| {
+            read_uleb128(body, pos, end).ok_or_else(|| format!("Truncated {name} at byte {pos}"))
+        };
+        self.block_size = read("block size", pos)?;
+        self.miniblocks_per_block = read("miniblock count", pos)?;
+        self.total_values = read("value count", pos)?;
+        self.first_value = zigzag(read("first value", pos)?);
+        self.header.end = *pos;
+
+        if self.block_size == 0 || !self.block_size.is_multiple_of(128) {
+            return Err(format!(
+                "Block size {} isn't a multiple of 128",
+                self.block_size
+            ));
+        }
+        if self.miniblocks_per_block == 0
+            || !(self.block_size / self.miniblocks_per_block).is_multiple_of(32)
+        {
+            return Err(format!(
+                "{} miniblocks per block don't hold a multiple of 32 values each",
+                self.miniblocks_per_block
+            ));
+        }
+        let values_per_miniblock = usize::try_from(self.block_size / self.miniblocks_per_block)
+            .map_err(|_| format!("Block size {} is too large", self.block_size))?;
+
+        if self.total_values == 0 {
+            return Ok(());
+        }
+        self.values.push(self.first_value);
+        let mut value = self.first_value;
+        let mut remaining = self.total_values as usize - 1;
+        // Every miniblock of a block has a bit width byte, so larger counts can't be stored
+        if remaining > 0 && self.miniblocks_per_block > (end - *pos) as u64 {
+            return Err(format!(
+                "{} miniblocks per block are more than the {} bytes left",
+                self.miniblocks_per_block,
+                end - *pos
+            ));
+        }
+        while remaining > 0 {
+            let block_start = *pos;
+            let min_delta = zigzag(read("min delta", pos)?);
+            let bit_widths = (*pos)
+                .checked_add(self.miniblocks_per_block as usize)
+                .filter(|widths_end| *widths_end <= end)
+                .and_then(|widths_end| body.get(*pos..widths_end))
+                .ok_or_else(|| format!("Truncated miniblock bit widths at byte {pos}"))?;
+            *pos += bit_widths.len();
+
+            let mut block = DeltaBlock {
+                min_delta,
+                bytes: block_start..*pos,
+                header_length: *pos - block_start,
+                miniblocks: vec![],
+            };
+            for bit_width in bit_widths {
+                if remaining == 0 {
+                    block.miniblocks.push(DeltaMiniblock {
+                        bit_width: *bit_width,
+                        bytes: *pos..*pos,
+                        num_values: 0,
+                    });
+                    continue;
+                }
+                if *bit_width > 64 {
+                    self.blocks.push(block);
+                    return Err(format!("Bit width {bit_width} is larger than 64"));
+                }
+                let Some(length) = values_per_miniblock
+                    .checked_mul(*bit_width as usize)
+                    .map(|bits| bits / 8)
+                else {
+                    self.blocks.push(block);
+                    return Err(format!(
+                        "Miniblock at byte {pos} of {values_per_miniblock} values is too large"
+                    ));
+                };
+                if length > end - *pos {
+                    self.blocks.push(block);
+                    return Err(format!(
+                        "Miniblock at byte {pos} needs {length} bytes, {} are left",
+                        end - *pos
+                    ));
+                }
+                let num_values = values_per_miniblock.min(remaining);
+                for delta in unpack(&body[*pos..*pos + length], *bit_width, num_values) {
+                    value = value.wrapping_add(min_delta).wrapping_add(delta as i64);
+                    self.values.push(value);
+                }
+                block.miniblocks.push(DeltaMiniblock {
+                    bit_width: *bit_width,
+                    bytes: *pos..*pos + length,
+                    num_values,
+                });
+                *pos += length;
+                remaining -= num_values;
+            }
+            block.bytes.end = *pos;
+            self.blocks.push(block);
+        }
+        Ok(())
+    }
+}
+
+/// The streams of a page encoded with one of the delta encodings, and the values they decode to.
+#[derive(Clone, Debug)]
+pub struct DeltaValues {
+    /// The values themselves, or the (prefix and suffix) lengths of byte arrays
+    pub streams: Vec<DeltaBinaryPacked>,
+    /// The concatenated bytes of byte arrays, or of their suffixes
+    pub data: Option<Range<usize>>,
+    pub values: Vec<String>,
+    pub error: Option<String>,
+}
+
+impl DeltaValues {
+    pub fn parse(
+        encoding: Encoding,
+        body: &[u8],
+        range: Range<usize>,
+        field: &Field,
+    ) -> Option<Self> {
+        let format =
+            |bytes: &[u8]| format_plain_value(field, bytes).unwrap_or_else(|| format_bytes(bytes));
+        let mut delta = Self {
+            streams: vec![],
+            data: None,
+            values: vec![],
+            error: None,
+        };
+        match encoding {
+            Encoding::DELTA_BINARY_PACKED => {
+                let stream = DeltaBinaryPacked::parse("Values", body, range);
+                delta.values = stream
+                    .values
+                    .iter()
+                    .map(|value| match field.get_physical_type() {
+                        PhysicalType::INT32 => format(&(*value as i32).to_le_bytes()),
+                        _ => format(&value.to_le_bytes()),
+                    })
+                    .collect();
+                delta.streams.push(stream);
+            }
+            Encoding::DELTA_LENGTH_BYTE_ARRAY => {
+                let lengths = DeltaBinaryPacked::parse("Lengths", body, range.clone());
+                let data = lengths.bytes.end..range.end;
+                let result = Self::split(body, data.clone(), &lengths.values);
+                delta.streams.push(lengths);
+                delta.data = Some(data);
+                match result {
+                    Ok(values) => delta.values = values.into_iter().map(format).collect(),
+                    Err(e) => delta.error = Some(e),
+                }
+            }
+            Encoding::DELTA_BYTE_ARRAY => {
+                let prefixes = DeltaBinaryPacked::parse("Prefix Lengths", body, range.clone());
+                let suffixes =
+                    DeltaBinaryPacked::parse("Suffix Lengths", body, prefixes.bytes.end..range.end);
+                let data = suffixes.bytes.end..range.end;
+                // Each value is a prefix of the previous value followed by its suffix
+                let result =
+                    Self::split(body, data.clone(), &suffixes.values).and_then(|suffixes| {
+                        let mut previous: Vec<u8> = vec![];
+                        let mut values = vec![];
+                        for (idx, (prefix_length, suffix)) in
+                            prefixes.values.iter().zip(suffixes).enumerate()
+                        {
+                            let prefix = usize::try_from(*prefix_length)
+                                .ok()
+                                .and_then(|length| previous.get(..length))
+                                .ok_or_else(|| {
+                                    format!(
+                                        "Value {idx} has an invalid prefix length {prefix_length}"
+                                    )
+                                })?;
+                            let value = [prefix, suffix].concat();
+                            values.push(format(&value));
+                            previous = value;
+                        }
+                        Ok(values)
+                    });
+                delta.streams.extend([prefixes, suffixes]);
+                delta.data = Some(data);
+                match result {
+                    Ok(values) => delta.values = values,
+                    Err(e) => delta.error = Some(e),
+                }
+            }
+            _ => return None,
+        }
+        Some(delta)
+    }
+
+    /// Split the concatenated byte arrays using their lengths.
+    fn split<'a>(
+        body: &'a [u8],
+        data: Range<usize>,
+        lengths: &[i64],
+    ) -> Result<Vec<&'a [u8]>, String> {
+        let mut pos = data.start;
+        lengths
+            .iter()
+            .enumerate()
+            .map(|(idx, length)| {
+                let end = usize::try_from(*length)
+                    .ok()
+                    .map(|length| pos + length)
+                    .filter(|end| *end <= data.end)
+                    .ok_or_else(|| {
+                        format!("Value {idx} has length {length}, past the end of the data")
+                    })?;
+                let value = &body[pos..end];
+                pos = end;
+                Ok(value)
+            })
+            .collect()
+    }
+}
+
+fn zigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
@@ -0,0 +1,171 @@
+use std::ops::Range;
+
+use super::{read_uleb128, unpack};
+
+/// How the values of a run of the RLE/bit-packed hybrid encoding are stored.
+#[derive(Clone, Debug)]
+pub enum RunKind {
+    /// A single value repeated `count` times
+    Rle { value: u64, count: usize },
+    /// Values bit-packed in groups of 8, the last group padded past the end of the stream
+    BitPacked { values: Vec<u64>, padding: usize },
+}
+
+/// A run of the RLE/bit-packed hybrid encoding.
+#[derive(Clone, Debug)]
+pub struct HybridRun {
+    pub kind: RunKind,
+    /// Bytes of the run's varint header and payload, within the page body
+    pub bytes: Range<usize>,
+    pub header_length: usize,
+}
+
+impl HybridRun {
+    pub fn num_values(&self) -> usize {
+        match &self.kind {
+            RunKind::Rle { count, .. } => *count,
+            RunKind::BitPacked { values, .. } => values.len(),
+        }
+    }
+}
+
+/// An RLE/bit-packed hybrid encoded stream of levels, dictionary indices or booleans.
+#[derive(Clone, Debug)]
+pub struct HybridStream {
+    pub name: &'static str,
+    pub bit_width: u8,
+    /// Bytes of the whole stream within the page body, including any length or bit width prefix
+    pub bytes: Range<usize>,
+    pub runs: Vec<HybridRun>,
+    /// Why the stream couldn't be parsed up to the expected number of values
+    pub error: Option<String>,
+}
+
+impl HybridStream {
+    /// Parse runs from `range` until `num_values` values have been decoded.
+    pub fn parse(
+        name: &'static str,
+        body: &[u8],
+        prefix_start: usize,
+        range: Range<usize>,
+        bit_width: u8,
+        num_values: usize,
+    ) -> Self {
+        let mut stream = Self {
+            name,
+            bit_width,
+            bytes: prefix_start..range.end,
+            runs: vec![],
+            error: None,
+        };
+        if bit_width > 64 {
+            stream.error = Some(format!("Bit width {bit_width} is larger than 64"));
+            return stream;
+        }
+
+        let mut pos = range.start;
+        let mut remaining = num_values;
+        while remaining > 0 {
+            if pos >= range.end {
+                stream.error = Some(format!("Stream ended with {remaining} values missing"));
+                break;
+            }
+            let start = pos;
+            let Some(header) = read_uleb128(body, &mut pos, range.end) else {
+                stream.error = Some(format!("Truncated run header at byte {start}"));
+                break;
+            };
+            let header_length = pos - start;
+
+            let kind = if header & 1 == 1 {
+                let groups = (header >> 1) as usize;
+                // The group count isn't bounded, so its byte and value counts can overflow
+                let (Some(length), Some(count)) = (
+                    groups.checked_mul(bit_width as usize),
+                    groups.checked_mul(8),
+                ) else {
+                    stream.error = Some(format!(
+                        "Bit-packed run at byte {start} has too many groups ({groups})"
+                    ));
+                    break;
+                };
+                if length > range.end - pos {
+                    stream.error = Some(format!(
+                        "Bit-packed run at byte {start} needs {length} bytes, {} are left",
+                        range.end - pos
+                    ));
+                    break;
+                }
+                // Zero-width values take no bytes, so only the values left bound their groups
+                if bit_width == 0 && groups > remaining.div_ceil(8) {
+                    stream.error = Some(format!(
+                        "Bit-packed run at byte {start} has {groups} groups of zero-width values, \
+                         {remaining} values are left"
+                    ));
+                    break;
+                }
+                let used = count.min(remaining);
+                let values = unpack(&body[pos..pos + length], bit_width, used);
+                pos += length;
+                remaining -= used;
+                RunKind::BitPacked {
+                    values,
+                    padding: count - used,
+                }
+            } else {
+                let count = (header >> 1) as usize;
+                let width = (bit_width as usize).div_ceil(8);
+                if width > range.end - pos {
+                    stream.error = Some(format!("Truncated RLE run value at byte {pos}"));
+                    break;
+                }
+                let value = body[pos..pos + width]
+                    .iter()
+                    .rev()
+                    .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+                pos += width;
+                if count == 0 {
+                    stream.error = Some(format!("Empty RLE run at byte {start}"));
+                    break;
+                }
+                let used = count.min(remaining);
+                remaining -= used;
+                RunKind::Rle { value, count: used }
+            };
+            stream.runs.push(HybridRun {
+                kind,
+                bytes: start..pos,
+                header_length,
+            });
+        }
+        stream
+    }
+
+    pub fn num_values(&self) -> usize {
+        self.runs.iter().map(HybridRun::num_values).sum()
+    }
+
+    /// Number of decoded values equal to `target`.
+    pub fn count(&self, target: u64) -> usize {
+        self.runs
+            .iter()
+            .map(|run| match &run.kind {
+                RunKind::Rle { value, count } => {
+                    if *value == target {
+                        *count
+                    } else {
+                        0
+                    }
+                }
+                RunKind::BitPacked { values, .. } => {
+                    values.iter().filter(|value| **value == target).count()
+                }
+            })
+            .sum()
+    }
+}
+
+/// Number of bits needed to store values up to `max`.
+pub fn bit_width(max: u64) -> u8 {
+    (64 - max.leading_zeros()) as u8
+}
@@ -0,0 +1,90 @@
+use std::ops::AddAssign;
+
+use parquet::format::PageHeader;
+
+use super::{ByteInterval, ParkhayDataSection, SectionMap, UnknownField};
+
+/// A page header along with where its thrift encoding is stored, right before the page's data.
+#[derive(Debug)]
+pub struct EncodedPageHeader {
+    pub header: PageHeader,
+    pub byte_interval: ByteInterval,
+    /// Fields of the header missing from the format's schema
+    pub unknown_fields: Vec<UnknownField>,
+}
+
+impl EncodedPageHeader {
+    pub fn new(
+        header: PageHeader,
+        header_start: u64,
+        data_start: u64,
+        unknown_fields: Vec<UnknownField>,
+    ) -> Self {
+        Self {
+            header,
+            byte_interval: (header_start, data_start - 1),
+            unknown_fields,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.byte_interval.1 - self.byte_interval.0 + 1
+    }
+}
+
+/// Bytes spent on page headers, compared to the bytes of the pages they describe.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HeaderOverhead {
+    pub num_pages: usize,
+    pub header_bytes: u64,
+    pub data_bytes: u64,
+    /// Pages whose header is larger than their data
+    pub num_oversized: usize,
+}
+
+impl HeaderOverhead {
+    /// Overhead of the pages in `sections` and all of their subsections.
+    pub fn of(sections: &SectionMap) -> Self {
+        let mut overhead = Self::default();
+        for section in sections.values() {
+            match section {
+                ParkhayDataSection::Page(_, header, _, _) => {
+                    let data_bytes = header.header.compressed_page_size.max(0) as u64;
+                    overhead += Self {
+                        num_pages: 1,
+                        header_bytes: header.size(),
+                        data_bytes,
+                        num_oversized: (header.size() > data_bytes) as usize,
+                    };
+                }
+                ParkhayDataSection::Root(sections)
+                | ParkhayDataSection::RowGroup(_, sections)
+                | ParkhayDataSection::ColumnChunk(_, sections, _)
+                | ParkhayDataSection::RecoveredColumnChunk(_, sections) => {
+                    overhead += Self::of(sections);
+                }
+                _ => {}
+            }
+        }
+        overhead
+    }
+
+    /// Fraction of the pages' bytes, headers included, taken up by their headers.
+    pub fn ratio(&self) -> f64 {
+        let total = self.header_bytes + self.data_bytes;
+        if total == 0 {
+            0.
+        } else {
+            self.header_bytes as f64 / total as f64
+        }
+    }
+}
+
+impl AddAssign for HeaderOverhead {
+    fn add_assign(&mut self, other: Self) {
+        self.num_pages += other.num_pages;
+        self.header_bytes += other.header_bytes;
+        self.data_bytes += other.data_bytes;
+        self.num_oversized += other.num_oversized;
+    }
+}
@@ -0,0 +1,641 @@
+use std::cmp::Ordering;
+
+use parquet::{
+    basic::{ConvertedType, LogicalType, TimeUnit, Type as PhysicalType},
+    schema::types::Type,
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::Field;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+/// Julian day number of the Unix epoch, used by INT96 timestamps
+const JULIAN_DAY_OF_EPOCH: i64 = 2_440_588;
+
+/// Format a single plain-encoded value of the given leaf column, as found in statistics and
+/// column indexes. Returns `None` if the bytes don't fit the column's physical type.
+pub fn format_plain_value(field: &Field, bytes: &[u8]) -> Option<String> {
+    if !field.is_primitive() {
+        return None;
+    }
+
+    let logical_type = logical_type(field);
+    match field.get_physical_type() {
+        PhysicalType::BOOLEAN => match bytes {
+            [0] => Some(String::from("false")),
+            [1] => Some(String::from("true")),
+            _ => None,
+        },
+        PhysicalType::INT32 => {
+            let value = i32::from_le_bytes(bytes.try_into().ok()?);
+            Some(match logical_type {
+                Some(LogicalType::Date) => format_date(value.into()),
+                Some(LogicalType::Decimal { scale, .. }) => format_decimal(value.into(), scale),
+                Some(LogicalType::Time { unit, .. }) => format_time(value.into(), &unit),
+                Some(LogicalType::Integer {
+                    is_signed: false, ..
+                }) => (value as u32).to_string(),
+                _ => value.to_string(),
+            })
+        }
+        PhysicalType::INT64 => {
+            let value = i64::from_le_bytes(bytes.try_into().ok()?);
+            Some(match logical_type {
+                Some(LogicalType::Decimal { scale, .. }) => format_decimal(value.into(), scale),
+                Some(LogicalType::Time { unit, .. }) => format_time(value, &unit),
+                Some(LogicalType::Timestamp {
+                    is_adjusted_to_u_t_c,
+                    unit,
+                }) => format_timestamp(value, &unit, is_adjusted_to_u_t_c),
+                Some(LogicalType::Integer {
+                    is_signed: false, ..
+                }) => (value as u64).to_string(),
+                _ => value.to_string(),
+            })
+        }
+        PhysicalType::INT96 => {
+            let bytes: [u8; 12] = bytes.try_into().ok()?;
+            let nanos_of_day = i64::from_le_bytes(bytes[..8].try_into().ok()?);
+            let julian_day = i32::from_le_bytes(bytes[8..].try_into().ok()?);
+            let days = i64::from(julian_day) - JULIAN_DAY_OF_EPOCH;
+            let nanos = days
+                .checked_mul(SECONDS_PER_DAY * 1_000_000_000)?
+                .checked_add(nanos_of_day)?;
+            Some(format_timestamp(
+                nanos,
+                &TimeUnit::NANOS(Default::default()),
+                false,
+            ))
+        }
+        PhysicalType::FLOAT => Some(f32::from_le_bytes(bytes.try_into().ok()?).to_string()),
+        PhysicalType::DOUBLE => Some(f64::from_le_bytes(bytes.try_into().ok()?).to_string()),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            if let Type::PrimitiveType {
+                physical_type: PhysicalType::FIXED_LEN_BYTE_ARRAY,
+                type_length,
+                ..
+            } = field.as_ref()
+                && bytes.len() != *type_length as usize
+            {
+                return None;
+            }
+            Some(match logical_type {
+                Some(LogicalType::String | LogicalType::Enum | LogicalType::Json) => {
+                    match std::str::from_utf8(bytes) {
+                        Ok(value) => format!("{value:?}"),
+                        Err(_) => format_bytes(bytes),
+                    }
+                }
+                Some(LogicalType::Decimal { scale, .. }) if bytes.len() <= 16 => {
+                    format_decimal(big_endian_to_i128(bytes), scale)
+                }
+                Some(LogicalType::Uuid) if bytes.len() == 16 => format_uuid(bytes),
+                Some(LogicalType::Float16) if bytes.len() == 2 => {
+                    float16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]])).to_string()
+                }
+                _ => format_bytes(bytes),
+            })
+        }
+    }
+}
+
+/// Parse a value typed by the user the way `format_plain_value` displays it, and encode it plain.
+/// Integers are accepted for dates, times and timestamps, and `0x`-prefixed hex for byte arrays.
+pub fn parse_plain_value(field: &Field, text: &str) -> Result<Vec<u8>> {
+    if !field.is_primitive() {
+        bail!("{} is not a leaf column", field.name());
+    }
+
+    let text = text.trim();
+    let logical_type = logical_type(field);
+    let bytes = match field.get_physical_type() {
+        PhysicalType::BOOLEAN => match text.to_lowercase().as_str() {
+            "true" => vec![1],
+            "false" => vec![0],
+            _ => bail!("Expected true or false"),
+        },
+        PhysicalType::INT32 => {
+            let value = match logical_type {
+                Some(LogicalType::Date) if !is_integer(text) => parse_date(text)?,
+                Some(LogicalType::Decimal { scale, .. }) => parse_decimal(text, scale)?,
+                Some(LogicalType::Time { unit, .. }) if !is_integer(text) => {
+                    parse_time(text, &unit)?
+                }
+                Some(LogicalType::Integer {
+                    is_signed: false, ..
+                }) => i128::from(text.parse::<u32>()?),
+                _ => i128::from(text.parse::<i32>()?),
+            };
+            i32::try_from(value)
+                .ok()
+                .or_else(|| u32::try_from(value).ok().map(|v| v as i32))
+                .context("Value doesn't fit in 32 bits")?
+                .to_le_bytes()
+                .to_vec()
+        }
+        PhysicalType::INT64 => {
+            let value = match logical_type {
+                Some(LogicalType::Decimal { scale, .. }) => parse_decimal(text, scale)?,
+                Some(LogicalType::Time { unit, .. }) if !is_integer(text) => {
+                    parse_time(text, &unit)?
+                }
+                Some(LogicalType::Timestamp { unit, .. }) if !is_integer(text) => {
+                    parse_timestamp(text, &unit)?
+                }
+                Some(LogicalType::Integer {
+                    is_signed: false, ..
+                }) => i128::from(text.parse::<u64>()?),
+                _ => i128::from(text.parse::<i64>()?),
+            };
+            i64::try_from(value)
+                .ok()
+                .or_else(|| u64::try_from(value).ok().map(|v| v as i64))
+                .context("Value doesn't fit in 64 bits")?
+                .to_le_bytes()
+                .to_vec()
+        }
+        PhysicalType::INT96 => {
+            let nanos = i64::try_from(parse_timestamp(text, &TimeUnit::NANOS(Default::default()))?)
+                .context("Timestamp out of range")?;
+            let per_day = SECONDS_PER_DAY * 1_000_000_000;
+            let julian_day = i32::try_from(nanos.div_euclid(per_day) + JULIAN_DAY_OF_EPOCH)
+                .context("Timestamp out of range")?;
+            let mut bytes = nanos.rem_euclid(per_day).to_le_bytes().to_vec();
+            bytes.extend(julian_day.to_le_bytes());
+            bytes
+        }
+        PhysicalType::FLOAT => text.parse::<f32>()?.to_le_bytes().to_vec(),
+        PhysicalType::DOUBLE => text.parse::<f64>()?.to_le_bytes().to_vec(),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            let type_length = match field.as_ref() {
+                Type::PrimitiveType {
+                    physical_type: PhysicalType::FIXED_LEN_BYTE_ARRAY,
+                    type_length,
+                    ..
+                } => Some(*type_length as usize),
+                _ => None,
+            };
+            let bytes = match logical_type {
+                Some(LogicalType::Decimal { scale, .. }) => {
+                    let unscaled = parse_decimal(text, scale)?.to_be_bytes();
+                    let minimal_len = minimal_twos_complement_len(&unscaled);
+                    let len = type_length.unwrap_or(minimal_len);
+                    if minimal_len > len {
+                        bail!("Decimal too large for {len} bytes");
+                    }
+                    // Sign-extend past the 16 bytes of the unscaled value
+                    let sign = if unscaled[0] & 0x80 != 0 { 0xff } else { 0x00 };
+                    let mut bytes = vec![sign; len.saturating_sub(16)];
+                    bytes.extend_from_slice(&unscaled[16 - len.min(16)..]);
+                    bytes
+                }
+                Some(LogicalType::Uuid) => {
+                    parse_hex(&text.replace('-', "")).context("Expected a UUID")?
+                }
+                Some(LogicalType::Float16) => {
+                    f32_to_float16(text.parse::<f32>()?).to_le_bytes().to_vec()
+                }
+                _ => parse_raw_bytes(text)?,
+            };
+            if let Some(type_length) = type_length
+                && bytes.len() != type_length
+            {
+                bail!("Expected {type_length} bytes, found {}", bytes.len());
+            }
+            bytes
+        }
+    };
+    Ok(bytes)
+}
+
+/// Bytes typed as `0x`-prefixed hex, or otherwise as UTF-8 text.
+pub fn parse_raw_bytes(text: &str) -> Result<Vec<u8>> {
+    match text.strip_prefix("0x") {
+        Some(hex) => parse_hex(hex),
+        None => Ok(text.as_bytes().to_vec()),
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Vec<u8>> {
+    let hex = hex.replace(' ', "");
+    // Checked first, as pairs of digits are sliced by byte
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        bail!("Invalid hex digit");
+    }
+    if !hex.len().is_multiple_of(2) {
+        bail!("Expected an even number of hex digits");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).context("Invalid hex digit"))
+        .collect()
+}
+
+fn is_integer(text: &str) -> bool {
+    text.parse::<i64>().is_ok()
+}
+
+/// Parse `YYYY-MM-DD` to days since the Unix epoch.
+/// Components are widened to i128, so that values out of range of their type are caught by the
+/// caller instead of overflowing.
+fn parse_date(text: &str) -> Result<i128> {
+    let mut parts = text
+        .splitn(3, '-')
+        .map(|part| part.parse::<i64>().map(i128::from));
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(year)), Some(Ok(month)), Some(Ok(day)))
+            if (1..=12).contains(&month) && (1..=31).contains(&day) =>
+        {
+            Ok(days_from_civil(year, month, day))
+        }
+        _ => bail!("Expected a date formatted as YYYY-MM-DD"),
+    }
+}
+
+/// Parse `HH:MM[:SS[.fraction]]` to units since midnight, widened to i128 like dates.
+fn parse_time(text: &str, unit: &TimeUnit) -> Result<i128> {
+    let (time, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let mut parts = time
+        .split(':')
+        .map(|part| part.parse::<i64>().map(i128::from));
+    let (hours, minutes, seconds) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(h)), Some(Ok(m)), None, None) => (h, m, 0),
+        (Some(Ok(h)), Some(Ok(m)), Some(Ok(s)), None) => (h, m, s),
+        _ => bail!("Expected a time formatted as HH:MM:SS.fraction"),
+    };
+
+    let per_second = units_per_second(unit);
+    let digits = per_second.ilog10() as usize;
+    if fraction.len() > digits || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        bail!("Expected at most {digits} fractional digits");
+    }
+    let fraction = format!("{fraction:0<digits$}").parse::<i128>().unwrap_or(0);
+    Ok(((hours * 60 + minutes) * 60 + seconds) * i128::from(per_second) + fraction)
+}
+
+/// Parse `YYYY-MM-DD[THH:MM:SS.fraction][Z]` to units since the Unix epoch.
+fn parse_timestamp(text: &str, unit: &TimeUnit) -> Result<i128> {
+    let text = text.strip_suffix('Z').unwrap_or(text);
+    let (date, time) = text.split_once(['T', ' ']).unwrap_or((text, "00:00"));
+    let days = parse_date(date)?;
+    let time = parse_time(time, unit)?;
+    Ok(days * i128::from(units_per_second(unit) * SECONDS_PER_DAY) + time)
+}
+
+/// Parse a decimal number to its unscaled value.
+fn parse_decimal(text: &str, scale: i32) -> Result<i128> {
+    let scale = usize::try_from(scale).context("Negative scales aren't supported")?;
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(digits) => (-1, digits),
+        None => (1, text),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if fraction.len() > scale {
+        bail!("Expected at most {scale} fractional digits");
+    }
+    let unscaled = format!("{integer}{fraction:0<scale$}")
+        .parse::<i128>()
+        .context("Expected a decimal number")?;
+    Ok(sign * unscaled)
+}
+
+/// The fewest big-endian bytes that represent the same two's complement integer.
+fn minimal_twos_complement_len(bytes: &[u8; 16]) -> usize {
+    let mut start = 0;
+    while start < 15 {
+        let redundant = (bytes[start] == 0 && bytes[start + 1] & 0x80 == 0)
+            || (bytes[start] == 0xff && bytes[start + 1] & 0x80 != 0);
+        if !redundant {
+            break;
+        }
+        start += 1;
+    }
+    16 - start
+}
+
+/// Compare two plain-encoded values of the given leaf column using its sort order. Returns `None`
+/// if the values don't fit the column's physical type or the type has no defined order.
+pub fn compare_plain_values(field: &Field, a: &[u8], b: &[u8]) -> Option<Ordering> {
+    if !field.is_primitive() {
+        return None;
+    }
+
+    let is_unsigned = matches!(
+        logical_type(field),
+        Some(LogicalType::Integer {
+            is_signed: false,
+            ..
+        })
+    );
+    match field.get_physical_type() {
+        PhysicalType::BOOLEAN => Some(a.first()?.cmp(b.first()?)),
+        PhysicalType::INT32 if is_unsigned => Some(
+            u32::from_le_bytes(a.try_into().ok()?).cmp(&u32::from_le_bytes(b.try_into().ok()?)),
+        ),
+        PhysicalType::INT32 => Some(
+            i32::from_le_bytes(a.try_into().ok()?).cmp(&i32::from_le_bytes(b.try_into().ok()?)),
+        ),
+        PhysicalType::INT64 if is_unsigned => Some(
+            u64::from_le_bytes(a.try_into().ok()?).cmp(&u64::from_le_bytes(b.try_into().ok()?)),
+        ),
+        PhysicalType::INT64 => Some(
+            i64::from_le_bytes(a.try_into().ok()?).cmp(&i64::from_le_bytes(b.try_into().ok()?)),
+        ),
+        // The order of INT96 values is undefined
+        PhysicalType::INT96 => None,
+        PhysicalType::FLOAT => f32::from_le_bytes(a.try_into().ok()?)
+            .partial_cmp(&f32::from_le_bytes(b.try_into().ok()?)),
+        PhysicalType::DOUBLE => f64::from_le_bytes(a.try_into().ok()?)
+            .partial_cmp(&f64::from_le_bytes(b.try_into().ok()?)),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            match logical_type(field) {
+                Some(LogicalType::Decimal { .. }) if a.len() <= 16 && b.len() <= 16 => {
+                    Some(big_endian_to_i128(a).cmp(&big_endian_to_i128(b)))
+                }
+                Some(LogicalType::Float16) if a.len() == 2 && b.len() == 2 => {
+                    float16_to_f32(u16::from_le_bytes([a[0], a[1]]))
+                        .partial_cmp(&float16_to_f32(u16::from_le_bytes([b[0], b[1]])))
+                }
+                // Unsigned lexicographic order
+                _ => Some(a.cmp(b)),
+            }
+        }
+    }
+}
+
+/// A number that orders plain-encoded values of the given leaf column the way its sort order
+/// does, for plotting. Byte arrays are only ordered by their first 8 bytes.
+pub fn plain_value_sort_key(field: &Field, bytes: &[u8]) -> Option<f64> {
+    if !field.is_primitive() {
+        return None;
+    }
+
+    let is_unsigned = matches!(
+        logical_type(field),
+        Some(LogicalType::Integer {
+            is_signed: false,
+            ..
+        })
+    );
+    let key = match field.get_physical_type() {
+        PhysicalType::BOOLEAN => f64::from(*bytes.first()?),
+        PhysicalType::INT32 if is_unsigned => f64::from(u32::from_le_bytes(bytes.try_into().ok()?)),
+        PhysicalType::INT32 => f64::from(i32::from_le_bytes(bytes.try_into().ok()?)),
+        PhysicalType::INT64 if is_unsigned => u64::from_le_bytes(bytes.try_into().ok()?) as f64,
+        PhysicalType::INT64 => i64::from_le_bytes(bytes.try_into().ok()?) as f64,
+        PhysicalType::INT96 => {
+            let bytes: [u8; 12] = bytes.try_into().ok()?;
+            let nanos_of_day = i64::from_le_bytes(bytes[..8].try_into().ok()?);
+            let julian_day = i32::from_le_bytes(bytes[8..].try_into().ok()?);
+            f64::from(julian_day) * (SECONDS_PER_DAY * 1_000_000_000) as f64 + nanos_of_day as f64
+        }
+        PhysicalType::FLOAT => f64::from(f32::from_le_bytes(bytes.try_into().ok()?)),
+        PhysicalType::DOUBLE => f64::from_le_bytes(bytes.try_into().ok()?),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            match logical_type(field) {
+                Some(LogicalType::Decimal { .. }) if bytes.len() <= 16 => {
+                    big_endian_to_i128(bytes) as f64
+                }
+                Some(LogicalType::Float16) if bytes.len() == 2 => {
+                    f64::from(float16_to_f32(u16::from_le_bytes([bytes[0], bytes[1]])))
+                }
+                _ => {
+                    let mut prefix = [0; 8];
+                    let len = bytes.len().min(8);
+                    prefix[..len].copy_from_slice(&bytes[..len]);
+                    u64::from_be_bytes(prefix) as f64
+                }
+            }
+        }
+    };
+    (!key.is_nan()).then_some(key)
+}
+
+/// Hexadecimal representation of raw bytes.
+pub fn format_bytes(bytes: &[u8]) -> String {
+    let hex = bytes
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!("[{hex}] ({} bytes)", bytes.len())
+}
+
+/// The logical type of the field, derived from its converted type for files written before
+/// logical types existed.
+fn logical_type(field: &Field) -> Option<LogicalType> {
+    let basic_info = field.get_basic_info();
+    if let Some(logical_type) = basic_info.logical_type() {
+        return Some(logical_type);
+    }
+
+    let integer = |bit_width, is_signed| LogicalType::Integer {
+        bit_width,
+        is_signed,
+    };
+    match basic_info.converted_type() {
+        ConvertedType::UTF8 => Some(LogicalType::String),
+        ConvertedType::ENUM => Some(LogicalType::Enum),
+        ConvertedType::JSON => Some(LogicalType::Json),
+        ConvertedType::DATE => Some(LogicalType::Date),
+        ConvertedType::DECIMAL => Some(LogicalType::Decimal {
+            scale: field.get_scale(),
+            precision: field.get_precision(),
+        }),
+        ConvertedType::TIME_MILLIS => Some(LogicalType::Time {
+            is_adjusted_to_u_t_c: true,
+            unit: TimeUnit::MILLIS(Default::default()),
+        }),
+        ConvertedType::TIME_MICROS => Some(LogicalType::Time {
+            is_adjusted_to_u_t_c: true,
+            unit: TimeUnit::MICROS(Default::default()),
+        }),
+        ConvertedType::TIMESTAMP_MILLIS => Some(LogicalType::Timestamp {
+            is_adjusted_to_u_t_c: true,
+            unit: TimeUnit::MILLIS(Default::default()),
+        }),
+        ConvertedType::TIMESTAMP_MICROS => Some(LogicalType::Timestamp {
+            is_adjusted_to_u_t_c: true,
+            unit: TimeUnit::MICROS(Default::default()),
+        }),
+        ConvertedType::UINT_8 => Some(integer(8, false)),
+        ConvertedType::UINT_16 => Some(integer(16, false)),
+        ConvertedType::UINT_32 => Some(integer(32, false)),
+        ConvertedType::UINT_64 => Some(integer(64, false)),
+        ConvertedType::INT_8 => Some(integer(8, true)),
+        ConvertedType::INT_16 => Some(integer(16, true)),
+        ConvertedType::INT_32 => Some(integer(32, true)),
+        ConvertedType::INT_64 => Some(integer(64, true)),
+        _ => None,
+    }
+}
+
+fn units_per_second(unit: &TimeUnit) -> i64 {
+    match unit {
+        TimeUnit::MILLIS(_) => 1_000,
+        TimeUnit::MICROS(_) => 1_000_000,
+        TimeUnit::NANOS(_) => 1_000_000_000,
+    }
+}
+
+fn format_fraction(fraction: i64, unit: &TimeUnit) -> String {
+    match unit {
+        TimeUnit::MILLIS(_) => format!("{fraction:03}"),
+        TimeUnit::MICROS(_) => format!("{fraction:06}"),
+        TimeUnit::NANOS(_) => format!("{fraction:09}"),
+    }
+}
+
+fn format_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn format_time(value: i64, unit: &TimeUnit) -> String {
+    let per_second = units_per_second(unit);
+    let seconds = value.div_euclid(per_second);
+    let fraction = value.rem_euclid(per_second);
+    format!(
+        "{:02}:{:02}:{:02}.{}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        format_fraction(fraction, unit)
+    )
+}
+
+fn format_timestamp(value: i64, unit: &TimeUnit, is_adjusted_to_utc: bool) -> String {
+    let per_day = units_per_second(unit) * SECONDS_PER_DAY;
+    let days = value.div_euclid(per_day);
+    let time_of_day = value.rem_euclid(per_day);
+    let suffix = if is_adjusted_to_utc { "Z" } else { "" };
+    format!(
+        "{}T{}{suffix}",
+        format_date(days),
+        format_time(time_of_day, unit)
+    )
+}
+
+/// Convert days since the Unix epoch to a proleptic Gregorian (year, month, day).
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn format_decimal(unscaled: i128, scale: i32) -> String {
+    if scale <= 0 {
+        return format!("{unscaled}{}", "0".repeat(scale.unsigned_abs() as usize));
+    }
+    let scale = scale as usize;
+    let sign = if unscaled < 0 { "-" } else { "" };
+    let digits = format!("{:0>width$}", unscaled.unsigned_abs(), width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    format!("{sign}{integer}.{fraction}")
+}
+
+/// Sign-extend a big-endian two's complement integer of at most 16 bytes.
+fn big_endian_to_i128(bytes: &[u8]) -> i128 {
+    let fill = match bytes.first() {
+        Some(b) if *b & 0x80 != 0 => 0xff,
+        _ => 0,
+    };
+    let mut buffer = [fill; 16];
+    buffer[16 - bytes.len()..].copy_from_slice(bytes);
+    i128::from_be_bytes(buffer)
+}
+
+fn format_uuid(bytes: &[u8]) -> String {
+    let hex = bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Convert days since the Unix epoch from a proleptic Gregorian date.
+/// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_from_civil(year: i128, month: i128, day: i128) -> i128 {
+    let year = year - i128::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_from_march = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Convert a single precision float to IEEE 754 half precision, rounding towards zero.
+fn f32_to_float16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent == 0xff {
+        // Infinity or NaN
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        sign | 0x7c00
+    } else if exponent <= 0 {
+        // Subnormal, or too small to represent
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        sign | (mantissa >> (14 - exponent)) as u16
+    } else {
+        sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16
+    }
+}
+
+/// Convert an IEEE 754 half-precision float to single precision.
+fn float16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = i32::from((bits >> 10) & 0x1f);
+    let mantissa = f32::from(bits & 0x3ff);
+    let value = match exponent {
+        0 => mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    };
+    sign * value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex_rejects_non_ascii_digits() {
+        assert!(parse_hex("aé1").is_err());
+        assert!(parse_hex("0g").is_err());
+        assert_eq!(parse_hex("0a ff").unwrap(), vec![0x0a, 0xff]);
+    }
+
+    #[test]
+    fn parse_time_and_date_widen_large_values() {
+        let unit = TimeUnit::NANOS(Default::default());
+        let hours = format!("{}:00", i64::MAX);
+        assert!(parse_time(&hours, &unit).unwrap() > i128::from(i64::MAX));
+        let year = format!("{}-01-01", i64::MAX);
+        assert!(parse_date(&year).unwrap() > i128::from(i64::MAX));
+        assert!(parse_timestamp(&format!("{year}T{hours}"), &unit).is_ok());
+    }
+}
@@ -0,0 +1,589 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+};
+
+use anyhow::{Context, Result, bail};
+use parquet::{
+    basic::{ColumnOrder, SortOrder},
+    format::{ColumnIndex, ColumnMetaData, OffsetIndex},
+};
+
+use super::{
+    BloomFilterProbe, ByteInterval, Field, ParkhayDataSection, ParkhayFile, ParkhayFooter,
+    SectionIndex, SectionMap, compare_plain_values, parse_plain_value,
+};
+
+/// Comparison applied to the predicate column, with plain-encoded operands.
+#[derive(Clone, Debug)]
+pub enum PredicateOp {
+    Eq(Vec<u8>),
+    NotEq(Vec<u8>),
+    Lt(Vec<u8>),
+    LtEq(Vec<u8>),
+    Gt(Vec<u8>),
+    GtEq(Vec<u8>),
+    Between(Vec<u8>, Vec<u8>),
+    IsNull,
+    IsNotNull,
+}
+
+/// A filter on a single leaf column that a reader could push down to the file.
+#[derive(Clone, Debug)]
+pub struct Predicate {
+    /// Index of the leaf column
+    pub column: usize,
+    pub field: Field,
+    pub op: PredicateOp,
+}
+
+/// Values and nulls of a column chunk or page, as recorded in its statistics or column index.
+struct ValueRange<'a> {
+    min: Option<&'a [u8]>,
+    max: Option<&'a [u8]>,
+    null_count: Option<i64>,
+    is_all_null: bool,
+}
+
+impl<'a> ValueRange<'a> {
+    fn from_statistics(field: &Field, metadata: &'a ColumnMetaData) -> Self {
+        let statistics = metadata.statistics.as_ref();
+        // The deprecated min and max were computed with signed comparisons, so they can only be
+        //  trusted when that is also the column's sort order
+        let basic_info = field.get_basic_info();
+        let is_signed = ColumnOrder::get_sort_order(
+            basic_info.logical_type(),
+            basic_info.converted_type(),
+            field.get_physical_type(),
+        ) == SortOrder::SIGNED;
+        let (min, max) = match statistics {
+            Some(s) if s.min_value.is_some() && s.max_value.is_some() => {
+                (s.min_value.as_deref(), s.max_value.as_deref())
+            }
+            Some(s) if is_signed => (s.min.as_deref(), s.max.as_deref()),
+            _ => (None, None),
+        };
+        let null_count = statistics.and_then(|s| s.null_count);
+        Self {
+            min,
+            max,
+            null_count,
+            is_all_null: null_count == Some(metadata.num_values),
+        }
+    }
+
+    fn from_column_index(column_index: &'a ColumnIndex, page_idx: usize) -> Self {
+        let is_all_null = column_index.null_pages.get(page_idx) == Some(&true);
+        Self {
+            // Null pages have empty min/max values
+            min: (!is_all_null)
+                .then(|| column_index.min_values.get(page_idx).map(Vec::as_slice))
+                .flatten(),
+            max: (!is_all_null)
+                .then(|| column_index.max_values.get(page_idx).map(Vec::as_slice))
+                .flatten(),
+            null_count: column_index
+                .null_counts
+                .as_ref()
+                .and_then(|null_counts| null_counts.get(page_idx).copied()),
+            is_all_null,
+        }
+    }
+}
+
+impl Predicate {
+    pub const HELP: &str = "Filter on a single column, e.g.\n\
+        col = x, col != x, col < x, col <= x, col > x, col >= x\n\
+        col BETWEEN a AND b\n\
+        col IS NULL, col IS NOT NULL\n\
+        Columns are dot-separated paths or unique leaf names. Values are typed the way they are \
+        displayed, and may be quoted.";
+
+    pub fn parse(text: &str, footer: &ParkhayFooter) -> Result<Self> {
+        let text = text.trim();
+        let name_end = text
+            .find(|c: char| c.is_whitespace() || "=!<>".contains(c))
+            .context("Expected an operator after the column")?;
+        let (name, rest) = text.split_at(name_end);
+        let column = footer.find_column(name)?;
+        let field = footer.leaves()[column].clone();
+
+        let rest = rest.trim_start();
+        // ASCII uppercasing keeps byte offsets into `rest` valid
+        let upper = rest.to_ascii_uppercase();
+        let value = |text: &str| {
+            parse_plain_value(&field, Self::unquote(text))
+                .with_context(|| format!("Invalid value `{}` for {name}", text.trim()))
+        };
+        let op = if upper == "IS NULL" {
+            PredicateOp::IsNull
+        } else if upper == "IS NOT NULL" {
+            PredicateOp::IsNotNull
+        } else if let Some(bounds) = upper.strip_prefix("BETWEEN ") {
+            // Searched after the prefix, as a missing lower bound puts ` AND ` inside of it
+            let and = bounds
+                .find(" AND ")
+                .map(|and| and + 8)
+                .context("Expected `BETWEEN a AND b`")?;
+            let lower = &rest[8..and];
+            if lower.trim().is_empty() {
+                bail!("Expected a lower bound in `BETWEEN a AND b`");
+            }
+            PredicateOp::Between(value(lower)?, value(&rest[and + 5..])?)
+        } else {
+            let (op, literal) = ["<=", ">=", "!=", "<>", "=", "<", ">"]
+                .iter()
+                .find_map(|op| rest.strip_prefix(op).map(|literal| (*op, literal)))
+                .context("Expected one of =, !=, <, <=, >, >=, BETWEEN, IS NULL or IS NOT NULL")?;
+            let value = value(literal)?;
+            match op {
+                "=" => PredicateOp::Eq(value),
+                "!=" | "<>" => PredicateOp::NotEq(value),
+                "<" => PredicateOp::Lt(value),
+                "<=" => PredicateOp::LtEq(value),
+                ">" => PredicateOp::Gt(value),
+                _ => PredicateOp::GtEq(value),
+            }
+        };
+
+        Ok(Self { column, field, op })
+    }
+
+    fn unquote(text: &str) -> &str {
+        let text = text.trim();
+        for quote in ['\'', '"'] {
+            if let Some(inner) = text
+                .strip_prefix(quote)
+                .and_then(|text| text.strip_suffix(quote))
+            {
+                return inner;
+            }
+        }
+        text
+    }
+
+    /// True if no value in the range can satisfy the predicate.
+    fn rules_out(&self, range: &ValueRange) -> bool {
+        let cmp = |a: &[u8], b: &[u8]| compare_plain_values(&self.field, a, b);
+        match &self.op {
+            PredicateOp::IsNull => range.null_count == Some(0),
+            PredicateOp::IsNotNull => range.is_all_null,
+            // Comparisons are never true for nulls
+            _ if range.is_all_null => true,
+            op => {
+                let (Some(min), Some(max)) = (range.min, range.max) else {
+                    return false;
+                };
+                match op {
+                    PredicateOp::Eq(v) => {
+                        cmp(v, min) == Some(Ordering::Less)
+                            || cmp(v, max) == Some(Ordering::Greater)
+                    }
+                    PredicateOp::NotEq(v) => {
+                        cmp(min, v) == Some(Ordering::Equal) && cmp(max, v) == Some(Ordering::Equal)
+                    }
+                    PredicateOp::Lt(v) => {
+                        matches!(cmp(min, v), Some(Ordering::Greater | Ordering::Equal))
+                    }
+                    PredicateOp::LtEq(v) => cmp(min, v) == Some(Ordering::Greater),
+                    PredicateOp::Gt(v) => {
+                        matches!(cmp(max, v), Some(Ordering::Less | Ordering::Equal))
+                    }
+                    PredicateOp::GtEq(v) => cmp(max, v) == Some(Ordering::Less),
+                    PredicateOp::Between(low, high) => {
+                        cmp(high, min) == Some(Ordering::Less)
+                            || cmp(low, max) == Some(Ordering::Greater)
+                    }
+                    PredicateOp::IsNull | PredicateOp::IsNotNull => unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+/// The first structure that ruled out every row of a row group.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SkipReason {
+    Statistics,
+    BloomFilter,
+    ColumnIndex,
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::Statistics => write!(f, "column chunk statistics"),
+            SkipReason::BloomFilter => write!(f, "bloom filter"),
+            SkipReason::ColumnIndex => write!(f, "column index"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReadKind {
+    /// Pages of the projected columns
+    Data,
+    /// Bloom filters and page indexes consulted to prune
+    Index,
+}
+
+#[derive(Debug)]
+pub struct RowGroupPruning {
+    pub row_group: SectionIndex,
+    pub num_rows: i64,
+    pub skipped_by: Option<SkipReason>,
+    /// Pages of the predicate column ruled out by its column index, out of all of its pages
+    pub skipped_pages: Option<(usize, usize)>,
+    /// Rows in the pages that are left to decode
+    pub selected_rows: i64,
+    /// Bytes of the projected column chunks
+    pub total_bytes: u64,
+    /// Bytes of the projected column chunks left to read
+    pub read_bytes: u64,
+}
+
+/// Which row groups, pages and bytes a reader could skip when evaluating a predicate.
+#[derive(Debug)]
+pub struct PruningReport {
+    pub row_groups: Vec<RowGroupPruning>,
+    /// Sorted, non-overlapping byte ranges the reader would fetch
+    pub reads: Vec<(ByteInterval, ReadKind)>,
+}
+
+/// Page indexes and bloom filters of every column chunk, keyed by (row group, column).
+#[derive(Default)]
+struct ColumnChunkIndexes<'a> {
+    column_indexes: HashMap<(SectionIndex, SectionIndex), (ByteInterval, &'a ColumnIndex)>,
+    offset_indexes: HashMap<(SectionIndex, SectionIndex), (ByteInterval, &'a OffsetIndex)>,
+    /// Byte range of the bitset, after the bloom filter header
+    bloom_filters: HashMap<(SectionIndex, SectionIndex), ByteInterval>,
+}
+
+impl<'a> ColumnChunkIndexes<'a> {
+    fn collect(&mut self, sections: &'a SectionMap) {
+        for (byte_interval, section) in sections {
+            match section {
+                ParkhayDataSection::ColumnIndex(location, column_index, _) => {
+                    self.column_indexes.insert(
+                        (location.row_group, location.column),
+                        (*byte_interval, column_index),
+                    );
+                }
+                ParkhayDataSection::OffsetIndex(location, offset_index, _) => {
+                    self.offset_indexes.insert(
+                        (location.row_group, location.column),
+                        (*byte_interval, offset_index),
+                    );
+                }
+                ParkhayDataSection::BloomFilter(_, Some(location), _, _, _, _) => {
+                    self.bloom_filters
+                        .insert((location.row_group, location.column), *byte_interval);
+                }
+                ParkhayDataSection::Root(sections)
+                | ParkhayDataSection::RowGroup(_, sections)
+                | ParkhayDataSection::ColumnChunk(_, sections, _)
+                | ParkhayDataSection::RecoveredColumnChunk(_, sections) => self.collect(sections),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl PruningReport {
+    /// Simulate a reader that prunes with column chunk statistics, then bloom filters, then the
+    /// column index, and reads either every column or only the predicate column.
+    pub fn new(
+        parkhay_file: &ParkhayFile,
+        predicate: &Predicate,
+        project_all_columns: bool,
+    ) -> Result<Self> {
+        let footer = parkhay_file
+            .footer
+            .as_ref()
+            .context("Pruning needs the footer's metadata")?;
+        let mut indexes = ColumnChunkIndexes::default();
+        if let ParkhayDataSection::Root(sections) = &parkhay_file.data {
+            indexes.collect(sections);
+        }
+        let mut file = File::open(&parkhay_file.path)
+            .with_context(|| format!("Couldn't open `{}`", parkhay_file.path))?;
+
+        let mut reads = vec![];
+        let mut row_groups = vec![];
+        for (rg_idx, rg) in footer.row_groups.iter().enumerate() {
+            let rg_idx = rg_idx as SectionIndex;
+            let key = (rg_idx, predicate.column as SectionIndex);
+            let chunk_interval = |column: usize| {
+                let metadata = rg.columns.get(column)?.meta_data.as_ref()?;
+                let start = metadata
+                    .dictionary_page_offset
+                    .unwrap_or(metadata.data_page_offset) as u64;
+                let length = metadata.total_compressed_size as u64;
+                (length > 0).then(|| (start, start + length - 1))
+            };
+            let projection = if project_all_columns {
+                (0..rg.columns.len()).collect::<Vec<_>>()
+            } else {
+                vec![predicate.column]
+            };
+            let total_bytes = projection
+                .iter()
+                .filter_map(|column| chunk_interval(*column))
+                .map(|(start, end)| end - start + 1)
+                .sum();
+
+            let mut skipped_by = None;
+            let metadata = rg
+                .columns
+                .get(predicate.column)
+                .and_then(|chunk| chunk.meta_data.as_ref());
+            if let Some(metadata) = metadata
+                && predicate.rules_out(&ValueRange::from_statistics(&predicate.field, metadata))
+            {
+                skipped_by = Some(SkipReason::Statistics);
+            }
+
+            // Bloom filters can only rule out equality
+            if let (None, PredicateOp::Eq(value), Some(bitset_interval), Some(metadata)) = (
+                skipped_by,
+                &predicate.op,
+                indexes.bloom_filters.get(&key),
+                metadata,
+            ) {
+                let header_start = metadata
+                    .bloom_filter_offset
+                    .map_or(bitset_interval.0, |offset| offset as u64);
+                reads.push(((header_start, bitset_interval.1), ReadKind::Index));
+                let bitset = Self::read_interval(&mut file, *bitset_interval)?;
+                if BloomFilterProbe::new(&bitset, value)
+                    .is_some_and(|probe| !probe.is_possibly_present())
+                {
+                    skipped_by = Some(SkipReason::BloomFilter);
+                }
+            }
+
+            // Row ranges of the predicate column's pages that the column index can't rule out
+            let mut selected_rows = None;
+            let mut skipped_pages = None;
+            if let (None, Some((ci_interval, column_index)), Some((oi_interval, offset_index))) = (
+                skipped_by,
+                indexes.column_indexes.get(&key),
+                indexes.offset_indexes.get(&key),
+            ) {
+                reads.push((*ci_interval, ReadKind::Index));
+                reads.push((*oi_interval, ReadKind::Index));
+                let page_rows = Self::page_rows(offset_index, rg.num_rows);
+                let rows = page_rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(page_idx, _)| {
+                        !predicate
+                            .rules_out(&ValueRange::from_column_index(column_index, *page_idx))
+                    })
+                    .map(|(_, rows)| *rows)
+                    .collect::<Vec<_>>();
+                skipped_pages = Some((page_rows.len() - rows.len(), page_rows.len()));
+                if rows.is_empty() {
+                    skipped_by = Some(SkipReason::ColumnIndex);
+                }
+                selected_rows = Some(rows);
+            }
+
+            let num_reads = reads.len();
+            if skipped_by.is_none() {
+                for column in &projection {
+                    let Some(chunk_interval) = chunk_interval(*column) else {
+                        continue;
+                    };
+                    match (
+                        &selected_rows,
+                        indexes
+                            .offset_indexes
+                            .get(&(rg_idx, *column as SectionIndex)),
+                    ) {
+                        (Some(rows), Some((oi_interval, offset_index))) => {
+                            reads.push((*oi_interval, ReadKind::Index));
+                            Self::push_page_reads(
+                                &mut reads,
+                                chunk_interval,
+                                offset_index,
+                                rows,
+                                rg.num_rows,
+                            );
+                        }
+                        // Without page locations the whole column chunk has to be read
+                        _ => reads.push((chunk_interval, ReadKind::Data)),
+                    }
+                }
+            }
+            let read_bytes = reads[num_reads..]
+                .iter()
+                .filter(|(_, kind)| *kind == ReadKind::Data)
+                .map(|((start, end), _)| end - start + 1)
+                .sum();
+
+            row_groups.push(RowGroupPruning {
+                row_group: rg_idx,
+                num_rows: rg.num_rows,
+                skipped_by,
+                skipped_pages,
+                selected_rows: match (skipped_by, &selected_rows) {
+                    (Some(_), _) => 0,
+                    (None, Some(rows)) => rows.iter().map(|(start, end)| end - start).sum(),
+                    (None, None) => rg.num_rows,
+                },
+                total_bytes,
+                read_bytes,
+            });
+        }
+
+        Ok(Self {
+            row_groups,
+            reads: Self::merge_reads(reads),
+        })
+    }
+
+    /// Half-open row range of each page.
+    fn page_rows(offset_index: &OffsetIndex, num_rows: i64) -> Vec<(i64, i64)> {
+        let locations = &offset_index.page_locations;
+        locations
+            .iter()
+            .enumerate()
+            .map(|(page_idx, location)| {
+                let end = locations
+                    .get(page_idx + 1)
+                    .map_or(num_rows, |next| next.first_row_index);
+                (location.first_row_index, end)
+            })
+            .collect()
+    }
+
+    /// Read the dictionary page, if any, and the pages overlapping the selected rows.
+    fn push_page_reads(
+        reads: &mut Vec<(ByteInterval, ReadKind)>,
+        chunk_interval: ByteInterval,
+        offset_index: &OffsetIndex,
+        rows: &[(i64, i64)],
+        num_rows: i64,
+    ) {
+        let locations = &offset_index.page_locations;
+        // The offset index only lists data pages, the dictionary page precedes them
+        if let Some(first) = locations.first()
+            && first.offset as u64 > chunk_interval.0
+        {
+            reads.push(((chunk_interval.0, first.offset as u64 - 1), ReadKind::Data));
+        }
+        for (location, (first_row, end_row)) in locations
+            .iter()
+            .zip(Self::page_rows(offset_index, num_rows))
+        {
+            let is_selected = rows
+                .iter()
+                .any(|(start, end)| first_row < *end && *start < end_row);
+            if is_selected && location.compressed_page_size > 0 {
+                let start = location.offset as u64;
+                reads.push((
+                    (start, start + location.compressed_page_size as u64 - 1),
+                    ReadKind::Data,
+                ));
+            }
+        }
+    }
+
+    /// Sort the reads and combine overlapping or adjacent ranges of the same kind.
+    fn merge_reads(mut reads: Vec<(ByteInterval, ReadKind)>) -> Vec<(ByteInterval, ReadKind)> {
+        reads.sort_by_key(|((start, end), _)| (*start, *end));
+        let mut merged: Vec<(ByteInterval, ReadKind)> = vec![];
+        for ((start, end), kind) in reads {
+            match merged.last_mut() {
+                Some(((_, last_end), last_kind))
+                    if *last_kind == kind && start <= last_end.saturating_add(1) =>
+                {
+                    *last_end = (*last_end).max(end);
+                }
+                Some(((_, last_end), _)) if start <= *last_end => {
+                    // Overlaps a range of another kind, only keep the part after it
+                    if end > *last_end {
+                        let start = *last_end + 1;
+                        merged.push(((start, end), kind));
+                    }
+                }
+                _ => merged.push(((start, end), kind)),
+            }
+        }
+        merged
+    }
+
+    fn read_interval(file: &mut File, (start, end): ByteInterval) -> Result<Vec<u8>> {
+        file.seek(SeekFrom::Start(start))
+            .context("Failed to seek to bloom filter")?;
+        let mut bytes = vec![0u8; (end - start + 1) as usize];
+        file.read_exact(&mut bytes)
+            .context("Failed to read bloom filter")?;
+        Ok(bytes)
+    }
+
+    /// Bytes of the projected column chunks in every row group.
+    pub fn total_bytes(&self) -> u64 {
+        self.row_groups.iter().map(|rg| rg.total_bytes).sum()
+    }
+
+    pub fn read_bytes(&self, kind: ReadKind) -> u64 {
+        self.reads
+            .iter()
+            .filter(|(_, read_kind)| *read_kind == kind)
+            .map(|((start, end), _)| end - start + 1)
+            .sum()
+    }
+
+    /// True if any byte of the interval would be read.
+    pub fn is_read(&self, (start, end): ByteInterval) -> bool {
+        let idx = self
+            .reads
+            .partition_point(|((_, read_end), _)| *read_end < start);
+        self.reads
+            .get(idx)
+            .is_some_and(|((read_start, _), _)| *read_start <= end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::schema::{parser::parse_message_type, types::to_thrift};
+
+    use super::*;
+
+    fn footer() -> ParkhayFooter {
+        let schema = parse_message_type("message m { required int32 a; }").unwrap();
+        ParkhayFooter::try_from(parquet::format::FileMetaData {
+            version: 1,
+            schema: to_thrift(&schema).unwrap(),
+            num_rows: 0,
+            row_groups: vec![],
+            key_value_metadata: None,
+            created_by: None,
+            column_orders: None,
+            encryption_algorithm: None,
+            footer_signing_key_metadata: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn malformed_between_clauses_are_rejected() {
+        let footer = footer();
+        for text in [
+            "a BETWEEN AND 5",
+            "a BETWEEN  AND 5",
+            "a BETWEEN 1 AND",
+            "a BETWEEN 1",
+            "a BETWEEN ",
+        ] {
+            assert!(Predicate::parse(text, &footer).is_err(), "{text}");
+        }
+        assert!(Predicate::parse("a BETWEEN 1 AND 5", &footer).is_ok());
+    }
+}
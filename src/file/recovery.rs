@@ -0,0 +1,520 @@
+use std::{
+    fs::File,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use parquet::{
+    file::reader::ChunkReader,
+    format::{BloomFilterHeader, Encoding, PageHeader, PageType},
+    thrift::TSerializable,
+};
+use thrift::protocol::TCompactInputProtocol;
+
+use super::{
+    BLOOM_FILTER_HEADER, ByteInterval, EncodedPageHeader, PAGE_HEADER, ParkhayDataSection,
+    SectionIndex, SectionMap, ThriftTrace, UnknownField,
+};
+
+#[derive(Debug)]
+pub struct RecoveryReport {
+    /// Why the footer could not be used
+    pub reason: String,
+    /// Byte range that was walked while looking for page headers
+    pub scanned: ByteInterval,
+    /// Byte ranges inside the scanned region that could not be attributed to any page
+    pub unrecognized: Vec<ByteInterval>,
+    pub num_pages: usize,
+    pub num_column_chunks: usize,
+}
+
+#[derive(Debug)]
+struct RecoveredBloomFilter {
+    header_start: u64,
+    header: BloomFilterHeader,
+    bitset: ByteInterval,
+    unknown_fields: Vec<UnknownField>,
+}
+
+/// What the statistics and size of a page reveal about the physical type of its values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ValueWidth {
+    /// Values of a fixed-width type, this many bytes wide
+    Fixed(usize),
+    /// Byte arrays, which are prefixed by their length when plain encoded
+    Variable,
+}
+
+#[derive(Debug)]
+struct RecoveredPage {
+    header_start: u64,
+    header: PageHeader,
+    data: ByteInterval,
+    unknown_fields: Vec<UnknownField>,
+}
+
+impl RecoveredPage {
+    fn end(&self) -> u64 {
+        self.data.1
+    }
+
+    fn is_dictionary_encoded(&self) -> bool {
+        let encoding = self
+            .header
+            .data_page_header
+            .as_ref()
+            .map(|h| h.encoding)
+            .or(self.header.data_page_header_v2.as_ref().map(|h| h.encoding));
+        matches!(
+            encoding,
+            Some(Encoding::PLAIN_DICTIONARY) | Some(Encoding::RLE_DICTIONARY)
+        )
+    }
+
+    /// Encoding of the values of a data page.
+    fn value_encoding(&self) -> Option<Encoding> {
+        self.header
+            .data_page_header
+            .as_ref()
+            .map(|h| h.encoding)
+            .or(self.header.data_page_header_v2.as_ref().map(|h| h.encoding))
+    }
+
+    /// Width of the values of a data page, from the width of its min and max statistics checked
+    /// against the bytes its values take up, or those of the chunk's dictionary for dictionary
+    /// encoded pages. Missing if the page has no statistics or its encoding hides the width.
+    fn value_width(&self, dictionary: Option<&RecoveredPage>) -> Option<ValueWidth> {
+        let (num_values, statistics, values_bytes) = match (
+            &self.header.data_page_header,
+            &self.header.data_page_header_v2,
+        ) {
+            (Some(h), _) => (
+                h.num_values,
+                h.statistics.as_ref(),
+                self.header.uncompressed_page_size,
+            ),
+            (_, Some(h)) => (
+                h.num_values,
+                h.statistics.as_ref(),
+                // Garbage headers can hold level lengths that overflow
+                self.header
+                    .uncompressed_page_size
+                    .checked_sub(h.definition_levels_byte_length)?
+                    .checked_sub(h.repetition_levels_byte_length)?,
+            ),
+            _ => return None,
+        };
+        let statistics = statistics?;
+        let (min, max) = statistics
+            .min_value
+            .as_ref()
+            .zip(statistics.max_value.as_ref())
+            .or(statistics.min.as_ref().zip(statistics.max.as_ref()))?;
+        // Min and max values of fixed-width types always have the same width
+        if min.len() != max.len() {
+            return Some(ValueWidth::Variable);
+        }
+        let width = min.len() as i64;
+
+        if self.is_dictionary_encoded() {
+            // Plain encoded dictionaries of fixed-width values are exactly as large as their values
+            let dictionary = dictionary?;
+            let num_entries = dictionary
+                .header
+                .dictionary_page_header
+                .as_ref()?
+                .num_values;
+            let is_fixed =
+                dictionary.header.uncompressed_page_size as i64 == num_entries as i64 * width;
+            return Some(if is_fixed {
+                ValueWidth::Fixed(width as usize)
+            } else {
+                ValueWidth::Variable
+            });
+        }
+        match self.value_encoding()? {
+            // Fixed-width values take up their width, besides the levels stored with them, while
+            // plain encoded byte arrays also take up 4 bytes for the length of each value
+            Encoding::PLAIN | Encoding::BYTE_STREAM_SPLIT => {
+                let max_fixed_bytes = num_values as i64 * (width + 2) + 16;
+                Some(if (values_bytes as i64) <= max_fixed_bytes {
+                    ValueWidth::Fixed(width as usize)
+                } else {
+                    ValueWidth::Variable
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Walks the data region of a file without relying on the footer, decoding consecutive page
+/// headers and grouping the pages into inferred column chunks.
+pub struct RecoveryScanner<'a> {
+    file: &'a File,
+    scan_end: u64,
+}
+
+impl<'a> RecoveryScanner<'a> {
+    // Page headers are small unless they carry large min/max statistics
+    const HEADER_WINDOW: u64 = 64 * 1024;
+    const MAX_HEADER_WINDOW: u64 = 16 * 1024 * 1024;
+    // How far past an undecodable region to look for the next page header
+    const RESYNC_WINDOW: u64 = 1024 * 1024;
+
+    pub fn new(file: &'a File, scan_end: u64) -> Self {
+        Self { file, scan_end }
+    }
+
+    pub fn scan(
+        &self,
+        scan_start: u64,
+        reason: String,
+    ) -> Result<(ParkhayDataSection, RecoveryReport)> {
+        let mut chunks: Vec<Vec<RecoveredPage>> = vec![];
+        let mut current_chunk: Vec<RecoveredPage> = vec![];
+        let mut bloom_filters = vec![];
+        let mut unrecognized = vec![];
+        let mut num_pages = 0;
+
+        let mut position = scan_start;
+        while position < self.scan_end {
+            let page = match self.read_page(position) {
+                Some(page) => page,
+                None => {
+                    // Anything that isn't a page ends the current column chunk
+                    if !current_chunk.is_empty() {
+                        chunks.push(std::mem::take(&mut current_chunk));
+                    }
+
+                    // Bloom filters may be written between row groups
+                    if let Some(bloom_filter) = self.read_bloom_filter(position) {
+                        position = bloom_filter.bitset.1 + 1;
+                        bloom_filters.push(bloom_filter);
+                        continue;
+                    }
+
+                    // Treat everything up to the next plausible page header as unrecognized
+                    let gap_end = self.resync(position + 1).unwrap_or(self.scan_end);
+                    unrecognized.push((position, gap_end - 1));
+                    position = gap_end;
+                    continue;
+                }
+            };
+
+            if Self::starts_new_column_chunk(&current_chunk, &page) {
+                chunks.push(std::mem::take(&mut current_chunk));
+            }
+
+            position = page.end() + 1;
+            num_pages += 1;
+            current_chunk.push(page);
+        }
+        if !current_chunk.is_empty() {
+            chunks.push(current_chunk);
+        }
+
+        let mut root_section = ParkhayDataSection::Root(SectionMap::new());
+        let num_column_chunks = chunks.len();
+        for (cc_idx, pages) in chunks.into_iter().enumerate() {
+            let cc_start = pages.first().map(|p| p.header_start).unwrap();
+            let cc_end = pages.last().map(|p| p.end()).unwrap();
+            let mut cc_section =
+                ParkhayDataSection::RecoveredColumnChunk(cc_idx as SectionIndex, SectionMap::new());
+            for (page_idx, page) in pages.into_iter().enumerate() {
+                let segments = ParkhayDataSection::page_segments(&page.header, page.data.0);
+                cc_section.insert(
+                    page.data,
+                    ParkhayDataSection::Page(
+                        page_idx as SectionIndex,
+                        Box::new(EncodedPageHeader::new(
+                            page.header,
+                            page.header_start,
+                            page.data.0,
+                            page.unknown_fields,
+                        )),
+                        Arc::new(Mutex::new(None)),
+                        segments,
+                    ),
+                );
+            }
+            root_section.insert((cc_start, cc_end), cc_section);
+        }
+        for (bloom_filter_idx, bloom_filter) in bloom_filters.into_iter().enumerate() {
+            root_section.insert(
+                bloom_filter.bitset,
+                ParkhayDataSection::BloomFilter(
+                    bloom_filter_idx as SectionIndex,
+                    None,
+                    bloom_filter.header,
+                    (bloom_filter.header_start, bloom_filter.bitset.0 - 1),
+                    bloom_filter.unknown_fields,
+                    Arc::new(Mutex::new(None)),
+                ),
+            );
+        }
+
+        let report = RecoveryReport {
+            reason,
+            scanned: (scan_start, self.scan_end.saturating_sub(1)),
+            unrecognized,
+            num_pages,
+            num_column_chunks,
+        };
+
+        Ok((root_section, report))
+    }
+
+    /// Decode a page header at `position` and check that the page it describes fits in the file.
+    fn read_page(&self, position: u64) -> Option<RecoveredPage> {
+        let mut window = Self::HEADER_WINDOW;
+        loop {
+            let length = window.min(self.scan_end - position);
+            let bytes = self.file.get_bytes(position, length as usize).ok()?;
+            match Self::decode_page_header(&bytes) {
+                Some((header, header_length)) => {
+                    let mut page = self.validate_page(position, header, header_length)?;
+                    page.unknown_fields =
+                        ThriftTrace::decode(&bytes[..header_length as usize], Some(&PAGE_HEADER))
+                            .unknown_fields(position);
+                    return Some(page);
+                }
+                // The header may have been cut off by the window, so retry with a larger one
+                None if length == window && window < Self::MAX_HEADER_WINDOW => window *= 4,
+                None => return None,
+            }
+        }
+    }
+
+    /// Decode a bloom filter header at `position` and check that its bitset fits in the file.
+    fn read_bloom_filter(&self, position: u64) -> Option<RecoveredBloomFilter> {
+        let length = Self::HEADER_WINDOW.min(self.scan_end - position);
+        let bytes = self.file.get_bytes(position, length as usize).ok()?;
+        let (header, header_length) = Self::decode_bloom_filter_header(&bytes)?;
+        let mut bloom_filter = self.validate_bloom_filter(position, header, header_length)?;
+        bloom_filter.unknown_fields =
+            ThriftTrace::decode(&bytes[..header_length as usize], Some(&BLOOM_FILTER_HEADER))
+                .unknown_fields(position);
+        Some(bloom_filter)
+    }
+
+    /// Search forward for the next position that decodes to a plausible page or bloom filter header.
+    fn resync(&self, from: u64) -> Option<u64> {
+        let mut window_start = from;
+        while window_start < self.scan_end {
+            let length = Self::RESYNC_WINDOW.min(self.scan_end - window_start);
+            let bytes = self.file.get_bytes(window_start, length as usize).ok()?;
+            let found = (0..bytes.len()).find(|&offset| {
+                let position = window_start + offset as u64;
+                let is_page = Self::decode_page_header(&bytes[offset..]).is_some_and(
+                    |(header, header_length)| {
+                        self.validate_page(position, header, header_length)
+                            .is_some()
+                    },
+                );
+                is_page
+                    || Self::decode_bloom_filter_header(&bytes[offset..]).is_some_and(
+                        |(header, header_length)| {
+                            self.validate_bloom_filter(position, header, header_length)
+                                .is_some()
+                        },
+                    )
+            });
+            if let Some(offset) = found {
+                return Some(window_start + offset as u64);
+            }
+            window_start += length;
+        }
+        None
+    }
+
+    fn decode_page_header(bytes: &[u8]) -> Option<(PageHeader, u64)> {
+        let mut remaining = bytes;
+        let mut blob = TCompactInputProtocol::new(&mut remaining);
+        let header = PageHeader::read_from_in_protocol(&mut blob).ok()?;
+        Some((header, (bytes.len() - remaining.len()) as u64))
+    }
+
+    fn decode_bloom_filter_header(bytes: &[u8]) -> Option<(BloomFilterHeader, u64)> {
+        let mut remaining = bytes;
+        let mut blob = TCompactInputProtocol::new(&mut remaining);
+        let header = BloomFilterHeader::read_from_in_protocol(&mut blob).ok()?;
+        Some((header, (bytes.len() - remaining.len()) as u64))
+    }
+
+    fn validate_bloom_filter(
+        &self,
+        header_start: u64,
+        header: BloomFilterHeader,
+        header_length: u64,
+    ) -> Option<RecoveredBloomFilter> {
+        // Split block bloom filters are made up of 32-byte blocks
+        if header.num_bytes <= 0 || header.num_bytes % 32 != 0 {
+            return None;
+        }
+        let bitset_start = header_start + header_length;
+        let bitset_end = bitset_start + header.num_bytes as u64 - 1;
+        if bitset_end >= self.scan_end {
+            return None;
+        }
+        Some(RecoveredBloomFilter {
+            header_start,
+            header,
+            bitset: (bitset_start, bitset_end),
+            unknown_fields: vec![],
+        })
+    }
+
+    fn validate_page(
+        &self,
+        header_start: u64,
+        header: PageHeader,
+        header_length: u64,
+    ) -> Option<RecoveredPage> {
+        if header.compressed_page_size < 0 || header.uncompressed_page_size < 0 {
+            return None;
+        }
+
+        // The matching type-specific header must be present
+        let has_matching_header = match header.type_ {
+            PageType::DATA_PAGE => header.data_page_header.as_ref().is_some_and(|h| {
+                h.num_values >= 0
+                    && parquet::basic::Encoding::try_from(h.encoding).is_ok()
+                    && parquet::basic::Encoding::try_from(h.definition_level_encoding).is_ok()
+                    && parquet::basic::Encoding::try_from(h.repetition_level_encoding).is_ok()
+            }),
+            PageType::DATA_PAGE_V2 => header.data_page_header_v2.as_ref().is_some_and(|h| {
+                h.num_values >= 0
+                    && h.num_rows >= 0
+                    && h.definition_levels_byte_length >= 0
+                    && h.repetition_levels_byte_length >= 0
+                    && parquet::basic::Encoding::try_from(h.encoding).is_ok()
+            }),
+            PageType::DICTIONARY_PAGE => header.dictionary_page_header.as_ref().is_some_and(|h| {
+                h.num_values >= 0 && parquet::basic::Encoding::try_from(h.encoding).is_ok()
+            }),
+            PageType::INDEX_PAGE => true,
+            _ => false,
+        };
+        if !has_matching_header {
+            return None;
+        }
+
+        let data_start = header_start + header_length;
+        let data_end = if header.compressed_page_size > 0 {
+            data_start + header.compressed_page_size as u64 - 1
+        } else {
+            // If the page size is 0, then the end of the page byte range is the final byte of the page header
+            data_start - 1
+        };
+        if data_end >= self.scan_end {
+            return None;
+        }
+
+        Some(RecoveredPage {
+            header_start,
+            header,
+            data: (data_start, data_end),
+            unknown_fields: vec![],
+        })
+    }
+
+    /// Decide whether `page` can continue the column chunk made up of `chunk_pages`.
+    fn starts_new_column_chunk(chunk_pages: &[RecoveredPage], page: &RecoveredPage) -> bool {
+        if chunk_pages.is_empty() {
+            return false;
+        }
+
+        match page.header.type_ {
+            // A dictionary page is always the first page of a column chunk
+            PageType::DICTIONARY_PAGE => true,
+            PageType::DATA_PAGE | PageType::DATA_PAGE_V2 => {
+                let has_dictionary = chunk_pages
+                    .iter()
+                    .any(|p| p.header.type_ == PageType::DICTIONARY_PAGE);
+                let last_data_page = chunk_pages.iter().rev().find(|p| {
+                    p.header.type_ == PageType::DATA_PAGE
+                        || p.header.type_ == PageType::DATA_PAGE_V2
+                });
+
+                // Dictionary-encoded pages need a dictionary page earlier in the chunk, and writers
+                //  only ever fall back from dictionary to plain encoding, never the other way
+                if page.is_dictionary_encoded()
+                    && (!has_dictionary
+                        || last_data_page.is_some_and(|p| !p.is_dictionary_encoded()))
+                {
+                    return true;
+                }
+
+                // Writers only ever fall back from dictionary encoding to a single other encoding
+                if let Some(last) = last_data_page
+                    && !last.is_dictionary_encoded()
+                    && !page.is_dictionary_encoded()
+                    && last.value_encoding() != page.value_encoding()
+                {
+                    return true;
+                }
+
+                // Values of a column chunk all have the same physical type, so the same width
+                let dictionary = chunk_pages
+                    .iter()
+                    .find(|p| p.header.type_ == PageType::DICTIONARY_PAGE);
+                let last_width = chunk_pages
+                    .iter()
+                    .rev()
+                    .find_map(|p| p.value_width(dictionary));
+                if let (Some(last_width), Some(width)) = (last_width, page.value_width(dictionary))
+                    && last_width != width
+                {
+                    return true;
+                }
+
+                match last_data_page {
+                    // Writers don't mix data page versions within a column chunk
+                    Some(last) if last.header.type_ != page.header.type_ => true,
+                    // Level encodings are fixed for the column
+                    Some(last) => {
+                        match (&last.header.data_page_header, &page.header.data_page_header) {
+                            (Some(a), Some(b)) => {
+                                a.definition_level_encoding != b.definition_level_encoding
+                                    || a.repetition_level_encoding != b.repetition_level_encoding
+                            }
+                            _ => false,
+                        }
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Copy the raw bytes (page headers included) of every recovered column chunk into `dir`.
+pub fn export_recovered_column_chunks(
+    file: &File,
+    data: &ParkhayDataSection,
+    dir: &std::path::Path,
+) -> Result<Vec<std::path::PathBuf>> {
+    let ParkhayDataSection::Root(sections) = data else {
+        unreachable!()
+    };
+
+    std::fs::create_dir_all(dir)
+        .context(format!("Couldn't create directory `{}`", dir.display()))?;
+
+    let mut paths = vec![];
+    for ((start, end), section) in sections {
+        if let ParkhayDataSection::RecoveredColumnChunk(idx, _) = section {
+            let bytes = file
+                .get_bytes(*start, (end - start + 1) as usize)
+                .context("Couldn't read recovered column chunk")?;
+            let path = dir.join(format!("column_chunk_{idx}_{start}-{end}.bin"));
+            std::fs::write(&path, &bytes)
+                .context(format!("Couldn't write `{}`", path.display()))?;
+            paths.push(path);
+        }
+    }
+
+    Ok(paths)
+}
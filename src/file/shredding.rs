@@ -0,0 +1,330 @@
+use std::fs::File;
+
+use anyhow::{Context, Result, bail};
+use parquet::{
+    basic::Repetition,
+    column::reader::{ColumnReader, ColumnReaderImpl},
+    data_type::{AsBytes, DataType},
+    file::{reader::FileReader, serialized_reader::SerializedFileReader},
+};
+
+use super::{
+    ByteInterval, Field, ParkhayDataSection, ParkhayFile, SectionIndex, format_bytes,
+    format_plain_value,
+};
+
+/// Records skipped per read while seeking to the shredded record
+const SKIP_BATCH_SIZE: usize = 4096;
+
+/// A field on the path from the schema root to a leaf, with the levels it contributes.
+#[derive(Clone, Debug)]
+pub struct PathNode {
+    pub name: String,
+    pub repetition: Repetition,
+    /// Definition level of a record in which this field is present
+    pub definition_level: i16,
+    /// Repetition level of a value that repeats this field, if it is repeated
+    pub repetition_level: Option<i16>,
+}
+
+/// The data page holding a level.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShreddedPage {
+    pub index: SectionIndex,
+    pub byte_interval: ByteInterval,
+}
+
+/// A repetition level, definition level and value, if the value is defined, stored for a leaf.
+#[derive(Debug)]
+pub struct LevelEntry {
+    pub repetition_level: i16,
+    pub definition_level: i16,
+    pub value: Option<String>,
+    pub page: Option<ShreddedPage>,
+}
+
+/// The levels and values a record was shredded into for one leaf column.
+#[derive(Debug)]
+pub struct ShreddedColumn {
+    pub path: String,
+    pub nodes: Vec<PathNode>,
+    pub max_definition_level: i16,
+    pub max_repetition_level: i16,
+    pub entries: Vec<LevelEntry>,
+}
+
+impl ShreddedColumn {
+    fn new(nodes: Vec<PathNode>) -> Self {
+        Self {
+            path: nodes
+                .iter()
+                .map(|node| node.name.as_str())
+                .collect::<Vec<_>>()
+                .join("."),
+            max_definition_level: nodes.last().map_or(0, |node| node.definition_level),
+            max_repetition_level: nodes
+                .iter()
+                .filter_map(|node| node.repetition_level)
+                .max()
+                .unwrap_or(0),
+            nodes,
+            entries: vec![],
+        }
+    }
+
+    /// What the levels of the entry say about the record, in terms of the fields on the path.
+    pub fn explain(&self, entry: &LevelEntry) -> String {
+        let repetition = match entry.repetition_level {
+            0 => String::from("Starts the record"),
+            level => match self
+                .nodes
+                .iter()
+                .find(|node| node.repetition_level == Some(level))
+            {
+                Some(node) => format!("Repeats `{}`", node.name),
+                None => format!("Invalid repetition level {level}"),
+            },
+        };
+        let definition = if entry.definition_level == self.max_definition_level {
+            String::from("the value is defined")
+        } else {
+            // The first field that isn't defined is the one at the next definition level
+            match self
+                .nodes
+                .iter()
+                .find(|node| node.definition_level == entry.definition_level + 1)
+            {
+                Some(node) if node.repetition == Repetition::REPEATED => {
+                    format!("`{}` is empty", node.name)
+                }
+                Some(node) => format!("`{}` is null", node.name),
+                None => format!("invalid definition level {}", entry.definition_level),
+            }
+        };
+        format!("{repetition}, {definition}")
+    }
+}
+
+/// How one record of a row group was shredded into the levels and values of each leaf column.
+#[derive(Debug)]
+pub struct RecordShredding {
+    pub row_group: usize,
+    pub row: i64,
+    pub columns: Vec<ShreddedColumn>,
+}
+
+impl RecordShredding {
+    pub fn new(parkhay_file: &ParkhayFile, row_group: usize, row: i64) -> Result<Self> {
+        let footer = parkhay_file
+            .footer
+            .as_ref()
+            .context("Shredding records needs the footer's schema")?;
+        let num_rows = footer
+            .row_groups
+            .get(row_group)
+            .with_context(|| format!("Row group {row_group} doesn't exist"))?
+            .num_rows;
+        if !(0..num_rows).contains(&row) {
+            bail!("Row {row} is outside of row group {row_group}, which has {num_rows} rows");
+        }
+
+        let mut columns = vec![];
+        for field in footer.schema_root.get_fields() {
+            Self::path_nodes(field, vec![], &mut columns);
+        }
+
+        let file = File::open(&parkhay_file.path)
+            .with_context(|| format!("Couldn't open `{}`", parkhay_file.path))?;
+        let reader = SerializedFileReader::new(file).context("Couldn't read the file metadata")?;
+        let row_group_reader = reader.get_row_group(row_group)?;
+        let leaves = footer.leaves();
+        for (column_idx, (column, field)) in columns.iter_mut().zip(leaves).enumerate() {
+            let pages = Self::data_pages(&parkhay_file.data, row_group, column_idx);
+            let max_definition_level = column.max_definition_level;
+            let column_reader = row_group_reader
+                .get_column_reader(column_idx)
+                .with_context(|| format!("Couldn't read column `{}`", column.path))?;
+            column.entries = match column_reader {
+                ColumnReader::BoolColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+                ColumnReader::Int32ColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+                ColumnReader::Int64ColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+                ColumnReader::Int96ColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+                ColumnReader::FloatColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+                ColumnReader::DoubleColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+                ColumnReader::ByteArrayColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+                ColumnReader::FixedLenByteArrayColumnReader(r) => {
+                    Self::read_record(r, row, field, max_definition_level, &pages)
+                }
+            }
+            .with_context(|| format!("Couldn't shred column `{}`", column.path))?;
+        }
+
+        Ok(Self {
+            row_group,
+            row,
+            columns,
+        })
+    }
+
+    /// Collect the fields on the path to each leaf, accumulating the levels each one adds.
+    fn path_nodes(field: &Field, mut nodes: Vec<PathNode>, columns: &mut Vec<ShreddedColumn>) {
+        let (definition_level, repetition_level) = nodes.last().map_or((0, 0), |node| {
+            (
+                node.definition_level,
+                nodes
+                    .iter()
+                    .filter_map(|node| node.repetition_level)
+                    .max()
+                    .unwrap_or(0),
+            )
+        });
+        let basic_info = field.get_basic_info();
+        let repetition = if basic_info.has_repetition() {
+            basic_info.repetition()
+        } else {
+            Repetition::REQUIRED
+        };
+        nodes.push(PathNode {
+            name: field.name().to_string(),
+            repetition,
+            definition_level: match repetition {
+                Repetition::REQUIRED => definition_level,
+                _ => definition_level + 1,
+            },
+            repetition_level: (repetition == Repetition::REPEATED).then_some(repetition_level + 1),
+        });
+
+        if field.is_primitive() {
+            columns.push(ShreddedColumn::new(nodes));
+        } else {
+            for child in field.get_fields() {
+                Self::path_nodes(child, nodes.clone(), columns);
+            }
+        }
+    }
+
+    /// Data pages of the column chunk, with the number of levels each one holds.
+    fn data_pages(
+        data: &ParkhayDataSection,
+        row_group: usize,
+        column: usize,
+    ) -> Vec<(ShreddedPage, i32)> {
+        let ParkhayDataSection::Root(sections) = data else {
+            return vec![];
+        };
+        let column_chunk = sections.values().find_map(|section| match section {
+            ParkhayDataSection::RowGroup(idx, sections) if *idx as usize == row_group => {
+                sections.values().find_map(|section| match section {
+                    ParkhayDataSection::ColumnChunk(location, sections, _)
+                        if location.column as usize == column =>
+                    {
+                        Some(sections)
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        });
+        column_chunk
+            .into_iter()
+            .flatten()
+            .filter_map(|(byte_interval, section)| match section {
+                ParkhayDataSection::Page(index, header, _, _) => {
+                    let header = &header.header;
+                    let num_values = header
+                        .data_page_header
+                        .as_ref()
+                        .map(|header| header.num_values)
+                        .or(header.data_page_header_v2.as_ref().map(|h| h.num_values))?;
+                    let page = ShreddedPage {
+                        index: *index,
+                        byte_interval: *byte_interval,
+                    };
+                    Some((page, num_values))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Read the levels and values of a record, skipping the records before it to find which
+    /// levels of the column chunk it starts at.
+    fn read_record<T: DataType>(
+        mut reader: ColumnReaderImpl<T>,
+        row: i64,
+        field: &Field,
+        max_definition_level: i16,
+        pages: &[(ShreddedPage, i32)],
+    ) -> Result<Vec<LevelEntry>> {
+        let mut first_level = 0;
+        let mut remaining = row as usize;
+        while remaining > 0 {
+            let (mut definition_levels, mut repetition_levels, mut values) =
+                (vec![], vec![], vec![]);
+            let (records, _, levels) = reader.read_records(
+                remaining.min(SKIP_BATCH_SIZE),
+                Some(&mut definition_levels),
+                Some(&mut repetition_levels),
+                &mut values,
+            )?;
+            if records == 0 {
+                bail!("The column chunk ended before row {row}");
+            }
+            remaining -= records;
+            first_level += levels;
+        }
+
+        let (mut definition_levels, mut repetition_levels, mut values) = (vec![], vec![], vec![]);
+        let (_, _, levels) = reader.read_records(
+            1,
+            Some(&mut definition_levels),
+            Some(&mut repetition_levels),
+            &mut values,
+        )?;
+
+        let mut values = values.iter();
+        let entries = (0..levels)
+            .map(|level| {
+                // Levels aren't stored for columns whose max level is 0
+                let definition_level = definition_levels.get(level).copied().unwrap_or(0);
+                let value = if definition_level == max_definition_level {
+                    values.next().map(|value| {
+                        let bytes = value.as_bytes();
+                        format_plain_value(field, bytes).unwrap_or_else(|| format_bytes(bytes))
+                    })
+                } else {
+                    None
+                };
+                LevelEntry {
+                    repetition_level: repetition_levels.get(level).copied().unwrap_or(0),
+                    definition_level,
+                    value,
+                    page: Self::page_of(pages, first_level + level),
+                }
+            })
+            .collect();
+        Ok(entries)
+    }
+
+    fn page_of(pages: &[(ShreddedPage, i32)], level: usize) -> Option<ShreddedPage> {
+        let mut end = 0;
+        pages.iter().find_map(|(page, num_values)| {
+            end += *num_values as usize;
+            (level < end).then_some(*page)
+        })
+    }
+}
@@ -0,0 +1,575 @@
+use std::{fmt::Display, io::Read, ops::Range};
+
+pub use schema::{
+    BLOOM_FILTER_HEADER, COLUMN_INDEX, FILE_METADATA, OFFSET_INDEX, PAGE_HEADER, ThriftSchema,
+};
+
+use super::ByteInterval;
+
+mod schema;
+
+/// Values nested deeper than this are treated as malformed.
+const MAX_DEPTH: usize = 64;
+/// Bytes of a binary value shown in its summary, since values can be megabytes long
+const MAX_BINARY_PREVIEW: usize = 64;
+
+/// Types of the thrift compact protocol.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThriftType {
+    Bool,
+    Byte,
+    I16,
+    I32,
+    I64,
+    Double,
+    Binary,
+    List,
+    Set,
+    Map,
+    Struct,
+}
+
+impl ThriftType {
+    /// The type of a field or collection element, from the low nibble of its header.
+    fn from_compact(nibble: u8) -> Option<Self> {
+        Some(match nibble {
+            1 | 2 => Self::Bool,
+            3 => Self::Byte,
+            4 => Self::I16,
+            5 => Self::I32,
+            6 => Self::I64,
+            7 => Self::Double,
+            8 => Self::Binary,
+            9 => Self::List,
+            10 => Self::Set,
+            11 => Self::Map,
+            12 => Self::Struct,
+            _ => return None,
+        })
+    }
+}
+
+impl Display for ThriftType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Bool => "bool",
+            Self::Byte => "byte",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::Double => "double",
+            Self::Binary => "binary",
+            Self::List => "list",
+            Self::Set => "set",
+            Self::Map => "map",
+            Self::Struct => "struct",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ThriftValue {
+    Bool(bool),
+    Byte(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Double(f64),
+    Binary(Vec<u8>),
+    /// Lists and sets, with the type of their elements
+    List(ThriftType, Vec<ThriftNode>),
+    Map(ThriftType, ThriftType, Vec<(ThriftNode, ThriftNode)>),
+    Struct(Vec<ThriftField>),
+}
+
+impl Display for ThriftValue {
+    /// A one line summary of the value, which doesn't list the contents of collections and
+    /// structs.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::Byte(value) => write!(f, "{value}"),
+            Self::I16(value) => write!(f, "{value}"),
+            Self::I32(value) => write!(f, "{value}"),
+            Self::I64(value) => write!(f, "{value}"),
+            Self::Double(value) => write!(f, "{value}"),
+            Self::Binary(bytes) => {
+                let preview = &bytes[..bytes.len().min(MAX_BINARY_PREVIEW)];
+                let is_cut = preview.len() < bytes.len();
+                let text = match std::str::from_utf8(preview) {
+                    Ok(text) => Some(text),
+                    // A character split by the end of the preview
+                    Err(e) if is_cut && e.error_len().is_none() => {
+                        std::str::from_utf8(&preview[..e.valid_up_to()]).ok()
+                    }
+                    Err(_) => None,
+                };
+                match text {
+                    Some(text) if !text.chars().any(char::is_control) => {
+                        if is_cut {
+                            write!(f, "{text:?}… ({} bytes)", bytes.len())
+                        } else {
+                            write!(f, "{text:?}")
+                        }
+                    }
+                    _ => write!(f, "{} bytes", bytes.len()),
+                }
+            }
+            Self::List(element_type, elements) => {
+                write!(f, "{} {element_type} elements", elements.len())
+            }
+            Self::Map(key_type, value_type, entries) => {
+                write!(f, "{} {key_type} → {value_type} entries", entries.len())
+            }
+            Self::Struct(fields) => write!(f, "{} fields", fields.len()),
+        }
+    }
+}
+
+/// A decoded value and the bytes it was decoded from.
+#[derive(Clone, Debug)]
+pub struct ThriftNode {
+    pub bytes: Range<usize>,
+    pub value: ThriftValue,
+}
+
+impl ThriftNode {
+    /// Bytes of a binary value, without its length prefix.
+    pub fn binary_bytes(&self) -> Option<Range<usize>> {
+        match &self.value {
+            ThriftValue::Binary(bytes) => Some(self.bytes.end - bytes.len()..self.bytes.end),
+            _ => None,
+        }
+    }
+}
+
+/// A field of a struct, found by its id in the schema if one was given.
+#[derive(Clone, Debug)]
+pub struct ThriftField {
+    pub id: i16,
+    pub field_type: ThriftType,
+    pub name: Option<&'static str>,
+    /// Name of the struct held by the field, or by the elements of its list, if the schema knows it
+    pub struct_name: Option<&'static str>,
+    /// Bytes of the field's header, which holds its id and type
+    pub header: Range<usize>,
+    pub value: ThriftNode,
+}
+
+impl ThriftField {
+    /// Bytes of the whole field, header included.
+    pub fn bytes(&self) -> Range<usize> {
+        self.header.start..self.value.bytes.end
+    }
+
+    /// The field's name, or its id if the schema doesn't know it.
+    pub fn label(&self) -> String {
+        match self.name {
+            Some(name) => name.to_string(),
+            None => format!("field {}", self.id),
+        }
+    }
+
+    /// The field's type, along with the name of its struct if it's known.
+    pub fn type_label(&self) -> String {
+        match self.struct_name {
+            Some(struct_name) => format!("{} ({struct_name})", self.field_type),
+            None => self.field_type.to_string(),
+        }
+    }
+}
+
+/// A struct decoded with the compact protocol, recording the bytes of every field.
+#[derive(Clone, Debug)]
+pub struct ThriftTrace {
+    pub fields: Vec<ThriftField>,
+    /// Bytes of the struct, up to and including its stop byte
+    pub bytes: Range<usize>,
+    /// Why decoding stopped before the end of the struct
+    pub error: Option<String>,
+}
+
+impl ThriftTrace {
+    /// Decode the struct starting at the beginning of `bytes`, naming fields from `schema`.
+    pub fn decode(bytes: &[u8], schema: Option<&'static ThriftSchema>) -> Self {
+        let mut decoder = Decoder { bytes, pos: 0 };
+        let mut fields = vec![];
+        let error = decoder.read_struct(schema, &mut fields, 0).err();
+        Self {
+            fields,
+            bytes: 0..decoder.pos,
+            error,
+        }
+    }
+
+    /// The innermost field holding the byte at `pos`, along with its path.
+    pub fn field_at(&self, pos: usize) -> Option<(String, &ThriftField)> {
+        Self::find_field(&self.fields, pos, "")
+    }
+
+    /// The value at the end of `path`. Each step is the id of a field of the current struct,
+    /// along with the index of an element if the field holds a list.
+    pub fn find(&self, path: &[(i16, Option<usize>)]) -> Option<&ThriftNode> {
+        let mut node: Option<&ThriftNode> = None;
+        for (id, idx) in path {
+            let fields = match node {
+                None => &self.fields,
+                Some(ThriftNode {
+                    value: ThriftValue::Struct(fields),
+                    ..
+                }) => fields,
+                Some(_) => return None,
+            };
+            let mut next = &fields.iter().find(|field| field.id == *id)?.value;
+            if let Some(idx) = idx {
+                let ThriftValue::List(_, elements) = &next.value else {
+                    return None;
+                };
+                next = elements.get(*idx)?;
+            }
+            node = Some(next);
+        }
+        node
+    }
+
+    fn find_field<'a>(
+        fields: &'a [ThriftField],
+        pos: usize,
+        prefix: &str,
+    ) -> Option<(String, &'a ThriftField)> {
+        // Fields are stored in order, so the one holding `pos` is the first that ends after it
+        let field = fields
+            .get(fields.partition_point(|field| field.bytes().end <= pos))
+            .filter(|field| field.bytes().contains(&pos))?;
+        let path = format!("{prefix}{}", field.label());
+        let nested = match &field.value.value {
+            ThriftValue::Struct(fields) => Self::find_field(fields, pos, &format!("{path}.")),
+            ThriftValue::List(ThriftType::Struct, elements) => {
+                let idx = elements.partition_point(|element| element.bytes.end <= pos);
+                match elements.get(idx).map(|element| &element.value) {
+                    Some(ThriftValue::Struct(fields)) => {
+                        Self::find_field(fields, pos, &format!("{path}[{idx}]."))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        nested.or(Some((path, field)))
+    }
+
+    /// Fields missing from the schema, which were likely added to the format after it. The bytes
+    /// of the struct start at `start` in the file.
+    pub fn unknown_fields(&self, start: u64) -> Vec<UnknownField> {
+        let mut unknown_fields = vec![];
+        Self::find_unknown_fields(&self.fields, start, "", &mut unknown_fields);
+        unknown_fields
+    }
+
+    fn find_unknown_fields(
+        fields: &[ThriftField],
+        start: u64,
+        path: &str,
+        unknown_fields: &mut Vec<UnknownField>,
+    ) {
+        for field in fields {
+            let Some(name) = field.name else {
+                let bytes = field.bytes();
+                unknown_fields.push(UnknownField {
+                    path: path.to_string(),
+                    id: field.id,
+                    field_type: field.field_type,
+                    byte_interval: (start + bytes.start as u64, start + bytes.end as u64 - 1),
+                    value: field.value.value.clone(),
+                });
+                continue;
+            };
+            // Nested structs are only checked if the schema knows them
+            if field.struct_name.is_none() {
+                continue;
+            }
+            let path = if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{path}.{name}")
+            };
+            match &field.value.value {
+                ThriftValue::Struct(fields) => {
+                    Self::find_unknown_fields(fields, start, &path, unknown_fields);
+                }
+                ThriftValue::List(_, elements) => {
+                    for (idx, element) in elements.iter().enumerate() {
+                        if let ThriftValue::Struct(fields) = &element.value {
+                            let path = format!("{path}[{idx}]");
+                            Self::find_unknown_fields(fields, start, &path, unknown_fields);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A field of a metadata struct or page header whose id isn't in the format's schema, kept so
+/// that fields written by newer writers can be inspected.
+#[derive(Clone, Debug)]
+pub struct UnknownField {
+    /// Path of the struct holding the field, empty for the outermost struct
+    pub path: String,
+    pub id: i16,
+    pub field_type: ThriftType,
+    /// Where the field is stored in the file, header included
+    pub byte_interval: ByteInterval,
+    pub value: ThriftValue,
+}
+
+/// A reader keeping a copy of the bytes read through it, to trace a struct after it's decoded.
+pub struct RecordingReader<R> {
+    inner: R,
+    pub bytes: Vec<u8>,
+}
+
+impl<R> RecordingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            bytes: vec![],
+        }
+    }
+}
+
+impl<R: Read> Read for RecordingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = self.inner.read(buf)?;
+        self.bytes.extend_from_slice(&buf[..length]);
+        Ok(length)
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn read_byte(&mut self) -> Result<u8, String> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or_else(|| format!("Unexpected end of the bytes at byte {}", self.pos))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, String> {
+        let start = self.pos;
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(format!("Varint at byte {start} is longer than 10 bytes"))
+    }
+
+    fn read_zigzag(&mut self) -> Result<i64, String> {
+        let value = self.read_varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// Read a length or size, which can't be larger than the bytes left.
+    fn read_size(&mut self, what: &str) -> Result<usize, String> {
+        let start = self.pos;
+        let size = self.read_varint()?;
+        usize::try_from(size)
+            .ok()
+            .filter(|size| *size <= self.bytes.len() - self.pos)
+            .ok_or_else(|| format!("{what} at byte {start} is too large ({size})"))
+    }
+
+    fn read_struct(
+        &mut self,
+        schema: Option<&'static ThriftSchema>,
+        fields: &mut Vec<ThriftField>,
+        depth: usize,
+    ) -> Result<(), String> {
+        let mut last_id = 0i16;
+        loop {
+            let header_start = self.pos;
+            let header = self.read_byte()?;
+            if header == 0 {
+                return Ok(());
+            }
+            let field_type = ThriftType::from_compact(header & 0x0f).ok_or_else(|| {
+                format!(
+                    "Unknown field type {} at byte {header_start}",
+                    header & 0x0f
+                )
+            })?;
+            let id = match header >> 4 {
+                0 => i16::try_from(self.read_zigzag()?)
+                    .map_err(|_| format!("Field id at byte {header_start} is out of range"))?,
+                delta => last_id.wrapping_add(delta as i16),
+            };
+            last_id = id;
+
+            let field_schema = schema.and_then(|schema| schema.field(id));
+            let nested = field_schema.and_then(|(_, nested)| nested);
+            let mut field = ThriftField {
+                id,
+                field_type,
+                name: field_schema.map(|(name, _)| name),
+                struct_name: nested.map(|nested| nested.name),
+                header: header_start..self.pos,
+                value: ThriftNode {
+                    bytes: self.pos..self.pos,
+                    value: ThriftValue::Bool(header & 0x0f == 1),
+                },
+            };
+            // Booleans are stored in the type of the field's header
+            let result = if field_type == ThriftType::Bool {
+                Ok(())
+            } else {
+                self.read_value(field_type, nested, &mut field.value, depth)
+            };
+            fields.push(field);
+            result?;
+        }
+    }
+
+    /// Read a value into `node`, which keeps whatever was decoded before an error.
+    fn read_value(
+        &mut self,
+        value_type: ThriftType,
+        schema: Option<&'static ThriftSchema>,
+        node: &mut ThriftNode,
+        depth: usize,
+    ) -> Result<(), String> {
+        node.bytes.start = self.pos;
+        // Lists and maps nest without structs, so the depth is checked for every value
+        if depth > MAX_DEPTH {
+            node.bytes.end = self.pos;
+            return Err(format!("Values are nested too deeply at byte {}", self.pos));
+        }
+        let result = self.read_value_inner(value_type, schema, node, depth);
+        node.bytes.end = self.pos;
+        result
+    }
+
+    fn read_value_inner(
+        &mut self,
+        value_type: ThriftType,
+        schema: Option<&'static ThriftSchema>,
+        node: &mut ThriftNode,
+        depth: usize,
+    ) -> Result<(), String> {
+        let start = self.pos;
+        node.value = match value_type {
+            ThriftType::Bool => ThriftValue::Bool(self.read_byte()? == 1),
+            ThriftType::Byte => ThriftValue::Byte(self.read_byte()? as i8),
+            ThriftType::I16 => ThriftValue::I16(
+                i16::try_from(self.read_zigzag()?)
+                    .map_err(|_| format!("i16 at byte {start} is out of range"))?,
+            ),
+            ThriftType::I32 => ThriftValue::I32(
+                i32::try_from(self.read_zigzag()?)
+                    .map_err(|_| format!("i32 at byte {start} is out of range"))?,
+            ),
+            ThriftType::I64 => ThriftValue::I64(self.read_zigzag()?),
+            ThriftType::Double => {
+                let bytes = self
+                    .bytes
+                    .get(self.pos..self.pos + 8)
+                    .ok_or_else(|| format!("Truncated double at byte {start}"))?;
+                self.pos += 8;
+                ThriftValue::Double(f64::from_le_bytes(bytes.try_into().unwrap()))
+            }
+            ThriftType::Binary => {
+                let length = self.read_size("Binary length")?;
+                let bytes = self.bytes[self.pos..self.pos + length].to_vec();
+                self.pos += length;
+                ThriftValue::Binary(bytes)
+            }
+            ThriftType::List | ThriftType::Set => {
+                let header = self.read_byte()?;
+                let element_type = ThriftType::from_compact(header & 0x0f).ok_or_else(|| {
+                    format!("Unknown element type {} at byte {start}", header & 0x0f)
+                })?;
+                let size = match header >> 4 {
+                    15 => self.read_size("List size")?,
+                    size => size as usize,
+                };
+                node.value = ThriftValue::List(element_type, vec![]);
+                for _ in 0..size {
+                    let mut element = ThriftNode {
+                        bytes: self.pos..self.pos,
+                        value: ThriftValue::Bool(false),
+                    };
+                    let result = self.read_value(element_type, schema, &mut element, depth + 1);
+                    if let ThriftValue::List(_, elements) = &mut node.value {
+                        elements.push(element);
+                    }
+                    result?;
+                }
+                return Ok(());
+            }
+            ThriftType::Map => {
+                let size = self.read_size("Map size")?;
+                let (key_type, value_type) = if size == 0 {
+                    (ThriftType::Binary, ThriftType::Binary)
+                } else {
+                    let types = self.read_byte()?;
+                    let key_type = ThriftType::from_compact(types >> 4);
+                    let value_type = ThriftType::from_compact(types & 0x0f);
+                    key_type.zip(value_type).ok_or_else(|| {
+                        format!("Unknown key or value type {types:#04x} at byte {start}")
+                    })?
+                };
+                node.value = ThriftValue::Map(key_type, value_type, vec![]);
+                for _ in 0..size {
+                    let mut key = ThriftNode {
+                        bytes: self.pos..self.pos,
+                        value: ThriftValue::Bool(false),
+                    };
+                    let mut value = key.clone();
+                    let result = self
+                        .read_value(key_type, None, &mut key, depth + 1)
+                        .and_then(|_| self.read_value(value_type, None, &mut value, depth + 1));
+                    if let ThriftValue::Map(_, _, entries) = &mut node.value {
+                        entries.push((key, value));
+                    }
+                    result?;
+                }
+                return Ok(());
+            }
+            ThriftType::Struct => {
+                node.value = ThriftValue::Struct(vec![]);
+                let ThriftValue::Struct(fields) = &mut node.value else {
+                    unreachable!()
+                };
+                return self.read_struct(schema, fields, depth + 1);
+            }
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deeply_nested_lists_stop_decoding() {
+        // A list field holding a list holding a list, and so on
+        let trace = ThriftTrace::decode(&[0x19; 200_000], None);
+        assert!(
+            trace
+                .error
+                .is_some_and(|error| error.contains("nested too deeply"))
+        );
+    }
+}
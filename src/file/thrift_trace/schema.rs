@@ -0,0 +1,344 @@
+/// Names of the fields of a thrift struct, and the schemas of the structs nested in them.
+/// Fields holding lists of structs refer to the schema of their elements.
+#[derive(Debug)]
+pub struct ThriftSchema {
+    pub name: &'static str,
+    pub fields: &'static [(i16, &'static str, Option<&'static ThriftSchema>)],
+}
+
+impl ThriftSchema {
+    pub fn field(&self, id: i16) -> Option<(&'static str, Option<&'static ThriftSchema>)> {
+        self.fields
+            .iter()
+            .find(|(field_id, _, _)| *field_id == id)
+            .map(|(_, name, schema)| (*name, *schema))
+    }
+}
+
+// The structs of parquet.thrift, with the field names used there
+
+pub static FILE_METADATA: ThriftSchema = ThriftSchema {
+    name: "FileMetaData",
+    fields: &[
+        (1, "version", None),
+        (2, "schema", Some(&SCHEMA_ELEMENT)),
+        (3, "num_rows", None),
+        (4, "row_groups", Some(&ROW_GROUP)),
+        (5, "key_value_metadata", Some(&KEY_VALUE)),
+        (6, "created_by", None),
+        (7, "column_orders", Some(&COLUMN_ORDER)),
+        (8, "encryption_algorithm", Some(&ENCRYPTION_ALGORITHM)),
+        (9, "footer_signing_key_metadata", None),
+    ],
+};
+
+pub static PAGE_HEADER: ThriftSchema = ThriftSchema {
+    name: "PageHeader",
+    fields: &[
+        (1, "type", None),
+        (2, "uncompressed_page_size", None),
+        (3, "compressed_page_size", None),
+        (4, "crc", None),
+        (5, "data_page_header", Some(&DATA_PAGE_HEADER)),
+        (6, "index_page_header", Some(&INDEX_PAGE_HEADER)),
+        (7, "dictionary_page_header", Some(&DICTIONARY_PAGE_HEADER)),
+        (8, "data_page_header_v2", Some(&DATA_PAGE_HEADER_V2)),
+    ],
+};
+
+pub static COLUMN_INDEX: ThriftSchema = ThriftSchema {
+    name: "ColumnIndex",
+    fields: &[
+        (1, "null_pages", None),
+        (2, "min_values", None),
+        (3, "max_values", None),
+        (4, "boundary_order", None),
+        (5, "null_counts", None),
+        (6, "repetition_level_histograms", None),
+        (7, "definition_level_histograms", None),
+    ],
+};
+
+pub static OFFSET_INDEX: ThriftSchema = ThriftSchema {
+    name: "OffsetIndex",
+    fields: &[
+        (1, "page_locations", Some(&PAGE_LOCATION)),
+        (2, "unencoded_byte_array_data_bytes", None),
+    ],
+};
+
+pub static BLOOM_FILTER_HEADER: ThriftSchema = ThriftSchema {
+    name: "BloomFilterHeader",
+    fields: &[
+        (1, "numBytes", None),
+        (2, "algorithm", Some(&BLOOM_FILTER_ALGORITHM)),
+        (3, "hash", Some(&BLOOM_FILTER_HASH)),
+        (4, "compression", Some(&BLOOM_FILTER_COMPRESSION)),
+    ],
+};
+
+static SCHEMA_ELEMENT: ThriftSchema = ThriftSchema {
+    name: "SchemaElement",
+    fields: &[
+        (1, "type", None),
+        (2, "type_length", None),
+        (3, "repetition_type", None),
+        (4, "name", None),
+        (5, "num_children", None),
+        (6, "converted_type", None),
+        (7, "scale", None),
+        (8, "precision", None),
+        (9, "field_id", None),
+        (10, "logicalType", Some(&LOGICAL_TYPE)),
+    ],
+};
+
+static LOGICAL_TYPE: ThriftSchema = ThriftSchema {
+    name: "LogicalType",
+    fields: &[
+        (1, "STRING", Some(&EMPTY)),
+        (2, "MAP", Some(&EMPTY)),
+        (3, "LIST", Some(&EMPTY)),
+        (4, "ENUM", Some(&EMPTY)),
+        (5, "DECIMAL", Some(&DECIMAL_TYPE)),
+        (6, "DATE", Some(&EMPTY)),
+        (7, "TIME", Some(&TIME_TYPE)),
+        (8, "TIMESTAMP", Some(&TIME_TYPE)),
+        (10, "INTEGER", Some(&INT_TYPE)),
+        (11, "UNKNOWN", Some(&EMPTY)),
+        (12, "JSON", Some(&EMPTY)),
+        (13, "BSON", Some(&EMPTY)),
+        (14, "UUID", Some(&EMPTY)),
+        (15, "FLOAT16", Some(&EMPTY)),
+    ],
+};
+
+static DECIMAL_TYPE: ThriftSchema = ThriftSchema {
+    name: "DecimalType",
+    fields: &[(1, "scale", None), (2, "precision", None)],
+};
+
+/// Shared by `TimeType` and `TimestampType`, which have the same fields
+static TIME_TYPE: ThriftSchema = ThriftSchema {
+    name: "TimeType",
+    fields: &[(1, "isAdjustedToUTC", None), (2, "unit", Some(&TIME_UNIT))],
+};
+
+static TIME_UNIT: ThriftSchema = ThriftSchema {
+    name: "TimeUnit",
+    fields: &[
+        (1, "MILLIS", Some(&EMPTY)),
+        (2, "MICROS", Some(&EMPTY)),
+        (3, "NANOS", Some(&EMPTY)),
+    ],
+};
+
+static INT_TYPE: ThriftSchema = ThriftSchema {
+    name: "IntType",
+    fields: &[(1, "bitWidth", None), (2, "isSigned", None)],
+};
+
+static ROW_GROUP: ThriftSchema = ThriftSchema {
+    name: "RowGroup",
+    fields: &[
+        (1, "columns", Some(&COLUMN_CHUNK)),
+        (2, "total_byte_size", None),
+        (3, "num_rows", None),
+        (4, "sorting_columns", Some(&SORTING_COLUMN)),
+        (5, "file_offset", None),
+        (6, "total_compressed_size", None),
+        (7, "ordinal", None),
+    ],
+};
+
+static COLUMN_CHUNK: ThriftSchema = ThriftSchema {
+    name: "ColumnChunk",
+    fields: &[
+        (1, "file_path", None),
+        (2, "file_offset", None),
+        (3, "meta_data", Some(&COLUMN_METADATA)),
+        (4, "offset_index_offset", None),
+        (5, "offset_index_length", None),
+        (6, "column_index_offset", None),
+        (7, "column_index_length", None),
+        (8, "crypto_metadata", Some(&COLUMN_CRYPTO_METADATA)),
+        (9, "encrypted_column_metadata", None),
+    ],
+};
+
+static COLUMN_METADATA: ThriftSchema = ThriftSchema {
+    name: "ColumnMetaData",
+    fields: &[
+        (1, "type", None),
+        (2, "encodings", None),
+        (3, "path_in_schema", None),
+        (4, "codec", None),
+        (5, "num_values", None),
+        (6, "total_uncompressed_size", None),
+        (7, "total_compressed_size", None),
+        (8, "key_value_metadata", Some(&KEY_VALUE)),
+        (9, "data_page_offset", None),
+        (10, "index_page_offset", None),
+        (11, "dictionary_page_offset", None),
+        (12, "statistics", Some(&STATISTICS)),
+        (13, "encoding_stats", Some(&PAGE_ENCODING_STATS)),
+        (14, "bloom_filter_offset", None),
+        (15, "bloom_filter_length", None),
+        (16, "size_statistics", Some(&SIZE_STATISTICS)),
+    ],
+};
+
+static COLUMN_CRYPTO_METADATA: ThriftSchema = ThriftSchema {
+    name: "ColumnCryptoMetaData",
+    fields: &[
+        (1, "ENCRYPTION_WITH_FOOTER_KEY", Some(&EMPTY)),
+        (
+            2,
+            "ENCRYPTION_WITH_COLUMN_KEY",
+            Some(&ENCRYPTION_WITH_COLUMN_KEY),
+        ),
+    ],
+};
+
+static ENCRYPTION_WITH_COLUMN_KEY: ThriftSchema = ThriftSchema {
+    name: "EncryptionWithColumnKey",
+    fields: &[(1, "path_in_schema", None), (2, "key_metadata", None)],
+};
+
+static KEY_VALUE: ThriftSchema = ThriftSchema {
+    name: "KeyValue",
+    fields: &[(1, "key", None), (2, "value", None)],
+};
+
+static SORTING_COLUMN: ThriftSchema = ThriftSchema {
+    name: "SortingColumn",
+    fields: &[
+        (1, "column_idx", None),
+        (2, "descending", None),
+        (3, "nulls_first", None),
+    ],
+};
+
+static PAGE_ENCODING_STATS: ThriftSchema = ThriftSchema {
+    name: "PageEncodingStats",
+    fields: &[
+        (1, "page_type", None),
+        (2, "encoding", None),
+        (3, "count", None),
+    ],
+};
+
+static SIZE_STATISTICS: ThriftSchema = ThriftSchema {
+    name: "SizeStatistics",
+    fields: &[
+        (1, "unencoded_byte_array_data_bytes", None),
+        (2, "repetition_level_histogram", None),
+        (3, "definition_level_histogram", None),
+    ],
+};
+
+static STATISTICS: ThriftSchema = ThriftSchema {
+    name: "Statistics",
+    fields: &[
+        (1, "max", None),
+        (2, "min", None),
+        (3, "null_count", None),
+        (4, "distinct_count", None),
+        (5, "max_value", None),
+        (6, "min_value", None),
+        (7, "is_max_value_exact", None),
+        (8, "is_min_value_exact", None),
+    ],
+};
+
+static COLUMN_ORDER: ThriftSchema = ThriftSchema {
+    name: "ColumnOrder",
+    fields: &[(1, "TYPE_ORDER", Some(&EMPTY))],
+};
+
+static ENCRYPTION_ALGORITHM: ThriftSchema = ThriftSchema {
+    name: "EncryptionAlgorithm",
+    fields: &[
+        (1, "AES_GCM_V1", Some(&AES_GCM)),
+        (2, "AES_GCM_CTR_V1", Some(&AES_GCM)),
+    ],
+};
+
+/// Shared by `AesGcmV1` and `AesGcmCtrV1`, which have the same fields
+static AES_GCM: ThriftSchema = ThriftSchema {
+    name: "AesGcmV1",
+    fields: &[
+        (1, "aad_prefix", None),
+        (2, "aad_file_unique", None),
+        (3, "supply_aad_prefix", None),
+    ],
+};
+
+static DATA_PAGE_HEADER: ThriftSchema = ThriftSchema {
+    name: "DataPageHeader",
+    fields: &[
+        (1, "num_values", None),
+        (2, "encoding", None),
+        (3, "definition_level_encoding", None),
+        (4, "repetition_level_encoding", None),
+        (5, "statistics", Some(&STATISTICS)),
+    ],
+};
+
+static INDEX_PAGE_HEADER: ThriftSchema = ThriftSchema {
+    name: "IndexPageHeader",
+    fields: &[],
+};
+
+static DICTIONARY_PAGE_HEADER: ThriftSchema = ThriftSchema {
+    name: "DictionaryPageHeader",
+    fields: &[
+        (1, "num_values", None),
+        (2, "encoding", None),
+        (3, "is_sorted", None),
+    ],
+};
+
+static DATA_PAGE_HEADER_V2: ThriftSchema = ThriftSchema {
+    name: "DataPageHeaderV2",
+    fields: &[
+        (1, "num_values", None),
+        (2, "num_nulls", None),
+        (3, "num_rows", None),
+        (4, "encoding", None),
+        (5, "definition_levels_byte_length", None),
+        (6, "repetition_levels_byte_length", None),
+        (7, "is_compressed", None),
+        (8, "statistics", Some(&STATISTICS)),
+    ],
+};
+
+static PAGE_LOCATION: ThriftSchema = ThriftSchema {
+    name: "PageLocation",
+    fields: &[
+        (1, "offset", None),
+        (2, "compressed_page_size", None),
+        (3, "first_row_index", None),
+    ],
+};
+
+static BLOOM_FILTER_ALGORITHM: ThriftSchema = ThriftSchema {
+    name: "BloomFilterAlgorithm",
+    fields: &[(1, "BLOCK", Some(&EMPTY))],
+};
+
+static BLOOM_FILTER_HASH: ThriftSchema = ThriftSchema {
+    name: "BloomFilterHash",
+    fields: &[(1, "XXHASH", Some(&EMPTY))],
+};
+
+static BLOOM_FILTER_COMPRESSION: ThriftSchema = ThriftSchema {
+    name: "BloomFilterCompression",
+    fields: &[(1, "UNCOMPRESSED", Some(&EMPTY))],
+};
+
+/// Structs without fields, used as the members of unions
+static EMPTY: ThriftSchema = ThriftSchema {
+    name: "Empty",
+    fields: &[],
+};
@@ -5,4 +5,4 @@ mod views;
 
 pub use app::ParkhayApp;
 pub use cli::ParkhayCli;
-pub use file::ParkhayFile;
+pub use file::{Coalescing, ParkhayFile};
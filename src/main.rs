@@ -1,12 +1,35 @@
 use anyhow::{Result, anyhow};
 use clap::Parser;
-use parkhay::ParkhayCli;
+use parkhay::{Coalescing, ParkhayCli, ParkhayFile};
 
 fn main() -> Result<()> {
     env_logger::init();
 
     let cli = ParkhayCli::parse();
 
+    if let Some(salvage_dir) = cli.salvage {
+        let parkhay_file = ParkhayFile::new(&cli.path)?;
+        for path in parkhay_file.export_recovered_pages(&salvage_dir)? {
+            println!("{}", path.display());
+        }
+        return Ok(());
+    }
+
+    if let Some(columns) = &cli.io_plan {
+        // Coalesce if either of the coalescing options is given
+        let coalescing =
+            (cli.coalesce_gap.is_some() || cli.max_request_size.is_some()).then(|| {
+                let default = Coalescing::default();
+                Coalescing {
+                    max_gap: cli.coalesce_gap.unwrap_or(default.max_gap),
+                    max_request_size: cli.max_request_size.unwrap_or(default.max_request_size),
+                }
+            });
+        let parkhay_file = ParkhayFile::new(&cli.path)?;
+        print!("{}", parkhay_file.io_plan(columns, coalescing)?);
+        return Ok(());
+    }
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([800.0, 600.0]),
         ..Default::default()
@@ -2,6 +2,16 @@ pub trait View {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame);
 }
 
+/// Rows of a row group to decode and show in the rows view.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RowsRequest {
+    pub row_group: usize,
+    /// Half-open range of rows within the row group, or all of them
+    pub rows: Option<(i64, i64)>,
+}
+
 mod layout;
+mod rows;
 
 pub use layout::LayoutView;
+pub use rows::RowsView;
@@ -1,34 +1,64 @@
-use std::sync::mpsc::Sender;
+use std::sync::{Arc, mpsc::Sender};
 
 use data_renderer::DataRenderer;
-use egui::{CentralPanel, Frame, Label, RichText, Ui, Widget, epaint::MarginF32};
+use egui::{
+    Align, CentralPanel, Color32, Frame, Label, RichText, ScrollArea, SidePanel, Stroke,
+    TopBottomPanel, Ui, Widget, epaint::MarginF32,
+};
 use footer_renderer::FooterRenderer;
+use io_planner::IoPlanner;
+use navigation::{LayoutRegion, Navigator, SectionHighlight};
+use pruning_simulator::PruningSimulator;
+use thrift_decoder::ThriftDecoder;
 
-use super::View;
-use crate::{ParkhayFile, file::ReadRequest};
+use super::{RowsRequest, View};
+use crate::{
+    ParkhayFile,
+    file::{ReadRequest, RecoveryReport},
+};
 
 mod components;
 mod data_renderer;
 mod footer_renderer;
+mod io_planner;
+mod navigation;
+mod pruning_simulator;
+mod thrift_decoder;
+mod thrift_renderer;
 
 const CORNER_RADIUS: f32 = 2.5;
 const LAYOUT_LABEL_SIZE: f32 = 18.;
 const INNER_SECTION_MARGIN: f32 = 8.;
 const WINDOW_PADDING_HORIZONTAL: f32 = 20.;
+const MATCH_HIGHLIGHT_COLOR: Color32 = Color32::from_rgb(240, 190, 60);
+const FOCUS_HIGHLIGHT_COLOR: Color32 = Color32::from_rgb(230, 120, 0);
 
 pub struct LayoutView {
-    parkhay_file: ParkhayFile,
+    parkhay_file: Arc<ParkhayFile>,
     data_reader_tx: Sender<ReadRequest>,
+    navigator: Navigator,
+    pruning_simulator: PruningSimulator,
+    io_planner: IoPlanner,
+    thrift_decoder: ThriftDecoder,
 }
 
 impl LayoutView {
-    pub fn new(parkhay_file: ParkhayFile, data_reader_tx: Sender<ReadRequest>) -> Self {
+    pub fn new(parkhay_file: Arc<ParkhayFile>, data_reader_tx: Sender<ReadRequest>) -> Self {
         Self {
             parkhay_file,
             data_reader_tx,
+            navigator: Navigator::default(),
+            pruning_simulator: PruningSimulator::default(),
+            io_planner: IoPlanner::default(),
+            thrift_decoder: ThriftDecoder::default(),
         }
     }
 
+    /// Rows requested from a row group or page section, to show in the rows view.
+    pub fn take_rows_request(&mut self) -> Option<RowsRequest> {
+        self.navigator.take_rows_request()
+    }
+
     fn render_layout_header(ui: &mut Ui, text: &str) {
         Label::new(
             RichText::new(text)
@@ -39,10 +69,136 @@ impl LayoutView {
         .selectable(false)
         .ui(ui);
     }
+
+    fn render_layout_frame(ui: &mut Ui, text: &str, highlight: SectionHighlight) {
+        let stroke = if highlight.is_focused {
+            Stroke::new(2., FOCUS_HIGHLIGHT_COLOR)
+        } else {
+            ui.style().visuals.widgets.inactive.bg_stroke
+        };
+        let response = Frame::default()
+            .fill(ui.style().visuals.widgets.inactive.bg_fill)
+            .stroke(stroke)
+            .corner_radius(CORNER_RADIUS)
+            .inner_margin(INNER_SECTION_MARGIN)
+            .outer_margin(MarginF32::ZERO)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                Self::render_layout_header(ui, text);
+            })
+            .response;
+        if highlight.should_scroll {
+            response.scroll_to_me(Some(Align::Min));
+        }
+    }
+
+    fn render_recovery_report(
+        ui: &mut Ui,
+        report: &RecoveryReport,
+        thrift_decoder: &mut ThriftDecoder,
+    ) {
+        Frame::default()
+            .fill(Color32::from_rgb(250, 230, 170))
+            .stroke(ui.style().visuals.widgets.inactive.bg_stroke)
+            .corner_radius(CORNER_RADIUS)
+            .inner_margin(INNER_SECTION_MARGIN)
+            .outer_margin(MarginF32::ZERO)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                Self::render_layout_header(ui, "Footer: Missing or Corrupt");
+                ui.label(RichText::new(&report.reason).monospace());
+                ui.separator();
+                ui.label(
+                    RichText::new(format!(
+                        "Recovered {} pages in {} inferred column chunks by scanning bytes {}..={}.",
+                        report.num_pages,
+                        report.num_column_chunks,
+                        report.scanned.0,
+                        report.scanned.1
+                    ))
+                    .monospace(),
+                );
+                for (start, end) in &report.unrecognized {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new(format!(
+                                "Unrecognized bytes {start}..={end} ({} bytes)",
+                                end - start + 1
+                            ))
+                            .monospace(),
+                        );
+                        if ui
+                            .small_button(RichText::new("Decode as Thrift").monospace())
+                            .clicked()
+                        {
+                            thrift_decoder.decode((*start, *end));
+                        }
+                    });
+                }
+                ui.label(
+                    RichText::new(
+                        "Column chunk boundaries are inferred and may be inaccurate. \
+                         Run with `--salvage <DIR>` to export the recovered pages.",
+                    )
+                    .monospace()
+                    .italics(),
+                );
+            });
+    }
 }
 
 impl View for LayoutView {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Byte ranges to decode requested from values in the previous frame
+        if let Some(byte_interval) = self.navigator.take_thrift_request() {
+            self.thrift_decoder.decode(byte_interval);
+        }
+
+        TopBottomPanel::top("navigation").show(ctx, |ui| {
+            ui.add_space(4.);
+            self.navigator.render_toolbar(ui, &self.parkhay_file);
+            ui.add_space(4.);
+            self.pruning_simulator
+                .render_toolbar(ui, &self.parkhay_file);
+            ui.add_space(4.);
+            self.thrift_decoder.render_toolbar(ui, &self.parkhay_file);
+            ui.add_space(4.);
+        });
+
+        let has_pruning_report = self.pruning_simulator.report().is_some();
+        if has_pruning_report || !self.io_planner.is_empty() || self.thrift_decoder.is_active() {
+            SidePanel::right("analysis")
+                .resizable(true)
+                .default_width(360.)
+                .show(ctx, |ui| {
+                    ScrollArea::vertical().show(ui, |ui| {
+                        if self.thrift_decoder.is_active() {
+                            Self::render_layout_header(ui, "Thrift");
+                            self.thrift_decoder.render(
+                                ui,
+                                &self.data_reader_tx,
+                                &mut self.navigator,
+                            );
+                            ui.add_space(INNER_SECTION_MARGIN);
+                        }
+                        if !self.io_planner.is_empty() {
+                            Self::render_layout_header(ui, "I/O Plan");
+                            self.io_planner
+                                .render(ui, &self.parkhay_file, &mut self.navigator);
+                            ui.add_space(INNER_SECTION_MARGIN);
+                        }
+                        if has_pruning_report {
+                            Self::render_layout_header(ui, "Pruning");
+                            self.pruning_simulator.render_report(
+                                ui,
+                                &self.parkhay_file,
+                                &mut self.navigator,
+                            );
+                        }
+                    });
+                });
+        }
+
         CentralPanel::default().show(ctx, |ui| {
             ui.set_width(ui.available_width());
             let window_padding_vertical = 0.2 * ui.available_width() / 2.;
@@ -57,53 +213,51 @@ impl View for LayoutView {
                 ))
                 .show(ui, |ui| {
                     egui::ScrollArea::vertical().show(ui, |ui| {
-                        Frame::default()
-                            .fill(ui.style().visuals.widgets.inactive.bg_fill)
-                            .stroke(ui.style().visuals.widgets.inactive.bg_stroke)
-                            .corner_radius(CORNER_RADIUS)
-                            .inner_margin(INNER_SECTION_MARGIN)
-                            .outer_margin(MarginF32::ZERO)
-                            .show(ui, |ui| {
-                                ui.set_width(ui.available_width());
-                                Self::render_layout_header(
-                                    ui,
-                                    &String::from_utf8_lossy(&self.parkhay_file.start_magic),
-                                );
-                            });
+                        Self::render_layout_frame(
+                            ui,
+                            &String::from_utf8_lossy(&self.parkhay_file.start_magic),
+                            self.navigator.layout_highlight(LayoutRegion::StartMagic),
+                        );
 
                         // Data
-                        DataRenderer::render(ui, &self.parkhay_file.data, &mut self.data_reader_tx);
-
-                        // Footer
-                        FooterRenderer::render(ui, &self.parkhay_file.footer);
-
-                        Frame::default()
-                            .fill(ui.style().visuals.widgets.inactive.bg_fill)
-                            .stroke(ui.style().visuals.widgets.inactive.bg_stroke)
-                            .corner_radius(CORNER_RADIUS)
-                            .inner_margin(INNER_SECTION_MARGIN)
-                            .outer_margin(MarginF32::ZERO)
-                            .show(ui, |ui| {
-                                ui.set_width(ui.available_width());
-                                Self::render_layout_header(
-                                    ui,
-                                    &format!("Footer Length: {}", self.parkhay_file.footer_length),
-                                );
-                            });
-
-                        Frame::default()
-                            .fill(ui.style().visuals.widgets.inactive.bg_fill)
-                            .stroke(ui.style().visuals.widgets.inactive.bg_stroke)
-                            .corner_radius(CORNER_RADIUS)
-                            .inner_margin(INNER_SECTION_MARGIN)
-                            .outer_margin(MarginF32::ZERO)
-                            .show(ui, |ui| {
-                                ui.set_width(ui.available_width());
-                                Self::render_layout_header(
-                                    ui,
-                                    &String::from_utf8_lossy(&self.parkhay_file.end_magic),
-                                );
-                            });
+                        DataRenderer::render(
+                            ui,
+                            &self.parkhay_file.data,
+                            self.parkhay_file.footer.as_ref(),
+                            &mut self.data_reader_tx,
+                            &mut self.navigator,
+                            self.pruning_simulator.report(),
+                        );
+
+                        if let Some(footer) = &self.parkhay_file.footer {
+                            // Footer
+                            // Scrolls to its encoded bytes when an offset within it is focused
+                            FooterRenderer::render(
+                                ui,
+                                footer,
+                                self.parkhay_file.footer_interval(),
+                                &self.data_reader_tx,
+                                &mut self.navigator,
+                                self.io_planner.projection_mut(),
+                            );
+
+                            Self::render_layout_frame(
+                                ui,
+                                &format!("Footer Length: {}", self.parkhay_file.footer_length),
+                                self.navigator.layout_highlight(LayoutRegion::FooterLength),
+                            );
+
+                            Self::render_layout_frame(
+                                ui,
+                                &String::from_utf8_lossy(&self.parkhay_file.end_magic),
+                                self.navigator.layout_highlight(LayoutRegion::EndMagic),
+                            );
+                        } else if let Some(report) = &self.parkhay_file.recovery {
+                            Self::render_recovery_report(ui, report, &mut self.thrift_decoder);
+                        }
+
+                        // The focused section has been revealed by now
+                        self.navigator.complete_focus();
                     });
                 });
         });
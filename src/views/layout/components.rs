@@ -1,4 +1,11 @@
-use egui::{CollapsingHeader, Response, RichText, Ui, lerp};
+use egui::{
+    Checkbox, CollapsingHeader, Color32, Pos2, Rect, Response, RichText, Sense, Stroke, StrokeKind,
+    Ui,
+    collapsing_header::{CollapsingState, paint_default_icon},
+    lerp, vec2,
+};
+
+use crate::file::ByteInterval;
 
 // *******************
 // COLLAPSIBLE SECTION
@@ -14,6 +21,7 @@ pub struct CollapsibleSection {
     header: RichText,
     icon: CollapsibleSectionIcon,
     should_indent: bool,
+    should_open: bool,
 }
 
 impl CollapsibleSection {
@@ -22,9 +30,16 @@ impl CollapsibleSection {
             header,
             icon,
             should_indent,
+            should_open: false,
         }
     }
 
+    /// Force the section open, eg. when navigating to something inside of it.
+    pub fn open(mut self, should_open: bool) -> Self {
+        self.should_open = should_open;
+        self
+    }
+
     pub fn empty_icon(_ui: &mut Ui, _openness: f32, _response: &Response) {}
     pub fn circle_icon(ui: &mut Ui, openness: f32, response: &Response) {
         let stroke = ui.style().interact(response).fg_stroke;
@@ -33,20 +48,56 @@ impl CollapsibleSection {
             .circle_filled(response.rect.center(), radius, stroke.color);
     }
 
-    pub fn show(self, ui: &mut Ui, content: impl FnOnce(&mut Ui)) {
+    /// Returns the response of the section header.
+    pub fn show(self, ui: &mut Ui, content: impl FnOnce(&mut Ui)) -> Response {
         let ch = CollapsingHeader::new(self.header);
         let ch = match self.icon {
             CollapsibleSectionIcon::Default => ch,
             CollapsibleSectionIcon::Blank => ch.icon(Self::empty_icon),
             CollapsibleSectionIcon::Circle => ch.icon(Self::circle_icon),
         };
+        let ch = if self.should_open {
+            ch.open(Some(true))
+        } else {
+            ch
+        };
 
         if self.should_indent {
-            ch.show(ui, content);
+            ch.show(ui, content).header_response
         } else {
-            ch.show_unindented(ui, content);
+            ch.show_unindented(ui, content).header_response
         }
     }
+
+    /// Like `show`, with the header as the label of a checkbox. Returns the checkbox's response.
+    pub fn show_with_checkbox(
+        self,
+        ui: &mut Ui,
+        checked: &mut bool,
+        indeterminate: bool,
+        content: impl FnOnce(&mut Ui),
+    ) -> Response {
+        let id = ui.make_persistent_id(self.header.text());
+        let mut state = CollapsingState::load_with_default_open(ui.ctx(), id, false);
+        if self.should_open {
+            state.set_open(true);
+        }
+
+        let header = ui.horizontal(|ui| {
+            match self.icon {
+                CollapsibleSectionIcon::Default => state.show_toggle_button(ui, paint_default_icon),
+                CollapsibleSectionIcon::Blank => state.show_toggle_button(ui, Self::empty_icon),
+                CollapsibleSectionIcon::Circle => state.show_toggle_button(ui, Self::circle_icon),
+            };
+            ui.add(Checkbox::new(checked, self.header).indeterminate(indeterminate))
+        });
+        if self.should_indent {
+            state.show_body_indented(&header.response, ui, content);
+        } else {
+            state.show_body_unindented(ui, content);
+        }
+        header.inner
+    }
 }
 
 // *************
@@ -61,4 +112,405 @@ impl LabeledValue {
             ui.label(value);
         });
     }
+
+    /// Like `show`, with a secondary, de-emphasized representation of the value underneath.
+    pub fn show_with_raw(ui: &mut Ui, label: RichText, value: RichText, raw: RichText) {
+        CollapsibleSection::new(label, CollapsibleSectionIcon::Default, true).show(ui, |ui| {
+            ui.label(value);
+            ui.label(raw.weak());
+        });
+    }
+
+    /// Like `show`, but the value is a link. Returns whether the link was clicked.
+    pub fn show_link(ui: &mut Ui, label: RichText, value: RichText) -> bool {
+        let mut clicked = false;
+        CollapsibleSection::new(label, CollapsibleSectionIcon::Default, true).show(ui, |ui| {
+            clicked = ui.link(value).clicked();
+        });
+        clicked
+    }
+}
+
+// *****************
+// STACKED BAR CHART
+// *****************
+
+const STACKED_BAR_CHART_HEIGHT: f32 = 120.;
+const STACKED_BAR_MAX_WIDTH: f32 = 40.;
+const STACKED_BAR_LIGHT_COLOR: Color32 = Color32::from_rgb(200, 220, 240);
+const STACKED_BAR_DARK_COLOR: Color32 = Color32::from_rgb(30, 70, 130);
+
+/// Bars made of one segment per series, eg. one bar per page with a segment per level.
+pub struct StackedBarChart;
+
+impl StackedBarChart {
+    /// `bars` holds the value of each series per bar. Bars are labeled `{bar_name} {idx}`.
+    pub fn show(ui: &mut Ui, bars: &[&[i64]], series_names: &[String], bar_name: &str) {
+        let Some(highest) = bars
+            .iter()
+            .map(|bar| bar.iter().sum::<i64>())
+            .max()
+            .filter(|highest| *highest > 0)
+        else {
+            ui.label(RichText::new("N/A").monospace());
+            return;
+        };
+
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(ui.available_width(), STACKED_BAR_CHART_HEIGHT),
+            Sense::hover(),
+        );
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2., ui.visuals().extreme_bg_color);
+
+        let bar_width = (rect.width() / bars.len() as f32).min(STACKED_BAR_MAX_WIDTH);
+        let hovered_bar = response
+            .hover_pos()
+            .map(|pos| ((pos.x - rect.left()) / bar_width) as usize)
+            .filter(|idx| *idx < bars.len());
+
+        for (idx, bar) in bars.iter().enumerate() {
+            let left = rect.left() + idx as f32 * bar_width;
+            let mut bottom = rect.bottom();
+            for (series, value) in bar.iter().enumerate() {
+                let height = *value as f32 / highest as f32 * rect.height();
+                let segment = Rect::from_min_max(
+                    Pos2::new(left, bottom - height),
+                    Pos2::new(left + (bar_width * 0.8).max(1.), bottom),
+                );
+                painter.rect_filled(segment, 0., Self::series_color(series, series_names.len()));
+                bottom -= height;
+            }
+            if hovered_bar == Some(idx) {
+                painter.rect_stroke(
+                    Rect::from_min_max(
+                        Pos2::new(left, rect.top()),
+                        Pos2::new(left + bar_width, rect.bottom()),
+                    ),
+                    0.,
+                    Stroke::new(1., ui.visuals().strong_text_color()),
+                    StrokeKind::Inside,
+                );
+            }
+        }
+
+        ui.horizontal_wrapped(|ui| {
+            for (series, name) in series_names.iter().enumerate() {
+                let (swatch, _) = ui.allocate_exact_size(vec2(10., 10.), Sense::hover());
+                ui.painter().rect_filled(
+                    swatch,
+                    1.,
+                    Self::series_color(series, series_names.len()),
+                );
+                ui.label(RichText::new(name).monospace().small());
+            }
+        });
+
+        if let Some(idx) = hovered_bar {
+            response.on_hover_ui_at_pointer(|ui| {
+                ui.label(
+                    RichText::new(format!("{bar_name} {idx}"))
+                        .monospace()
+                        .strong(),
+                );
+                for (name, value) in series_names.iter().zip(bars[idx].iter()) {
+                    ui.label(RichText::new(format!("{name}: {value}")).monospace());
+                }
+            });
+        }
+    }
+
+    fn series_color(series: usize, num_series: usize) -> Color32 {
+        let t = if num_series > 1 {
+            series as f32 / (num_series - 1) as f32
+        } else {
+            1.
+        };
+        STACKED_BAR_LIGHT_COLOR.lerp_to_gamma(STACKED_BAR_DARK_COLOR, t)
+    }
+}
+
+// *********************
+// LEVEL HISTOGRAM CHART
+// *********************
+
+#[derive(Clone, Copy)]
+pub enum LevelKind {
+    Repetition,
+    Definition,
+}
+
+/// Repetition or definition level histograms, stored as `max_level + 1` counts per page (or a
+/// single histogram for a whole column chunk), shown as one stacked bar per histogram.
+pub struct LevelHistogramChart;
+
+impl LevelHistogramChart {
+    pub fn show(ui: &mut Ui, histograms: &[i64], max_level: i16, kind: LevelKind, bar_name: &str) {
+        let num_levels = max_level as usize + 1;
+        if histograms.is_empty() || !histograms.len().is_multiple_of(num_levels) {
+            ui.label(
+                RichText::new(format!(
+                    "Expected a multiple of {num_levels} counts, found {}",
+                    histograms.len()
+                ))
+                .monospace(),
+            );
+            return;
+        }
+
+        let series_names = (0..=max_level)
+            .map(|level| match (kind, level) {
+                (LevelKind::Repetition, 0) => String::from("Level 0 (new record)"),
+                (LevelKind::Repetition, level) => format!("Level {level} (repeated)"),
+                (LevelKind::Definition, level) if level == max_level => {
+                    format!("Level {level} (non-null)")
+                }
+                (LevelKind::Definition, level) => format!("Level {level} (null)"),
+            })
+            .collect::<Vec<_>>();
+        let bars = histograms.chunks(num_levels).collect::<Vec<_>>();
+        StackedBarChart::show(ui, &bars, &series_names, bar_name);
+    }
+}
+
+// ********
+// FILE MAP
+// ********
+
+const FILE_MAP_HEIGHT: f32 = 24.;
+const FILE_MAP_BACKGROUND_COLOR: Color32 = Color32::from_rgb(235, 235, 235);
+
+/// The whole file as a horizontal strip, with byte ranges painted over it.
+pub struct FileMap;
+
+impl FileMap {
+    /// Returns the offset that was clicked, if any.
+    pub fn show(ui: &mut Ui, file_size: u64, ranges: &[(ByteInterval, Color32)]) -> Option<u64> {
+        let (rect, response) =
+            ui.allocate_exact_size(vec2(ui.available_width(), FILE_MAP_HEIGHT), Sense::click());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2., FILE_MAP_BACKGROUND_COLOR);
+
+        let file_size = file_size.max(1);
+        let x =
+            |offset: u64| rect.left() + (offset as f64 / file_size as f64) as f32 * rect.width();
+        for ((start, end), color) in ranges {
+            let left = x(*start);
+            // Keep tiny ranges visible
+            let right = x(end + 1).max(left + 1.);
+            painter.rect_filled(
+                Rect::from_min_max(Pos2::new(left, rect.top()), Pos2::new(right, rect.bottom())),
+                0.,
+                *color,
+            );
+        }
+        painter.rect_stroke(
+            rect,
+            2.,
+            Stroke::new(1., Color32::LIGHT_GRAY),
+            StrokeKind::Inside,
+        );
+
+        let hovered_offset = response.hover_pos().map(|pos| {
+            let fraction = ((pos.x - rect.left()) / rect.width()).clamp(0., 1.);
+            ((fraction as f64 * file_size as f64) as u64).min(file_size - 1)
+        });
+        let clicked = response.clicked();
+        if let Some(offset) = hovered_offset {
+            response.on_hover_ui_at_pointer(|ui| {
+                ui.label(RichText::new(format!("Offset {offset}")).monospace());
+                if let Some(((start, end), _)) = ranges
+                    .iter()
+                    .find(|((start, end), _)| (*start..=*end).contains(&offset))
+                {
+                    ui.label(
+                        RichText::new(format!("{start}..={end} ({} bytes)", end - start + 1))
+                            .monospace(),
+                    );
+                }
+            });
+        }
+        hovered_offset.filter(|_| clicked)
+    }
+}
+
+// *******
+// TREEMAP
+// *******
+
+const TREEMAP_HEIGHT: f32 = 220.;
+/// Space between a node's rectangle and the rectangles of its children, leaving room for its label
+const TREEMAP_LABEL_HEIGHT: f32 = 14.;
+const TREEMAP_PADDING: f32 = 2.;
+const TREEMAP_TEXT_SIZE: f32 = 10.;
+const TREEMAP_COLORS: [Color32; 8] = [
+    Color32::from_rgb(140, 180, 220),
+    Color32::from_rgb(240, 170, 110),
+    Color32::from_rgb(150, 200, 140),
+    Color32::from_rgb(220, 150, 190),
+    Color32::from_rgb(230, 210, 120),
+    Color32::from_rgb(150, 200, 200),
+    Color32::from_rgb(190, 170, 220),
+    Color32::from_rgb(200, 190, 170),
+];
+
+/// A value shown in a treemap. Nodes with children are split into them, so their value should
+/// be the sum of their children's.
+pub struct TreemapNode {
+    pub label: String,
+    pub value: u64,
+    pub children: Vec<TreemapNode>,
+}
+
+/// Nested rectangles with areas proportional to their values, eg. the bytes of each part of a
+/// file.
+pub struct Treemap;
+
+impl Treemap {
+    pub fn show(ui: &mut Ui, nodes: &[TreemapNode]) {
+        let (rect, response) =
+            ui.allocate_exact_size(vec2(ui.available_width(), TREEMAP_HEIGHT), Sense::hover());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2., ui.visuals().extreme_bg_color);
+
+        let total = nodes.iter().map(|node| node.value).sum::<u64>().max(1);
+        let hover_pos = response.hover_pos();
+        let mut hovered = vec![];
+        for (idx, (node, node_rect)) in nodes.iter().zip(Self::squarify(nodes, rect)).enumerate() {
+            let color = TREEMAP_COLORS[idx % TREEMAP_COLORS.len()];
+            Self::paint(&painter, node, node_rect, color, hover_pos, &mut hovered);
+        }
+
+        if !hovered.is_empty() {
+            response.on_hover_ui_at_pointer(|ui| {
+                for node in hovered {
+                    ui.label(
+                        RichText::new(format!(
+                            "{}: {} ({:.1}%)",
+                            node.label,
+                            node.value,
+                            100. * node.value as f64 / total as f64
+                        ))
+                        .monospace(),
+                    );
+                }
+            });
+        }
+    }
+
+    /// Paint a node and its children, collecting the nodes under the pointer.
+    fn paint<'a>(
+        painter: &egui::Painter,
+        node: &'a TreemapNode,
+        rect: Rect,
+        color: Color32,
+        hover_pos: Option<Pos2>,
+        hovered: &mut Vec<&'a TreemapNode>,
+    ) {
+        if !rect.is_positive() {
+            return;
+        }
+        painter.rect_filled(rect, 0., color);
+        painter.rect_stroke(
+            rect,
+            0.,
+            Stroke::new(1., Color32::WHITE),
+            StrokeKind::Inside,
+        );
+        if hover_pos.is_some_and(|pos| rect.contains(pos)) {
+            hovered.push(node);
+        }
+
+        let has_label_room = rect.height() > TREEMAP_LABEL_HEIGHT && rect.width() > 30.;
+        if has_label_room {
+            painter.with_clip_rect(rect.shrink(TREEMAP_PADDING)).text(
+                rect.left_top() + vec2(3., 1.),
+                egui::Align2::LEFT_TOP,
+                &node.label,
+                egui::FontId::monospace(TREEMAP_TEXT_SIZE),
+                Color32::from_gray(40),
+            );
+        }
+
+        let mut inner = rect.shrink(TREEMAP_PADDING);
+        if has_label_room {
+            inner.min.y = rect.top() + TREEMAP_LABEL_HEIGHT;
+        }
+        if node.children.is_empty() || !inner.is_positive() {
+            return;
+        }
+        let child_color = color.lerp_to_gamma(Color32::WHITE, 0.35);
+        for (child, child_rect) in node
+            .children
+            .iter()
+            .zip(Self::squarify(&node.children, inner))
+        {
+            Self::paint(painter, child, child_rect, child_color, hover_pos, hovered);
+        }
+    }
+
+    /// Lay the nodes out in `rect`, in rows whose rectangles are as close to squares as possible.
+    /// Returns the rectangle of each node, in the order of `nodes`.
+    fn squarify(nodes: &[TreemapNode], rect: Rect) -> Vec<Rect> {
+        let mut order = (0..nodes.len()).collect::<Vec<_>>();
+        order.sort_by_key(|idx| std::cmp::Reverse(nodes[*idx].value));
+        let total = nodes.iter().map(|node| node.value).sum::<u64>().max(1) as f32;
+        let areas = order
+            .iter()
+            .map(|idx| nodes[*idx].value as f32 / total * rect.area())
+            .collect::<Vec<_>>();
+
+        // Aspect ratio of the most elongated rectangle of a row laid along a side of `side` length
+        let worst = |row: &[f32], side: f32| {
+            let sum = row.iter().sum::<f32>();
+            let (min, max) = row.iter().fold((f32::MAX, 0f32), |(min, max), area| {
+                (min.min(*area), max.max(*area))
+            });
+            (side * side * max / (sum * sum)).max(sum * sum / (side * side * min))
+        };
+
+        let mut rects = vec![Rect::NOTHING; nodes.len()];
+        let mut free = rect;
+        let mut start = 0;
+        while start < areas.len() && areas[start] > 0. {
+            let side = free.width().min(free.height());
+            let mut end = start + 1;
+            while end < areas.len()
+                && areas[end] > 0.
+                && worst(&areas[start..=end], side) <= worst(&areas[start..end], side)
+            {
+                end += 1;
+            }
+
+            let row_area = areas[start..end].iter().sum::<f32>();
+            let mut offset = 0.;
+            if free.width() >= free.height() {
+                // A column along the left side
+                let width = row_area / free.height();
+                for (idx, area) in order[start..end].iter().zip(&areas[start..end]) {
+                    let height = area / width;
+                    rects[*idx] = Rect::from_min_size(
+                        Pos2::new(free.left(), free.top() + offset),
+                        vec2(width, height),
+                    );
+                    offset += height;
+                }
+                free.min.x += width;
+            } else {
+                // A row along the top side
+                let height = row_area / free.width();
+                for (idx, area) in order[start..end].iter().zip(&areas[start..end]) {
+                    let width = area / height;
+                    rects[*idx] = Rect::from_min_size(
+                        Pos2::new(free.left() + offset, free.top()),
+                        vec2(width, height),
+                    );
+                    offset += width;
+                }
+                free.min.y += height;
+            }
+            start = end;
+        }
+        rects
+    }
 }
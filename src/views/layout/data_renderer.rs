@@ -1,24 +1,52 @@
 use std::{
+    collections::HashMap,
     f32,
+    ops::Range,
     sync::{Arc, Mutex, mpsc::Sender},
 };
 
 use anyhow::Context;
-use egui::{Color32, Frame, Label, Margin, RichText, Sense, Stroke, Ui, UiBuilder, Widget};
+use egui::{
+    Align, Color32, Frame, Grid, Id, Label, Margin, Response, RichText, Sense, Stroke, Ui,
+    UiBuilder, Widget,
+};
+use parquet::schema::types::ColumnDescPtr;
+
+use crate::file::{
+    BLOOM_FILTER_HEADER, ByteInterval, COLUMN_INDEX, ColumnChunkLocation, EncodedPageHeader, Field,
+    HeaderOverhead, OFFSET_INDEX, PAGE_HEADER, PageBody, PageSegment, ParkhayDataSection,
+    ParkhayFooter, PruningReport, ReadRequest, SectionIndex, SectionMap, ThriftSchema,
+    UnknownField, format_bytes, format_plain_value,
+};
+use crate::views::RowsRequest;
 
-use crate::file::{ByteInterval, ParkhayDataSection, ReadRequest};
+use bloom_filter_renderer::BloomFilterRenderer;
+use column_index_renderer::ColumnIndexRenderer;
+use page_alignment_renderer::{ColumnPages, PageAlignmentRenderer};
+use page_body_renderer::PageBodyRenderer;
 
 use super::{
-    CORNER_RADIUS,
+    CORNER_RADIUS, FOCUS_HIGHLIGHT_COLOR, MATCH_HIGHLIGHT_COLOR,
     components::{CollapsibleSection, CollapsibleSectionIcon, LabeledValue},
+    navigation::{FooterLocation, Navigator, SectionHighlight, SectionPath},
+    thrift_renderer::ThriftRenderer,
 };
 
+mod bloom_filter_renderer;
+mod column_index_renderer;
+mod page_alignment_renderer;
+mod page_body_renderer;
+
 const LAYOUT_LABEL_SIZE: f32 = 16.;
 const HEADER_LABEL_SIZE: f32 = 15.;
 const HEADER_VALUE_SIZE: f32 = 14.;
 const DATA_BUTTON_SIZE: f32 = 11.;
 const DATA_PREVIEW_SIZE: f32 = 13.;
 const DATA_PREVIEW_APPROX_ROW_COUNT: usize = 15;
+const SKIPPED_SECTION_COLOR: Color32 = Color32::from_rgb(200, 200, 200);
+
+/// Page header overhead of each column, labeled with its index and path
+type ColumnHeaderOverheads = Vec<(String, HeaderOverhead)>;
 
 #[derive(Clone, Default, PartialEq)]
 enum PreviewState {
@@ -28,13 +56,57 @@ enum PreviewState {
     Pending,
 }
 
+/// State shared by all sections while rendering the data tree
+struct SectionContext<'a> {
+    root: &'a SectionMap,
+    footer: Option<&'a ParkhayFooter>,
+    reader_tx: &'a Sender<ReadRequest>,
+    navigator: &'a mut Navigator,
+    path: SectionPath,
+    /// Leaf column of the column chunk being rendered, used to decode statistics
+    column: Option<Field>,
+    /// Location of the column chunk being rendered, used to find the rows of its pages
+    location: Option<ColumnChunkLocation>,
+    pruning: Option<&'a PruningReport>,
+}
+
+impl SectionContext<'_> {
+    /// Highlight of the section at the end of `path`.
+    fn highlight(&self) -> SectionHighlight {
+        let mut highlight = self.navigator.highlight(&self.path);
+        highlight.is_skipped = match (self.pruning, self.path.last()) {
+            (Some(pruning), Some(byte_interval)) => !pruning.is_read(*byte_interval),
+            _ => false,
+        };
+        highlight
+    }
+}
+
 pub struct DataRenderer;
 impl DataRenderer {
-    pub fn render(ui: &mut Ui, data: &ParkhayDataSection, reader_tx: &mut Sender<ReadRequest>) {
+    pub fn render(
+        ui: &mut Ui,
+        data: &ParkhayDataSection,
+        footer: Option<&ParkhayFooter>,
+        reader_tx: &mut Sender<ReadRequest>,
+        navigator: &mut Navigator,
+        pruning: Option<&PruningReport>,
+    ) {
         match data {
             ParkhayDataSection::Root(sections) => {
+                let mut ctx = SectionContext {
+                    root: sections,
+                    footer,
+                    reader_tx,
+                    navigator,
+                    path: SectionPath::new(),
+                    column: None,
+                    location: None,
+                    pruning,
+                };
+                Self::render_file_header_overhead(ui, sections);
                 for (byte_interval, section) in sections {
-                    Self::render_section(ui, byte_interval, section, reader_tx.clone());
+                    Self::render_section(ui, byte_interval, section, &mut ctx, None);
                 }
             }
             _ => unreachable!(),
@@ -64,6 +136,49 @@ impl DataRenderer {
         );
     }
 
+    /// Render a plain-encoded value decoded using the column's type, followed by its raw bytes.
+    fn render_plain_value(
+        ui: &mut Ui,
+        label: impl AsRef<str>,
+        value: Option<&[u8]>,
+        column: Option<&Field>,
+    ) {
+        let Some(value) = value else {
+            Self::render_header_labeled_value(ui, label, "N/A");
+            return;
+        };
+        match column.and_then(|column| format_plain_value(column, value)) {
+            Some(decoded) => LabeledValue::show_with_raw(
+                ui,
+                RichText::new(label.as_ref())
+                    .monospace()
+                    .size(HEADER_LABEL_SIZE),
+                RichText::new(decoded).monospace().size(HEADER_VALUE_SIZE),
+                RichText::new(format_bytes(value))
+                    .monospace()
+                    .size(HEADER_VALUE_SIZE),
+            ),
+            None => Self::render_header_labeled_value(ui, label, format_bytes(value)),
+        }
+    }
+
+    /// Like `render_header_labeled_value`, but the value is a link. Returns whether it was clicked.
+    fn render_header_labeled_link(
+        ui: &mut Ui,
+        label: impl AsRef<str>,
+        value: impl AsRef<str>,
+    ) -> bool {
+        LabeledValue::show_link(
+            ui,
+            RichText::new(label.as_ref())
+                .monospace()
+                .size(HEADER_LABEL_SIZE),
+            RichText::new(value.as_ref())
+                .monospace()
+                .size(HEADER_VALUE_SIZE),
+        )
+    }
+
     fn render_header_value(ui: &mut Ui, value: impl AsRef<str>) {
         ui.label(
             RichText::new(value.as_ref())
@@ -91,62 +206,492 @@ impl DataRenderer {
         ui: &mut Ui,
         byte_interval: &ByteInterval,
         section: &ParkhayDataSection,
-        data_reader_tx: Sender<ReadRequest>,
+        ctx: &mut SectionContext,
+        footer_location: Option<FooterLocation>,
     ) {
+        ctx.path.push(*byte_interval);
+        if !ctx.navigator.is_visible(&ctx.path) {
+            ctx.path.pop();
+            return;
+        }
+
+        let highlight = ctx.highlight();
         ui.style_mut().visuals.collapsing_header_frame = true;
         match section {
             ParkhayDataSection::Root(_) => unreachable!(),
             ParkhayDataSection::RowGroup(idx, sections) => {
-                Self::render_collapsible_section(ui, &format!("Row Group: {idx}"), |ui| {
-                    for (byte_interval, subsection) in sections {
-                        Self::render_section(ui, byte_interval, subsection, data_reader_tx.clone());
-                    }
-                });
+                let location = FooterLocation {
+                    row_group: *idx as usize,
+                    column: None,
+                };
+                Self::render_collapsible_section(
+                    ui,
+                    &format!("Row Group: {idx}"),
+                    highlight,
+                    |ui| {
+                        Self::render_footer_link(ui, Some(location), ctx.navigator);
+                        let rows_request = ctx.footer.map(|_| RowsRequest {
+                            row_group: *idx as usize,
+                            rows: None,
+                        });
+                        Self::render_rows_link(ui, rows_request, ctx.navigator);
+                        if let Some(footer) = ctx.footer {
+                            Self::render_page_alignment(ui, *idx, footer, ctx.root, ctx.navigator);
+                            ui.separator();
+                        }
+                        for (byte_interval, subsection) in sections {
+                            Self::render_section(
+                                ui,
+                                byte_interval,
+                                subsection,
+                                ctx,
+                                Some(location),
+                            );
+                        }
+                    },
+                );
             }
-            ParkhayDataSection::ColumnChunk(idx, sections, _schema) => {
-                Self::render_collapsible_section(ui, &format!("Column Chunk: {idx}"), |ui| {
-                    for (byte_interval, subsection) in sections {
-                        Self::render_section(ui, byte_interval, subsection, data_reader_tx.clone());
-                    }
-                });
+            ParkhayDataSection::ColumnChunk(location, sections, schema) => {
+                let footer_location = ctx.footer.map(|_| FooterLocation::from(location));
+                Self::render_collapsible_section(
+                    ui,
+                    &format!("Column Chunk: {} ({})", location.column, location.path),
+                    highlight,
+                    |ui| {
+                        Self::render_footer_link(ui, footer_location, ctx.navigator);
+                        Self::render_column_schema(ui, schema);
+                        Self::render_header_overhead(ui, &HeaderOverhead::of(sections));
+                        ctx.column = Some(schema.clone());
+                        ctx.location = Some(location.clone());
+                        for (byte_interval, subsection) in sections {
+                            Self::render_section(
+                                ui,
+                                byte_interval,
+                                subsection,
+                                ctx,
+                                footer_location,
+                            );
+                        }
+                        ctx.column = None;
+                        ctx.location = None;
+                    },
+                );
             }
-            ParkhayDataSection::Page(idx, header, data) => {
-                Self::render_page(
+            ParkhayDataSection::RecoveredColumnChunk(idx, sections) => {
+                Self::render_collapsible_section(
                     ui,
-                    byte_interval,
-                    *idx as usize,
-                    header,
-                    data.clone(),
-                    data_reader_tx,
+                    &format!("Column Chunk (recovered): {idx}"),
+                    highlight,
+                    |ui| {
+                        Self::render_header_overhead(ui, &HeaderOverhead::of(sections));
+                        for (byte_interval, subsection) in sections {
+                            Self::render_section(ui, byte_interval, subsection, ctx, None);
+                        }
+                    },
                 );
             }
-            ParkhayDataSection::OffsetIndex(idx, offset_index) => {
-                Self::render_collapsible_section(ui, &format!("Offset Index: {idx}"), |ui| {
-                    Self::render_offset_index(ui, offset_index);
-                });
+            ParkhayDataSection::Page(..) => {
+                Self::render_page(ui, byte_interval, section, ctx, footer_location);
             }
-            ParkhayDataSection::ColumnIndex(idx, column_index) => {
-                Self::render_collapsible_section(ui, &format!("Column Index: {idx}"), |ui| {
-                    Self::render_column_index(ui, column_index);
-                });
+            // Segments need the header of their page, so they are rendered by it
+            ParkhayDataSection::PageSegment(_, _) => unreachable!(),
+            ParkhayDataSection::OffsetIndex(location, offset_index, unknown_fields) => {
+                let footer_location = ctx.footer.map(|_| FooterLocation::from(location));
+                Self::render_collapsible_section(
+                    ui,
+                    &format!("Offset Index: {location}"),
+                    highlight,
+                    |ui| {
+                        Self::render_footer_link(ui, footer_location, ctx.navigator);
+                        Self::render_offset_index(ui, offset_index, ctx.navigator);
+                        ui.separator();
+                        Self::render_encoded_struct(
+                            ui,
+                            "Encoded Offset Index",
+                            byte_interval,
+                            &OFFSET_INDEX,
+                            highlight,
+                            ctx,
+                        );
+                        Self::render_unknown_fields(ui, unknown_fields);
+                    },
+                );
             }
-            ParkhayDataSection::BloomFilter(idx, header, bitset) => {
-                Self::render_bloom_filter(
+            ParkhayDataSection::ColumnIndex(location, column_index, unknown_fields) => {
+                let offset_index = Self::find_offset_index(ctx.root, location);
+                let footer_location = ctx.footer.map(|_| FooterLocation::from(location));
+                Self::render_collapsible_section(
                     ui,
-                    byte_interval,
-                    *idx as usize,
-                    header,
-                    bitset.clone(),
-                    data_reader_tx,
+                    &format!("Column Index: {location}"),
+                    highlight,
+                    |ui| {
+                        Self::render_footer_link(ui, footer_location, ctx.navigator);
+                        let column = ctx.footer.and_then(|footer| {
+                            footer
+                                .column_descriptors()
+                                .get(location.column as usize)
+                                .cloned()
+                        });
+                        ColumnIndexRenderer::render(
+                            ui,
+                            ui.make_persistent_id(("column_index_ranges", *byte_interval)),
+                            column_index,
+                            offset_index,
+                            column.as_ref(),
+                        );
+                        ui.separator();
+                        Self::render_encoded_struct(
+                            ui,
+                            "Encoded Column Index",
+                            byte_interval,
+                            &COLUMN_INDEX,
+                            highlight,
+                            ctx,
+                        );
+                        Self::render_unknown_fields(ui, unknown_fields);
+                    },
                 );
             }
+            ParkhayDataSection::BloomFilter(..) => {
+                Self::render_bloom_filter(ui, byte_interval, section, ctx);
+            }
         }
         ui.style_mut().visuals.collapsing_header_frame = false;
+        ctx.path.pop();
+    }
+
+    /// Bytes spent on page headers in the whole file, and in each column across row groups.
+    /// The overhead is computed once, the first time the section is opened.
+    fn render_file_header_overhead(ui: &mut Ui, sections: &SectionMap) {
+        let has_column_chunks = sections.values().any(|section| {
+            matches!(
+                section,
+                ParkhayDataSection::RowGroup(..)
+                    | ParkhayDataSection::ColumnChunk(..)
+                    | ParkhayDataSection::RecoveredColumnChunk(..)
+            )
+        });
+        if !has_column_chunks {
+            return;
+        }
+
+        Self::render_header_collapsible(ui, "Page Header Overhead", |ui| {
+            let id = ui.make_persistent_id("file_header_overhead");
+            let columns = match ui.data(|d| d.get_temp::<Arc<ColumnHeaderOverheads>>(id)) {
+                Some(columns) => columns,
+                None => {
+                    let columns = Arc::new(Self::column_header_overheads(sections));
+                    ui.data_mut(|d| d.insert_temp(id, columns.clone()));
+                    columns
+                }
+            };
+
+            let mut total = HeaderOverhead::default();
+            for (_, overhead) in columns.iter() {
+                total += *overhead;
+            }
+            Self::render_header_overhead(ui, &total);
+            ui.separator();
+            let cell = |ui: &mut Ui, text: String| {
+                ui.label(RichText::new(text).monospace().size(HEADER_VALUE_SIZE));
+            };
+            Grid::new("Page Header Overhead")
+                .num_columns(5)
+                .spacing([10., 5.])
+                .striped(true)
+                .show(ui, |ui| {
+                    for title in ["Column", "Pages", "Header Bytes", "Share", "Oversized"] {
+                        ui.label(
+                            RichText::new(title)
+                                .monospace()
+                                .size(HEADER_VALUE_SIZE)
+                                .strong(),
+                        );
+                    }
+                    ui.end_row();
+
+                    for (column, overhead) in columns.iter() {
+                        cell(ui, column.clone());
+                        cell(ui, overhead.num_pages.to_string());
+                        cell(ui, overhead.header_bytes.to_string());
+                        cell(ui, format!("{:.2}%", 100. * overhead.ratio()));
+                        cell(ui, overhead.num_oversized.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.add_space(4.);
+    }
+
+    /// Page header overhead of each column, summed across row groups, in the order the columns
+    /// first appear.
+    fn column_header_overheads(sections: &SectionMap) -> ColumnHeaderOverheads {
+        let mut columns: ColumnHeaderOverheads = vec![];
+        let mut column_indices: HashMap<String, usize> = HashMap::new();
+        for section in sections.values() {
+            let chunks: Vec<_> = match section {
+                ParkhayDataSection::RowGroup(_, sections) => sections.values().collect(),
+                section => vec![section],
+            };
+            for chunk in chunks {
+                let (column, overhead) = match chunk {
+                    ParkhayDataSection::ColumnChunk(location, sections, _) => (
+                        format!("{} ({})", location.column, location.path),
+                        HeaderOverhead::of(sections),
+                    ),
+                    ParkhayDataSection::RecoveredColumnChunk(idx, sections) => {
+                        (format!("{idx} (recovered)"), HeaderOverhead::of(sections))
+                    }
+                    _ => continue,
+                };
+                match column_indices.get(&column) {
+                    Some(idx) => columns[*idx].1 += overhead,
+                    None => {
+                        column_indices.insert(column.clone(), columns.len());
+                        columns.push((column, overhead));
+                    }
+                }
+            }
+        }
+        columns
+    }
+
+    fn render_header_overhead(ui: &mut Ui, overhead: &HeaderOverhead) {
+        let mut summary = format!(
+            "{} headers, {} bytes ({:.2}% of the pages)",
+            overhead.num_pages,
+            overhead.header_bytes,
+            100. * overhead.ratio()
+        );
+        if overhead.num_oversized > 0 {
+            summary += &format!(", {} larger than their data", overhead.num_oversized);
+        }
+        Self::render_header_labeled_value(ui, "Page Headers", summary);
+    }
+
+    /// A metadata struct stored at `byte_interval`, annotated with the fields its bytes decode to.
+    /// It's revealed with the focused byte's field selected when the focus target is within it.
+    fn render_encoded_struct(
+        ui: &mut Ui,
+        header: &str,
+        byte_interval: &ByteInterval,
+        schema: &'static ThriftSchema,
+        highlight: SectionHighlight,
+        ctx: &mut SectionContext,
+    ) {
+        let focused_byte = Self::relative_byte(byte_interval, highlight);
+        CollapsibleSection::new(
+            RichText::new(header).monospace().size(HEADER_LABEL_SIZE),
+            CollapsibleSectionIcon::Default,
+            true,
+        )
+        .open(highlight.should_scroll && focused_byte.is_some())
+        .show(ui, |ui| {
+            ThriftRenderer::render_encoded(
+                ui,
+                ui.make_persistent_id(("encoded_struct", *byte_interval)),
+                *byte_interval,
+                schema,
+                focused_byte,
+                ctx.reader_tx,
+                ctx.navigator,
+            );
+        });
+    }
+
+    /// Fields of a struct missing from the format's schema, which are only shown if there are any.
+    fn render_unknown_fields(ui: &mut Ui, unknown_fields: &[UnknownField]) {
+        let Some(first) = unknown_fields.first() else {
+            return;
+        };
+        ui.separator();
+        Self::render_header_collapsible(ui, "Unknown Fields", |ui| {
+            ThriftRenderer::render_unknown_fields(
+                ui,
+                ui.make_persistent_id(("unknown_fields", first.byte_interval)),
+                unknown_fields,
+            );
+        });
+    }
+
+    fn render_column_schema(ui: &mut Ui, schema: &Field) {
+        Self::render_header_collapsible(ui, "Schema", |ui| {
+            Self::render_header_labeled_value(ui, "Name", schema.name());
+            ui.separator();
+            Self::render_header_labeled_value(
+                ui,
+                "Physical Type",
+                schema.get_physical_type().to_string(),
+            );
+            let basic_info = schema.get_basic_info();
+            if let Some(logical_type) = basic_info.logical_type() {
+                ui.separator();
+                Self::render_header_labeled_value(ui, "Logical Type", format!("{logical_type:?}"));
+            }
+            if basic_info.has_repetition() {
+                ui.separator();
+                Self::render_header_labeled_value(
+                    ui,
+                    "Repetition",
+                    basic_info.repetition().to_string(),
+                );
+            }
+        });
+    }
+
+    /// Pages of every column in the row group, aligned by row.
+    fn render_page_alignment(
+        ui: &mut Ui,
+        row_group: SectionIndex,
+        footer: &ParkhayFooter,
+        root: &SectionMap,
+        navigator: &mut Navigator,
+    ) {
+        Self::render_header_collapsible(ui, "Page Alignment", |ui| {
+            let num_rows = footer.row_groups[row_group as usize].num_rows;
+            let id = ui.make_persistent_id(("page_alignment", row_group));
+            let columns = match ui.data(|d| d.get_temp::<Arc<Vec<ColumnPages>>>(id)) {
+                Some(columns) => columns,
+                None => {
+                    let columns = Arc::new(Self::column_pages(row_group, num_rows, footer, root));
+                    ui.data_mut(|d| d.insert_temp(id, columns.clone()));
+                    columns
+                }
+            };
+            PageAlignmentRenderer::render(ui, &columns, num_rows, navigator);
+        });
+    }
+
+    /// Pages of every leaf column in the row group, from their offset indexes.
+    fn column_pages(
+        row_group: SectionIndex,
+        num_rows: i64,
+        footer: &ParkhayFooter,
+        root: &SectionMap,
+    ) -> Vec<ColumnPages> {
+        footer
+            .column_paths()
+            .into_iter()
+            .enumerate()
+            .map(|(column, path)| {
+                let location = ColumnChunkLocation {
+                    row_group,
+                    column: column as SectionIndex,
+                    path,
+                };
+                ColumnPages::new(
+                    location.path.string(),
+                    Self::find_offset_index(root, &location),
+                    num_rows,
+                )
+            })
+            .collect()
+    }
+
+    /// The offset index of the same column chunk, which is stored with the other page indexes.
+    fn find_offset_index<'a>(
+        sections: &'a SectionMap,
+        location: &ColumnChunkLocation,
+    ) -> Option<&'a parquet::format::OffsetIndex> {
+        sections.values().find_map(|section| match section {
+            ParkhayDataSection::OffsetIndex(l, offset_index, _) if l == location => {
+                Some(offset_index)
+            }
+            _ => None,
+        })
+    }
+
+    fn render_footer_link(
+        ui: &mut Ui,
+        location: Option<FooterLocation>,
+        navigator: &mut Navigator,
+    ) {
+        if let Some(location) = location {
+            let clicked = ui
+                .link(
+                    RichText::new("Footer Metadata ↗")
+                        .monospace()
+                        .size(DATA_BUTTON_SIZE),
+                )
+                .clicked();
+            if clicked {
+                navigator.focus_footer(location);
+            }
+        }
+    }
+
+    fn render_rows_link(ui: &mut Ui, request: Option<RowsRequest>, navigator: &mut Navigator) {
+        if let Some(request) = request {
+            let clicked = ui
+                .link(
+                    RichText::new("Show Rows ↗")
+                        .monospace()
+                        .size(DATA_BUTTON_SIZE),
+                )
+                .on_hover_text("Decode the rows in the rows view")
+                .clicked();
+            if clicked {
+                navigator.request_rows(ui, request);
+            }
+        }
+    }
+
+    /// The rows held by a data page, found by matching its byte range in the offset index.
+    fn page_rows(ctx: &SectionContext, byte_interval: &ByteInterval) -> Option<RowsRequest> {
+        let location = ctx.location.as_ref()?;
+        let row_group = ctx.footer?.row_groups.get(location.row_group as usize)?;
+        let page_locations = &Self::find_offset_index(ctx.root, location)?.page_locations;
+        // Offset index page sizes include the page header, which the page's interval excludes
+        let page_idx = page_locations.iter().position(|page| {
+            (page.offset + page.compressed_page_size as i64 - 1) as u64 == byte_interval.1
+        })?;
+        let first_row = page_locations[page_idx].first_row_index;
+        let end_row = page_locations
+            .get(page_idx + 1)
+            .map_or(row_group.num_rows, |next| next.first_row_index);
+        Some(RowsRequest {
+            row_group: location.row_group as usize,
+            rows: Some((first_row, end_row)),
+        })
+    }
+
+    fn section_frame(ui: &Ui, response: &Response, highlight: SectionHighlight) -> Frame {
+        let visuals = *ui.style().interact(response);
+
+        let (bg_stroke, bg_fill) = if highlight.is_focused {
+            (
+                Stroke::new(2., FOCUS_HIGHLIGHT_COLOR),
+                FOCUS_HIGHLIGHT_COLOR.gamma_multiply(0.15),
+            )
+        } else if highlight.is_match {
+            (
+                Stroke::new(1.5, MATCH_HIGHLIGHT_COLOR),
+                MATCH_HIGHLIGHT_COLOR.gamma_multiply(0.15),
+            )
+        } else if highlight.is_skipped {
+            (
+                Stroke::new(1., SKIPPED_SECTION_COLOR),
+                SKIPPED_SECTION_COLOR.gamma_multiply(0.3),
+            )
+        } else {
+            (
+                Stroke::new(1., visuals.bg_stroke.color),
+                visuals.bg_fill.gamma_multiply(0.2),
+            )
+        };
+
+        Frame::canvas(ui.style())
+            .corner_radius(CORNER_RADIUS)
+            .fill(bg_fill)
+            .stroke(bg_stroke)
+            .inner_margin(ui.spacing().menu_margin)
+            .outer_margin(Margin::ZERO)
     }
 
     fn render_collapsible_section(
         ui: &mut Ui,
         identifier: &str,
+        highlight: SectionHighlight,
         section_content: impl FnOnce(&mut Ui),
     ) {
         let id = ui.make_persistent_id(identifier);
@@ -155,221 +700,460 @@ impl DataRenderer {
             let mut is_visible =
                 ui.data_mut(|d| *d.get_temp_mut_or_insert_with::<bool>(id, || false));
 
-            if response.clicked() {
+            if response.clicked() || (highlight.should_reveal && !is_visible) {
                 ui.data_mut(|d| {
                     d.insert_temp(id, !is_visible);
                     is_visible = !is_visible;
                 })
             }
 
-            let visuals = *ui.style().interact(&response);
-
-            let bg_stroke = Stroke::new(1., visuals.bg_stroke.color);
-            let bg_fill = visuals.bg_fill.gamma_multiply(0.2);
+            let frame_response = Self::section_frame(ui, &response, highlight).show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                Self::render_layout_label(ui, identifier);
+                if is_visible {
+                    ui.scope(section_content);
+                }
+            });
 
-            Frame::canvas(ui.style())
-                .corner_radius(CORNER_RADIUS)
-                .fill(bg_fill)
-                .stroke(bg_stroke)
-                .inner_margin(ui.spacing().menu_margin)
-                .outer_margin(Margin::ZERO)
-                .show(ui, |ui| {
-                    ui.set_width(ui.available_width());
-                    Self::render_layout_label(ui, identifier);
-                    if is_visible {
-                        ui.scope(section_content);
-                    }
-                });
+            if highlight.should_scroll {
+                frame_response.response.scroll_to_me(Some(Align::Min));
+            }
         });
     }
 
     fn render_page(
         ui: &mut Ui,
         byte_interval: &ByteInterval,
-        page_idx: usize,
-        page_header: &parquet::format::PageHeader,
-        page_data: Arc<Mutex<Option<Vec<u8>>>>,
-        data_reader_tx: Sender<ReadRequest>,
+        page: &ParkhayDataSection,
+        ctx: &mut SectionContext,
+        footer_location: Option<FooterLocation>,
     ) {
+        let ParkhayDataSection::Page(page_idx, encoded_header, page_data, segments) = page else {
+            unreachable!()
+        };
+        let page_header = &encoded_header.header;
         let identifier = format!("Page: {page_idx}");
+        let highlight = ctx.highlight();
 
         let id = ui.make_persistent_id(&identifier);
         ui.scope_builder(UiBuilder::new().id_salt(id).sense(Sense::click()), |ui| {
             let response = ui.response();
-            let visuals = *ui.style().interact(&response);
 
-            let bg_stroke = Stroke::new(1., visuals.bg_stroke.color);
-            let bg_fill = visuals.bg_fill.gamma_multiply(0.2);
+            let frame_response = Self::section_frame(ui, &response, highlight).show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                Self::render_layout_label(ui, identifier);
+                Self::render_footer_link(ui, footer_location, ctx.navigator);
+                Self::render_rows_link(ui, Self::page_rows(ctx, byte_interval), ctx.navigator);
 
-            Frame::canvas(ui.style())
-                .corner_radius(CORNER_RADIUS)
-                .fill(bg_fill)
-                .stroke(bg_stroke)
-                .inner_margin(ui.spacing().menu_margin)
-                .outer_margin(Margin::ZERO)
-                .show(ui, |ui| {
-                    ui.set_width(ui.available_width());
-                    Self::render_layout_label(ui, identifier);
+                Self::render_page_header(ui, encoded_header, ctx.column.as_ref());
 
-                    Self::render_page_header(ui, page_header);
+                ui.separator();
 
-                    ui.separator();
+                Self::render_encoded_struct(
+                    ui,
+                    "Encoded Header",
+                    &encoded_header.byte_interval,
+                    &PAGE_HEADER,
+                    highlight,
+                    ctx,
+                );
+                Self::render_unknown_fields(ui, &encoded_header.unknown_fields);
 
-                    // Get current preview state, setting it to default if it's not set
-                    let current_state =
-                        ui.data_mut(|d| d.get_temp_mut_or_default::<PreviewState>(id).clone());
+                ui.separator();
 
-                    // Compute next state based on current state and UI interactions
-                    let next_state = match current_state {
-                        PreviewState::Hidden => {
-                            let button_clicked = ui
-                                .vertical_centered_justified(|ui| {
-                                    ui.button(
-                                        RichText::new("Show Preview")
-                                            .monospace()
-                                            .size(DATA_BUTTON_SIZE)
-                                            .strong(),
-                                    )
-                                    .clicked()
-                                })
-                                .inner;
-
-                            if button_clicked {
-                                if let Ok(pd) = page_data.lock() {
-                                    if pd.is_some() {
-                                        PreviewState::Visible
-                                    } else {
-                                        PreviewState::Pending
-                                    }
-                                } else {
-                                    panic!("Can't get lock on page data");
-                                }
-                            } else {
-                                current_state.clone()
-                            }
-                        }
-                        PreviewState::Visible => {
-                            let button_clicked = ui
-                                .vertical_centered_justified(|ui| {
-                                    ui.button(
-                                        RichText::new("Hide Preview")
-                                            .monospace()
-                                            .size(DATA_BUTTON_SIZE)
-                                            .strong(),
-                                    )
-                                    .clicked()
-                                })
-                                .inner;
+                Self::render_preview(
+                    ui,
+                    id,
+                    byte_interval,
+                    page_data,
+                    highlight,
+                    ctx,
+                    |ui, bytes, ctx| Self::page_body(ui, id, page_header, bytes, ctx),
+                );
 
-                            if button_clicked {
-                                PreviewState::Hidden
-                            } else {
-                                current_state.clone()
-                            }
-                        }
-                        PreviewState::Pending => {
-                            if let Ok(pd) = page_data.lock() {
-                                if pd.is_some() {
-                                    PreviewState::Visible
-                                } else {
-                                    ui.vertical_centered_justified(|ui| {
-                                        ui.add_enabled(
-                                            false,
-                                            egui::Button::new(
-                                                RichText::new("Show Preview")
-                                                    .monospace()
-                                                    .size(DATA_BUTTON_SIZE)
-                                                    .strong(),
-                                            ),
-                                        );
-                                    });
-                                    current_state.clone()
-                                }
-                            } else {
-                                panic!("Can't get lock on page data");
-                            }
-                        }
+                for (segment_interval, segment) in segments {
+                    let ParkhayDataSection::PageSegment(segment, segment_data) = segment else {
+                        unreachable!()
                     };
+                    ctx.path.push(*segment_interval);
+                    if ctx.navigator.is_visible(&ctx.path) {
+                        Self::render_page_segment(
+                            ui,
+                            segment_interval,
+                            *segment,
+                            segment_data,
+                            page_header,
+                            ctx,
+                        );
+                    }
+                    ctx.path.pop();
+                }
+            });
 
-                    // Store next state
-                    ui.data_mut(|d| {
-                        d.insert_temp(id, next_state.clone());
-                    });
-
-                    match (current_state, next_state) {
-                        // Show cached data
-                        // Show newly fetched data
-                        // Keep showing cached data
-                        (PreviewState::Hidden, PreviewState::Visible)
-                        | (PreviewState::Pending, PreviewState::Visible)
-                        | (PreviewState::Visible, PreviewState::Visible) => {
-                            if let Ok(pd) = page_data.lock() {
-                                if let Some(ref pd_bytes) = *pd {
-                                    Self::render_data_preview(ui, pd_bytes);
-                                }
-                            } else {
-                                panic!("Can't get lock on page data");
-                            }
-                        }
-                        // Fetch data
-                        (PreviewState::Hidden, PreviewState::Pending) => {
-                            data_reader_tx
-                                .send(ReadRequest(*byte_interval, page_data.clone()))
-                                .context("Couldn't send message to reader thread")
-                                .unwrap();
-                        }
-                        // Invalid states
-                        (PreviewState::Visible, PreviewState::Pending)
-                        | (PreviewState::Pending, PreviewState::Hidden) => {
-                            unreachable!()
+            if highlight.should_scroll {
+                frame_response.response.scroll_to_me(Some(Align::Min));
+            }
+        });
+    }
+
+    /// Segment of a V2 data page, with its own preview and decoding.
+    fn render_page_segment(
+        ui: &mut Ui,
+        byte_interval: &ByteInterval,
+        segment: PageSegment,
+        segment_data: &Arc<Mutex<Option<Vec<u8>>>>,
+        page_header: &parquet::format::PageHeader,
+        ctx: &mut SectionContext,
+    ) {
+        let identifier = format!("Segment: {segment}");
+        let highlight = ctx.highlight();
+        let id = ui.make_persistent_id(&identifier).with("preview");
+        Self::render_collapsible_section(ui, &identifier, highlight, |ui| {
+            Self::render_header_labeled_value(
+                ui,
+                "Bytes",
+                format!(
+                    "{}..={} ({} bytes)",
+                    byte_interval.0,
+                    byte_interval.1,
+                    byte_interval.1 - byte_interval.0 + 1
+                ),
+            );
+            if segment == PageSegment::Values {
+                let is_compressed = page_header
+                    .data_page_header_v2
+                    .as_ref()
+                    .and_then(|header| header.is_compressed)
+                    .unwrap_or(true);
+                Self::render_header_labeled_value(ui, "Compressed", is_compressed.to_string());
+            }
+            ui.separator();
+            Self::render_preview(
+                ui,
+                id,
+                byte_interval,
+                segment_data,
+                highlight,
+                ctx,
+                |ui, bytes, ctx| Self::segment_body(ui, id, page_header, segment, bytes, ctx),
+            );
+        });
+    }
+
+    /// Preview of a page's or segment's bytes, which are read when it is first shown, followed by
+    /// the streams they decode to.
+    fn render_preview(
+        ui: &mut Ui,
+        id: Id,
+        byte_interval: &ByteInterval,
+        page_data: &Arc<Mutex<Option<Vec<u8>>>>,
+        highlight: SectionHighlight,
+        ctx: &mut SectionContext,
+        decode: impl FnOnce(&Ui, &[u8], &SectionContext) -> Option<Arc<Result<PageBody, String>>>,
+    ) {
+        // Get current preview state, setting it to default if it's not set
+        let current_state = ui.data_mut(|d| d.get_temp_mut_or_default::<PreviewState>(id).clone());
+
+        let reveal_preview =
+            highlight.should_scroll && Self::relative_byte(byte_interval, highlight).is_some();
+
+        // Compute next state based on current state and UI interactions
+        let next_state = match current_state {
+            PreviewState::Hidden => {
+                let button_clicked = ui
+                    .vertical_centered_justified(|ui| {
+                        ui.button(
+                            RichText::new("Show Preview")
+                                .monospace()
+                                .size(DATA_BUTTON_SIZE)
+                                .strong(),
+                        )
+                        .clicked()
+                    })
+                    .inner;
+
+                // Jumping to a byte in the page opens its preview
+                if button_clicked || reveal_preview {
+                    if let Ok(pd) = page_data.lock() {
+                        if pd.is_some() {
+                            PreviewState::Visible
+                        } else {
+                            PreviewState::Pending
                         }
-                        // Don't show any data
-                        (PreviewState::Visible, PreviewState::Hidden)
-                        | (PreviewState::Pending, PreviewState::Pending)
-                        | (PreviewState::Hidden, PreviewState::Hidden) => {}
+                    } else {
+                        panic!("Can't get lock on page data");
                     }
-                });
+                } else {
+                    current_state.clone()
+                }
+            }
+            PreviewState::Visible => {
+                let button_clicked = ui
+                    .vertical_centered_justified(|ui| {
+                        ui.button(
+                            RichText::new("Hide Preview")
+                                .monospace()
+                                .size(DATA_BUTTON_SIZE)
+                                .strong(),
+                        )
+                        .clicked()
+                    })
+                    .inner;
+
+                if button_clicked {
+                    PreviewState::Hidden
+                } else {
+                    current_state.clone()
+                }
+            }
+            PreviewState::Pending => {
+                if let Ok(pd) = page_data.lock() {
+                    if pd.is_some() {
+                        PreviewState::Visible
+                    } else {
+                        ui.vertical_centered_justified(|ui| {
+                            ui.add_enabled(
+                                false,
+                                egui::Button::new(
+                                    RichText::new("Show Preview")
+                                        .monospace()
+                                        .size(DATA_BUTTON_SIZE)
+                                        .strong(),
+                                ),
+                            );
+                        });
+                        current_state.clone()
+                    }
+                } else {
+                    panic!("Can't get lock on page data");
+                }
+            }
+        };
+
+        // Store next state
+        ui.data_mut(|d| {
+            d.insert_temp(id, next_state.clone());
         });
+
+        match (current_state, next_state) {
+            // Show cached data
+            // Show newly fetched data
+            // Keep showing cached data
+            (PreviewState::Hidden, PreviewState::Visible)
+            | (PreviewState::Pending, PreviewState::Visible)
+            | (PreviewState::Visible, PreviewState::Visible) => {
+                if let Ok(pd) = page_data.lock() {
+                    if let Some(ref pd_bytes) = *pd {
+                        let page_body = decode(ui, pd_bytes, ctx);
+                        let highlighted = Self::relative_byte(byte_interval, highlight)
+                            .map(|idx| idx..idx + 1)
+                            .or_else(|| {
+                                let body = page_body.as_deref()?.as_ref().ok()?;
+                                PageBodyRenderer::selected_bytes(ui, id, body)
+                            });
+                        Self::render_data_preview(ui, pd_bytes, highlighted);
+                        if let Some(page_body) = page_body {
+                            ui.separator();
+                            Self::render_header_collapsible(ui, "Encoded Streams", |ui| {
+                                match page_body.as_ref() {
+                                    Ok(body) => PageBodyRenderer::render(ui, id, body),
+                                    Err(e) => Self::render_header_value(ui, e),
+                                }
+                            });
+                        }
+                    }
+                } else {
+                    panic!("Can't get lock on page data");
+                }
+            }
+            // Fetch data
+            (PreviewState::Hidden, PreviewState::Pending) => {
+                ctx.reader_tx
+                    .send(ReadRequest(*byte_interval, page_data.clone()))
+                    .context("Couldn't send message to reader thread")
+                    .unwrap();
+            }
+            // Invalid states
+            (PreviewState::Visible, PreviewState::Pending)
+            | (PreviewState::Pending, PreviewState::Hidden) => {
+                unreachable!()
+            }
+            // Don't show any data
+            (PreviewState::Visible, PreviewState::Hidden)
+            | (PreviewState::Pending, PreviewState::Pending)
+            | (PreviewState::Hidden, PreviewState::Hidden) => {}
+        }
+    }
+
+    /// The decompressed body of a data page, parsed once its bytes have been read.
+    fn page_body(
+        ui: &Ui,
+        id: Id,
+        page_header: &parquet::format::PageHeader,
+        pd_bytes: &[u8],
+        ctx: &SectionContext,
+    ) -> Option<Arc<Result<PageBody, String>>> {
+        if page_header.data_page_header.is_none() && page_header.data_page_header_v2.is_none() {
+            return None;
+        }
+        let body_id = id.with("page_body");
+        if let Some(page_body) = ui.data(|d| d.get_temp(body_id)) {
+            return Some(page_body);
+        }
+
+        let (codec, column) = Self::codec_and_column(ctx)?;
+        let page_body = Arc::new(
+            PageBody::new(page_header, codec, &column, pd_bytes).map_err(|e| format!("{e:#}")),
+        );
+        ui.data_mut(|d| d.insert_temp(body_id, page_body.clone()));
+        Some(page_body)
+    }
+
+    /// The decoded segment of a V2 data page, parsed once its bytes have been read.
+    fn segment_body(
+        ui: &Ui,
+        id: Id,
+        page_header: &parquet::format::PageHeader,
+        segment: PageSegment,
+        segment_bytes: &[u8],
+        ctx: &SectionContext,
+    ) -> Option<Arc<Result<PageBody, String>>> {
+        let body_id = id.with("segment_body");
+        if let Some(segment_body) = ui.data(|d| d.get_temp(body_id)) {
+            return Some(segment_body);
+        }
+
+        let (codec, column) = Self::codec_and_column(ctx)?;
+        let segment_body = Arc::new(
+            PageBody::from_segment(page_header, codec, &column, segment, segment_bytes)
+                .map_err(|e| format!("{e:#}")),
+        );
+        ui.data_mut(|d| d.insert_temp(body_id, segment_body.clone()));
+        Some(segment_body)
     }
 
-    fn render_data_preview(ui: &mut Ui, pd_bytes: &[u8]) {
+    /// The codec and descriptor of the column chunk being rendered, which are only known from the
+    /// footer.
+    fn codec_and_column(
+        ctx: &SectionContext,
+    ) -> Option<(parquet::format::CompressionCodec, ColumnDescPtr)> {
+        let location = ctx.location.as_ref()?;
+        let footer = ctx.footer?;
+        let codec = footer
+            .row_groups
+            .get(location.row_group as usize)?
+            .columns
+            .get(location.column as usize)?
+            .meta_data
+            .as_ref()?
+            .codec;
+        let column = footer
+            .column_descriptors()
+            .get(location.column as usize)?
+            .clone();
+        Some((codec, column))
+    }
+
+    /// Position of the focused byte within the byte interval, if it falls inside it.
+    fn relative_byte(byte_interval: &ByteInterval, highlight: SectionHighlight) -> Option<usize> {
+        highlight
+            .focused_byte
+            .filter(|byte| (byte_interval.0..=byte_interval.1).contains(byte))
+            .map(|byte| (byte - byte_interval.0) as usize)
+    }
+
+    fn render_data_preview(ui: &mut Ui, pd_bytes: &[u8], highlighted: Option<Range<usize>>) {
         let font_id = egui::FontId::monospace(DATA_PREVIEW_SIZE);
         let char_width = ui.fonts(|fonts| {
             fonts.glyph_width(&font_id, 'a') // Pick an arbitrary char since the font is monospace
         });
 
-        let byte_count_per_row = (ui.available_width() / (2. * char_width)).floor() as usize; // 2 hex chars for each byte
+        let byte_count_per_row =
+            ((ui.available_width() / (2. * char_width)).floor() as usize).max(1); // 2 hex chars for each byte
         let bytes_to_take = byte_count_per_row * DATA_PREVIEW_APPROX_ROW_COUNT;
 
-        let hex_string = pd_bytes
-            .iter()
-            .take(bytes_to_take)
-            .map(|b| format!("{b:02x}"))
-            .collect::<String>();
+        // Start a couple of rows above the highlighted bytes so that they have some context
+        let bytes_to_skip = highlighted
+            .as_ref()
+            .map(|range| (range.start / byte_count_per_row).saturating_sub(2) * byte_count_per_row)
+            .unwrap_or(0);
 
-        ui.label(
-            RichText::new(hex_string)
-                .monospace()
-                .size(DATA_PREVIEW_SIZE),
-        );
+        let hex_string =
+            |bytes: &[u8]| bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+        let format = egui::TextFormat::simple(font_id.clone(), ui.visuals().text_color());
+        let mut job = egui::text::LayoutJob::default();
+        let window_end = (bytes_to_skip + bytes_to_take).min(pd_bytes.len());
+        match highlighted.filter(|range| range.start < window_end) {
+            Some(range) => {
+                let end = range.end.min(window_end);
+                job.append(
+                    &hex_string(&pd_bytes[bytes_to_skip..range.start]),
+                    0.,
+                    format.clone(),
+                );
+                job.append(
+                    &hex_string(&pd_bytes[range.start..end]),
+                    0.,
+                    egui::TextFormat {
+                        background: FOCUS_HIGHLIGHT_COLOR,
+                        ..format.clone()
+                    },
+                );
+                job.append(&hex_string(&pd_bytes[end..window_end]), 0., format);
+            }
+            None => job.append(
+                &hex_string(&pd_bytes[bytes_to_skip..window_end]),
+                0.,
+                format,
+            ),
+        }
+
+        if bytes_to_skip > 0 {
+            Self::render_preview_notice(ui, format!("Skipped {bytes_to_skip} bytes for preview."));
+        }
+
+        ui.label(job);
 
         let pd_bytes_len = pd_bytes.len();
-        if bytes_to_take < pd_bytes_len {
-            ui.add(egui::Label::new(
-                RichText::new(format!(
-                    "Truncated {} bytes for preview.",
-                    pd_bytes_len - bytes_to_take
-                ))
+        if window_end < pd_bytes_len {
+            Self::render_preview_notice(
+                ui,
+                format!("Truncated {} bytes for preview.", pd_bytes_len - window_end),
+            );
+        }
+    }
+
+    fn render_preview_notice(ui: &mut Ui, notice: String) {
+        ui.add(egui::Label::new(
+            RichText::new(notice)
                 .size(DATA_BUTTON_SIZE)
                 .background_color(Color32::from_rgb(250, 230, 170))
                 .monospace()
                 .strong(),
-            ));
-        }
+        ));
     }
 
-    fn render_page_header(ui: &mut Ui, page_header: &parquet::format::PageHeader) {
+    fn render_page_header(ui: &mut Ui, encoded_header: &EncodedPageHeader, column: Option<&Field>) {
+        let page_header = &encoded_header.header;
         Self::render_header_collapsible(ui, "Page Header", |ui| {
+            let (header_size, data_size) = (
+                encoded_header.size(),
+                page_header.compressed_page_size.max(0) as u64,
+            );
+            let header_bytes = format!(
+                "{}..={} ({header_size} bytes, {:.1}% of the page)",
+                encoded_header.byte_interval.0,
+                encoded_header.byte_interval.1,
+                100. * header_size as f64 / (header_size + data_size).max(1) as f64,
+            );
+            Self::render_header_labeled_value(ui, "Header Bytes", header_bytes);
+            if header_size > data_size {
+                Self::render_preview_notice(
+                    ui,
+                    String::from("The header is larger than the page's data."),
+                );
+            }
+            ui.separator();
             Self::render_header_labeled_value(
                 ui,
                 "Page Type",
@@ -402,7 +1186,7 @@ impl DataRenderer {
             ui.separator();
             Self::render_header_collapsible(ui, "Data Page Header", |ui| {
                 if let Some(data_page_header) = &page_header.data_page_header {
-                    Self::render_data_page_header(ui, data_page_header);
+                    Self::render_data_page_header(ui, data_page_header, column);
                 } else {
                     Self::render_header_value(ui, "N/A");
                 }
@@ -418,7 +1202,7 @@ impl DataRenderer {
             ui.separator();
             Self::render_header_collapsible(ui, "Data Page Header V2", |ui| {
                 if let Some(data_page_header_v2) = &page_header.data_page_header_v2 {
-                    Self::render_data_page_header_v2(ui, data_page_header_v2);
+                    Self::render_data_page_header_v2(ui, data_page_header_v2, column);
                 } else {
                     Self::render_header_value(ui, "N/A");
                 }
@@ -426,7 +1210,11 @@ impl DataRenderer {
         });
     }
 
-    fn render_data_page_header(ui: &mut Ui, data_page_header: &parquet::format::DataPageHeader) {
+    fn render_data_page_header(
+        ui: &mut Ui,
+        data_page_header: &parquet::format::DataPageHeader,
+        column: Option<&Field>,
+    ) {
         Self::render_header_labeled_value(
             ui,
             "Num Values",
@@ -459,7 +1247,7 @@ impl DataRenderer {
         ui.separator();
         Self::render_header_collapsible(ui, "Statistics", |ui| {
             if let Some(statistics) = &data_page_header.statistics {
-                Self::render_statistics(ui, statistics);
+                Self::render_statistics(ui, statistics, column);
             } else {
                 Self::render_header_value(ui, "N/A");
             }
@@ -469,6 +1257,7 @@ impl DataRenderer {
     fn render_data_page_header_v2(
         ui: &mut Ui,
         data_page_header_v2: &parquet::format::DataPageHeaderV2,
+        column: Option<&Field>,
     ) {
         Self::render_header_labeled_value(
             ui,
@@ -519,7 +1308,7 @@ impl DataRenderer {
         ui.separator();
         Self::render_header_collapsible(ui, "Statistics", |ui| {
             if let Some(statistics) = &data_page_header_v2.statistics {
-                Self::render_statistics(ui, statistics);
+                Self::render_statistics(ui, statistics, column);
             } else {
                 Self::render_header_value(ui, "N/A");
             }
@@ -554,26 +1343,14 @@ impl DataRenderer {
         );
     }
 
-    fn render_statistics(ui: &mut Ui, statistics: &parquet::format::Statistics) {
-        Self::render_header_labeled_value(
-            ui,
-            "Max (deprecated)",
-            statistics
-                .max
-                .as_ref()
-                .map(|v| format!("{v:?}"))
-                .unwrap_or(String::from("N/A")),
-        );
+    fn render_statistics(
+        ui: &mut Ui,
+        statistics: &parquet::format::Statistics,
+        column: Option<&Field>,
+    ) {
+        Self::render_plain_value(ui, "Max (deprecated)", statistics.max.as_deref(), column);
         ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Min (deprecated)",
-            statistics
-                .min
-                .as_ref()
-                .map(|v| format!("{v:?}"))
-                .unwrap_or(String::from("N/A")),
-        );
+        Self::render_plain_value(ui, "Min (deprecated)", statistics.min.as_deref(), column);
         ui.separator();
         Self::render_header_labeled_value(
             ui,
@@ -593,25 +1370,9 @@ impl DataRenderer {
                 .unwrap_or(String::from("N/A")),
         );
         ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Max Value",
-            statistics
-                .max_value
-                .as_ref()
-                .map(|v| format!("{v:?}"))
-                .unwrap_or(String::from("N/A")),
-        );
+        Self::render_plain_value(ui, "Max Value", statistics.max_value.as_deref(), column);
         ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Min Value",
-            statistics
-                .min_value
-                .as_ref()
-                .map(|v| format!("{v:?}"))
-                .unwrap_or(String::from("N/A")),
-        );
+        Self::render_plain_value(ui, "Min Value", statistics.min_value.as_deref(), column);
         ui.separator();
         Self::render_header_labeled_value(
             ui,
@@ -632,76 +1393,24 @@ impl DataRenderer {
         );
     }
 
-    fn render_column_index(ui: &mut Ui, column_index: &parquet::format::ColumnIndex) {
-        Self::render_header_labeled_value(
-            ui,
-            "Null Pages",
-            format!("{:?}", column_index.null_pages),
-        );
-        ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Min Values",
-            format!("{:?}", column_index.min_values),
-        );
-        ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Max Values",
-            format!("{:?}", column_index.max_values),
-        );
-        ui.separator();
-        // TODO find a better way to find human-readable boundary order
-        let boundary_order = match column_index.boundary_order.0 {
-            0 => "UNORDERED",
-            1 => "ASCENDING",
-            2 => "DESCENDING",
-            n => panic!("Unexpected boundary Order {n} found"),
-        };
-        Self::render_header_labeled_value(ui, "Boundary Order", boundary_order);
-        ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Null Counts",
-            column_index
-                .null_counts
-                .as_ref()
-                .map(|v| format!("{v:?}"))
-                .unwrap_or(String::from("N/A")),
-        );
-        ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Repetition Level Histograms",
-            column_index
-                .repetition_level_histograms
-                .as_ref()
-                .map(|v| format!("{v:?}"))
-                .unwrap_or(String::from("N/A")),
-        );
-        ui.separator();
-        Self::render_header_labeled_value(
-            ui,
-            "Definition Level Histograms",
-            column_index
-                .definition_level_histograms
-                .as_ref()
-                .map(|v| format!("{v:?}"))
-                .unwrap_or(String::from("N/A")),
-        );
-    }
-
-    fn render_offset_index(ui: &mut Ui, offset_index: &parquet::format::OffsetIndex) {
+    fn render_offset_index(
+        ui: &mut Ui,
+        offset_index: &parquet::format::OffsetIndex,
+        navigator: &mut Navigator,
+    ) {
         for (page_offset_idx, page_location) in offset_index.page_locations.iter().enumerate() {
             let identifier = format!("Page Location: {page_offset_idx}");
             let id = ui.make_persistent_id(&identifier);
             ui.push_id(id, |ui| {
                 Self::render_header_collapsible(ui, identifier, |ui| {
-                    Self::render_header_labeled_value(
+                    let clicked = Self::render_header_labeled_link(
                         ui,
                         "Offset",
                         page_location.offset.to_string(),
                     );
+                    if clicked {
+                        navigator.request_offset(ui, page_location.offset as u64);
+                    }
                     ui.separator();
                     Self::render_header_labeled_value(
                         ui,
@@ -733,25 +1442,78 @@ impl DataRenderer {
     fn render_bloom_filter(
         ui: &mut Ui,
         byte_interval: &ByteInterval,
-        bf_idx: usize,
-        header: &parquet::format::BloomFilterHeader,
-        bitset: Arc<Mutex<Option<Vec<u8>>>>,
-        reader_tx: Sender<ReadRequest>,
+        bloom_filter: &ParkhayDataSection,
+        ctx: &mut SectionContext,
     ) {
-        let identifier = format!("Bloom Filter: {bf_idx}");
+        let ParkhayDataSection::BloomFilter(
+            bf_idx,
+            location,
+            header,
+            header_interval,
+            unknown_fields,
+            bitset,
+        ) = bloom_filter
+        else {
+            unreachable!()
+        };
+        let location = location.as_ref();
+        let identifier = match location {
+            Some(location) => format!("Bloom Filter: {location}"),
+            None => format!("Bloom Filter (recovered): {bf_idx}"),
+        };
+        let highlight = ctx.highlight();
+        let column = location
+            .zip(ctx.footer)
+            .and_then(|(location, footer)| footer.leaves().get(location.column as usize).cloned());
+        let metadata = location.zip(ctx.footer).and_then(|(location, footer)| {
+            footer.row_groups[location.row_group as usize].columns[location.column as usize]
+                .meta_data
+                .as_ref()
+        });
+        let location = location
+            .filter(|_| ctx.footer.is_some())
+            .map(FooterLocation::from);
 
         let id = ui.make_persistent_id(&identifier);
-        Self::render_collapsible_section(ui, &identifier, |ui| {
+        Self::render_collapsible_section(ui, &identifier, highlight, |ui| {
             ui.set_width(ui.available_width());
+            Self::render_footer_link(ui, location, ctx.navigator);
 
             Self::render_bloom_filter_header(ui, header);
 
             ui.separator();
 
+            Self::render_encoded_struct(
+                ui,
+                "Encoded Header",
+                header_interval,
+                &BLOOM_FILTER_HEADER,
+                highlight,
+                ctx,
+            );
+            Self::render_unknown_fields(ui, unknown_fields);
+
+            ui.separator();
+
+            Self::render_header_collapsible(ui, "Probe", |ui| {
+                BloomFilterRenderer::render_probe(ui, byte_interval, column.as_ref(), bitset, ctx);
+            });
+
+            ui.separator();
+
+            Self::render_header_collapsible(ui, "Saturation", |ui| {
+                BloomFilterRenderer::render_saturation(ui, byte_interval, metadata, bitset, ctx);
+            });
+
+            ui.separator();
+
             // Get current preview state, setting it to default if it's not set
             let current_state =
                 ui.data_mut(|d| d.get_temp_mut_or_default::<PreviewState>(id).clone());
 
+            let reveal_preview =
+                highlight.should_scroll && Self::relative_byte(byte_interval, highlight).is_some();
+
             // Compute next state based on current state and UI interactions
             let next_state = match current_state {
                 PreviewState::Hidden => {
@@ -767,7 +1529,8 @@ impl DataRenderer {
                         })
                         .inner;
 
-                    if button_clicked {
+                    // Jumping to a byte in the bitset opens its preview
+                    if button_clicked || reveal_preview {
                         if let Ok(pd) = bitset.lock() {
                             if pd.is_some() {
                                 PreviewState::Visible
@@ -838,7 +1601,12 @@ impl DataRenderer {
                 | (PreviewState::Visible, PreviewState::Visible) => {
                     if let Ok(pd) = bitset.lock() {
                         if let Some(ref pd_bytes) = *pd {
-                            Self::render_data_preview(ui, pd_bytes);
+                            Self::render_data_preview(
+                                ui,
+                                pd_bytes,
+                                Self::relative_byte(byte_interval, highlight)
+                                    .map(|idx| idx..idx + 1),
+                            );
                         }
                     } else {
                         panic!("Can't get lock on bitset data");
@@ -846,7 +1614,7 @@ impl DataRenderer {
                 }
                 // Fetch data
                 (PreviewState::Hidden, PreviewState::Pending) => {
-                    reader_tx
+                    ctx.reader_tx
                         .send(ReadRequest(*byte_interval, bitset.clone()))
                         .context("Couldn't send message to reader thread")
                         .unwrap();
@@ -0,0 +1,474 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Context;
+use egui::{Color32, Rect, RichText, Sense, TextEdit, Ui, Vec2, vec2};
+
+use crate::file::{
+    BLOCK_SIZE, BloomFilterProbe, BloomFilterSaturation, ByteInterval, Field, ReadRequest,
+    format_bytes, format_plain_value, parse_plain_value, parse_raw_bytes, recommended_num_bytes,
+};
+
+use super::{DATA_BUTTON_SIZE, DataRenderer, HEADER_VALUE_SIZE, SectionContext};
+
+const ABSENT_COLOR: Color32 = Color32::from_rgb(30, 130, 60);
+const PRESENT_COLOR: Color32 = Color32::from_rgb(190, 90, 0);
+
+/// Filters whose estimated false positive probability is above this barely prune anything
+const SATURATED_FALSE_POSITIVE_PROBABILITY: f64 = 0.1;
+/// Filters at least this many times larger than recommended waste space in the file
+const OVERSIZED_FACTOR: usize = 4;
+/// The default false positive probability of parquet writers, used to size filters
+const TARGET_FALSE_POSITIVE_PROBABILITY: f64 = 0.05;
+const HEATMAP_CELL_SIZE: f32 = 8.;
+const HEATMAP_MAX_CELLS: usize = 8192;
+const HEATMAP_EMPTY_COLOR: Color32 = Color32::from_rgb(245, 245, 245);
+const HEATMAP_FULL_COLOR: Color32 = Color32::from_rgb(170, 20, 20);
+
+pub struct BloomFilterRenderer;
+impl BloomFilterRenderer {
+    /// Check whether a value typed by the user could be in the column chunk, the same way a
+    /// reader would before skipping it.
+    pub fn render_probe(
+        ui: &mut Ui,
+        byte_interval: &ByteInterval,
+        column: Option<&Field>,
+        bitset: &Arc<Mutex<Option<Vec<u8>>>>,
+        ctx: &mut SectionContext,
+    ) {
+        let id = ui.make_persistent_id("Bloom Filter Probe");
+        let mut query = ui.data_mut(|d| d.get_temp_mut_or_default::<String>(id).clone());
+
+        let hint = match column {
+            Some(column) if column.is_primitive() => {
+                let basic_info = column.get_basic_info();
+                match basic_info.logical_type() {
+                    Some(logical_type) => {
+                        format!("{} ({logical_type:?})", column.get_physical_type())
+                    }
+                    None => column.get_physical_type().to_string(),
+                }
+            }
+            _ => String::from("raw bytes, as text or 0x-prefixed hex"),
+        };
+        ui.add(
+            TextEdit::singleline(&mut query)
+                .font(egui::TextStyle::Monospace)
+                .hint_text(format!("Value to probe: {hint}"))
+                .desired_width(f32::INFINITY),
+        );
+
+        let parsed = (!query.is_empty()).then(|| match column {
+            Some(column) => parse_plain_value(column, &query),
+            None => parse_raw_bytes(&query),
+        });
+        ui.data_mut(|d| d.insert_temp(id, query));
+
+        let is_loaded = Self::load_bitset(ui, byte_interval, bitset, parsed.is_some(), ctx);
+
+        match parsed {
+            None => {}
+            Some(Err(e)) => {
+                ui.label(
+                    RichText::new(format!("Can't parse value: {e}"))
+                        .monospace()
+                        .size(DATA_BUTTON_SIZE)
+                        .color(ui.visuals().error_fg_color),
+                );
+            }
+            Some(Ok(_)) if !is_loaded => {
+                DataRenderer::render_header_value(ui, "Reading bitset...");
+            }
+            Some(Ok(value)) => {
+                let bitset = bitset.lock().expect("Can't get lock on bitset data");
+                let probe = bitset
+                    .as_deref()
+                    .and_then(|bitset| BloomFilterProbe::new(bitset, &value));
+                match probe {
+                    Some(probe) => {
+                        Self::render_probe_result(ui, byte_interval, column, &value, &probe, ctx)
+                    }
+                    None => DataRenderer::render_header_value(
+                        ui,
+                        format!("The bitset isn't made of {BLOCK_SIZE} byte blocks"),
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Whether the bitset has been read, requesting it from the reader thread once it's needed.
+    fn load_bitset(
+        ui: &mut Ui,
+        byte_interval: &ByteInterval,
+        bitset: &Arc<Mutex<Option<Vec<u8>>>>,
+        is_needed: bool,
+        ctx: &mut SectionContext,
+    ) -> bool {
+        let id = ui.make_persistent_id("Bloom Filter Bitset Requested");
+        let is_requested = ui.data(|d| d.get_temp::<bool>(id).unwrap_or(false));
+        let is_loaded = bitset
+            .lock()
+            .expect("Can't get lock on bitset data")
+            .is_some();
+        if !is_loaded && is_needed && !is_requested {
+            ctx.reader_tx
+                .send(ReadRequest(*byte_interval, bitset.clone()))
+                .context("Couldn't send message to reader thread")
+                .unwrap();
+            ui.data_mut(|d| d.insert_temp(id, true));
+        }
+        is_loaded
+    }
+
+    /// Show how full the bitset is, and whether it is sized for the values in the column chunk.
+    pub fn render_saturation(
+        ui: &mut Ui,
+        byte_interval: &ByteInterval,
+        metadata: Option<&parquet::format::ColumnMetaData>,
+        bitset: &Arc<Mutex<Option<Vec<u8>>>>,
+        ctx: &mut SectionContext,
+    ) {
+        let id = ui.make_persistent_id("Bloom Filter Saturation");
+        let mut is_needed = ui.data(|d| d.get_temp::<bool>(id).unwrap_or(false));
+        if !is_needed {
+            is_needed = ui
+                .vertical_centered_justified(|ui| {
+                    ui.button(
+                        RichText::new("Analyze Bitset")
+                            .monospace()
+                            .size(DATA_BUTTON_SIZE)
+                            .strong(),
+                    )
+                    .clicked()
+                })
+                .inner;
+            ui.data_mut(|d| d.insert_temp(id, is_needed));
+        }
+        if !Self::load_bitset(ui, byte_interval, bitset, is_needed, ctx) {
+            if is_needed {
+                DataRenderer::render_header_value(ui, "Reading bitset...");
+            }
+            return;
+        }
+
+        let bitset = bitset.lock().expect("Can't get lock on bitset data");
+        let Some(bitset) = bitset.as_deref() else {
+            return;
+        };
+        // Popcounting the whole bitset is slow for large filters, so it's only done once
+        let saturation_id = id.with("saturation");
+        let saturation =
+            match ui.data(|d| d.get_temp::<Arc<Option<BloomFilterSaturation>>>(saturation_id)) {
+                Some(saturation) => saturation,
+                None => {
+                    let saturation = Arc::new(BloomFilterSaturation::new(bitset));
+                    ui.data_mut(|d| d.insert_temp(saturation_id, saturation.clone()));
+                    saturation
+                }
+            };
+        let Some(saturation) = saturation.as_ref() else {
+            DataRenderer::render_header_value(
+                ui,
+                format!("The bitset isn't made of {BLOCK_SIZE} byte blocks"),
+            );
+            return;
+        };
+
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Blocks",
+            format!(
+                "{} ({} bytes)",
+                saturation.block_popcounts.len(),
+                bitset.len()
+            ),
+        );
+        ui.separator();
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Fill Ratio",
+            format!("{:.2}%", saturation.fill_ratio * 100.),
+        );
+        ui.separator();
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Estimated False Positive Probability",
+            match saturation.false_positive_probability {
+                // Tiny probabilities would round to zero
+                p if p < 1e-6 => format!("{p:.3e}"),
+                p => format!("{:.4}%", p * 100.),
+            },
+        );
+        ui.separator();
+
+        // Compare the size with what a writer would have picked, given each estimate of the
+        // number of distinct values, from most to least accurate
+        let distinct_values = [
+            (
+                "Distinct Count",
+                metadata
+                    .and_then(|m| m.statistics.as_ref())
+                    .and_then(|s| s.distinct_count)
+                    .and_then(|v| u64::try_from(v).ok()),
+            ),
+            (
+                "Estimated From Bitset",
+                saturation
+                    .estimated_distinct_values
+                    .map(|v| v.round() as u64),
+            ),
+            (
+                "Num Values (upper bound)",
+                metadata.and_then(|m| u64::try_from(m.num_values).ok()),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(source, ndv)| {
+            let ndv = ndv?;
+            Some((
+                source,
+                ndv,
+                recommended_num_bytes(ndv, TARGET_FALSE_POSITIVE_PROBABILITY),
+            ))
+        })
+        .collect::<Vec<_>>();
+
+        DataRenderer::render_header_collapsible(ui, "Sizing", |ui| {
+            DataRenderer::render_header_value(
+                ui,
+                format!(
+                    "Recommended sizes target a {}% false positive probability",
+                    TARGET_FALSE_POSITIVE_PROBABILITY * 100.
+                ),
+            );
+            egui::Grid::new("Sizing")
+                .num_columns(4)
+                .spacing([10., 5.])
+                .striped(true)
+                .show(ui, |ui| {
+                    for title in ["Source", "Distinct Values", "Recommended Bytes", "Actual"] {
+                        ui.label(
+                            RichText::new(title)
+                                .monospace()
+                                .size(HEADER_VALUE_SIZE)
+                                .strong(),
+                        );
+                    }
+                    ui.end_row();
+                    for (source, ndv, recommended) in &distinct_values {
+                        for text in [
+                            source.to_string(),
+                            ndv.to_string(),
+                            recommended.to_string(),
+                            format!("{:.2}x", bitset.len() as f64 / *recommended as f64),
+                        ] {
+                            ui.label(RichText::new(text).monospace().size(HEADER_VALUE_SIZE));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+        ui.separator();
+
+        let (verdict, color) = match distinct_values.first() {
+            _ if saturation.false_positive_probability > SATURATED_FALSE_POSITIVE_PROBABILITY => (
+                String::from("Saturated: too many values for the bitset to prune effectively"),
+                ui.visuals().error_fg_color,
+            ),
+            Some((source, _, recommended)) if bitset.len() >= OVERSIZED_FACTOR * recommended => (
+                format!(
+                    "Oversized: {}x larger than needed, based on {}",
+                    bitset.len() / recommended,
+                    source.to_lowercase()
+                ),
+                ui.visuals().warn_fg_color,
+            ),
+            _ => (String::from("Sized appropriately"), ABSENT_COLOR),
+        };
+        ui.label(
+            RichText::new(verdict)
+                .monospace()
+                .size(HEADER_VALUE_SIZE)
+                .strong()
+                .color(color),
+        );
+        ui.separator();
+
+        DataRenderer::render_header_collapsible(ui, "Block Heatmap", |ui| {
+            Self::render_heatmap(ui, byte_interval, &saturation.block_popcounts, ctx);
+        });
+    }
+
+    /// One cell per block, darker the more of its 256 bits are set. Large filters average
+    /// consecutive blocks into a cell.
+    fn render_heatmap(
+        ui: &mut Ui,
+        byte_interval: &ByteInterval,
+        block_popcounts: &[u32],
+        ctx: &mut SectionContext,
+    ) {
+        let blocks_per_cell = block_popcounts.len().div_ceil(HEATMAP_MAX_CELLS);
+        let cells = block_popcounts
+            .chunks(blocks_per_cell)
+            .map(|blocks| blocks.iter().sum::<u32>() as f32 / blocks.len() as f32)
+            .collect::<Vec<_>>();
+
+        let columns = ((ui.available_width() / HEATMAP_CELL_SIZE) as usize).max(1);
+        let rows = cells.len().div_ceil(columns);
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(
+                columns as f32 * HEATMAP_CELL_SIZE,
+                rows as f32 * HEATMAP_CELL_SIZE,
+            ),
+            Sense::click(),
+        );
+        let painter = ui.painter_at(rect);
+        for (idx, popcount) in cells.iter().enumerate() {
+            let min = rect.min
+                + vec2(
+                    (idx % columns) as f32 * HEATMAP_CELL_SIZE,
+                    (idx / columns) as f32 * HEATMAP_CELL_SIZE,
+                );
+            painter.rect_filled(
+                Rect::from_min_size(min, Vec2::splat(HEATMAP_CELL_SIZE - 1.)),
+                0.,
+                HEATMAP_EMPTY_COLOR.lerp_to_gamma(HEATMAP_FULL_COLOR, popcount / 256.),
+            );
+        }
+
+        let hovered_cell = response.hover_pos().and_then(|pos| {
+            let offset = pos - rect.min;
+            let idx = (offset.y / HEATMAP_CELL_SIZE) as usize * columns
+                + (offset.x / HEATMAP_CELL_SIZE) as usize;
+            (idx < cells.len()).then_some(idx)
+        });
+        let Some(idx) = hovered_cell else {
+            return;
+        };
+
+        let first_block = idx * blocks_per_cell;
+        let last_block = (first_block + blocks_per_cell).min(block_popcounts.len()) - 1;
+        let block_start = byte_interval.0 + (first_block * BLOCK_SIZE) as u64;
+        if response.clicked() {
+            ctx.navigator.request_offset(ui, block_start);
+        }
+        response.on_hover_ui_at_pointer(|ui| {
+            let blocks = if first_block == last_block {
+                format!("Block {first_block}")
+            } else {
+                format!("Blocks {first_block}-{last_block}")
+            };
+            DataRenderer::render_header_value(ui, blocks);
+            DataRenderer::render_header_value(ui, format!("{:.1} of 256 bits set", cells[idx]));
+            DataRenderer::render_header_value(ui, format!("Starts at byte {block_start}"));
+        });
+    }
+
+    fn render_probe_result(
+        ui: &mut Ui,
+        byte_interval: &ByteInterval,
+        column: Option<&Field>,
+        value: &[u8],
+        probe: &BloomFilterProbe,
+        ctx: &mut SectionContext,
+    ) {
+        let (verdict, color) = if probe.is_possibly_present() {
+            (
+                "Possibly present: all 8 bits are set, so the column chunk must be read",
+                PRESENT_COLOR,
+            )
+        } else {
+            (
+                "Definitely absent: the column chunk would be skipped",
+                ABSENT_COLOR,
+            )
+        };
+        ui.label(
+            RichText::new(verdict)
+                .monospace()
+                .size(HEADER_VALUE_SIZE)
+                .strong()
+                .color(color),
+        );
+
+        ui.separator();
+
+        if let Some(decoded) = column.and_then(|column| format_plain_value(column, value)) {
+            DataRenderer::render_header_labeled_value(ui, "Parsed Value", decoded);
+            ui.separator();
+        }
+        DataRenderer::render_header_labeled_value(ui, "Plain Encoding", format_bytes(value));
+        ui.separator();
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "XXH64 Hash",
+            format!("0x{:016x}", probe.hash),
+        );
+        ui.separator();
+
+        // The block is selected by the upper half of the hash
+        let block_start = byte_interval.0 + (probe.block_index * BLOCK_SIZE) as u64;
+        let clicked = DataRenderer::render_header_labeled_link(
+            ui,
+            "Block",
+            format!(
+                "{} (bytes {block_start}-{}), from upper 32 bits 0x{:08x}",
+                probe.block_index,
+                block_start + BLOCK_SIZE as u64 - 1,
+                probe.hash >> 32
+            ),
+        );
+        if clicked {
+            ctx.navigator.request_offset(ui, block_start);
+        }
+        ui.separator();
+
+        // One bit per 32-bit word is selected by the lower half of the hash
+        DataRenderer::render_header_collapsible(ui, "Checked Bits", |ui| {
+            DataRenderer::render_header_value(
+                ui,
+                format!(
+                    "bit = (0x{:08x} * salt) >> 27 for each word",
+                    probe.hash as u32
+                ),
+            );
+            egui::Grid::new("Checked Bits")
+                .num_columns(5)
+                .spacing([10., 5.])
+                .striped(true)
+                .show(ui, |ui| {
+                    for title in ["Word", "Salt", "Bit", "Word Value", "Set"] {
+                        ui.label(
+                            RichText::new(title)
+                                .monospace()
+                                .size(HEADER_VALUE_SIZE)
+                                .strong(),
+                        );
+                    }
+                    ui.end_row();
+                    for (idx, word) in probe.words.iter().enumerate() {
+                        for text in [
+                            idx.to_string(),
+                            format!("0x{:08x}", word.salt),
+                            word.bit.to_string(),
+                            format!("{:032b}", word.word),
+                        ] {
+                            ui.label(RichText::new(text).monospace().size(HEADER_VALUE_SIZE));
+                        }
+                        let (text, color) = if word.is_set {
+                            ("yes", PRESENT_COLOR)
+                        } else {
+                            ("no", ABSENT_COLOR)
+                        };
+                        ui.label(
+                            RichText::new(text)
+                                .monospace()
+                                .size(HEADER_VALUE_SIZE)
+                                .color(color),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
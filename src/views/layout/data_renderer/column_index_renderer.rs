@@ -0,0 +1,350 @@
+use std::sync::Arc;
+
+use egui::{Color32, Id, Label, Pos2, Rect, RichText, Sense, Stroke, Ui, Widget, vec2};
+use egui_extras::{Column, TableBuilder};
+use parquet::schema::types::ColumnDescPtr;
+
+use crate::file::{Field, format_bytes, format_plain_value, plain_value_sort_key};
+
+use crate::views::layout::components::{LevelHistogramChart, LevelKind};
+
+use super::{DataRenderer, HEADER_VALUE_SIZE};
+
+const TABLE_MAX_HEIGHT: f32 = 300.;
+const TABLE_ROW_HEIGHT: f32 = 18.;
+const RANGE_CHART_HEIGHT: f32 = 160.;
+const RANGE_CHART_BACKGROUND_COLOR: Color32 = Color32::from_rgb(250, 250, 250);
+const RANGE_CHART_BAR_COLOR: Color32 = Color32::from_rgb(70, 130, 180);
+const RANGE_CHART_HOVER_COLOR: Color32 = Color32::from_rgb(230, 120, 0);
+const RANGE_CHART_NULL_PAGE_COLOR: Color32 = Color32::from_rgb(225, 225, 225);
+
+/// Min/max of a single page, decoded for display and mapped onto a numeric axis for the chart.
+struct PageRange {
+    min: String,
+    max: String,
+    raw_min: String,
+    raw_max: String,
+    keys: Option<(f64, f64)>,
+}
+
+impl PageRange {
+    fn new(min: &[u8], max: &[u8], column: Option<&Field>) -> Self {
+        let decode = |value: &[u8]| {
+            column
+                .and_then(|column| format_plain_value(column, value))
+                .unwrap_or_else(|| format_bytes(value))
+        };
+        let keys = column.and_then(|column| {
+            Some((
+                plain_value_sort_key(column, min)?,
+                plain_value_sort_key(column, max)?,
+            ))
+        });
+        Self {
+            min: decode(min),
+            max: decode(max),
+            raw_min: format_bytes(min),
+            raw_max: format_bytes(max),
+            keys,
+        }
+    }
+}
+
+pub struct ColumnIndexRenderer;
+impl ColumnIndexRenderer {
+    /// The decoded page ranges are kept in temp data under `id`, as decoding them is costly for
+    /// columns with many pages.
+    pub fn render(
+        ui: &mut Ui,
+        id: Id,
+        column_index: &parquet::format::ColumnIndex,
+        offset_index: Option<&parquet::format::OffsetIndex>,
+        column: Option<&ColumnDescPtr>,
+    ) {
+        let ranges = match ui.data(|d| d.get_temp::<Arc<Vec<Option<PageRange>>>>(id)) {
+            Some(ranges) => ranges,
+            None => {
+                let field = column.map(|column| column.self_type_ptr());
+                // Null pages have empty min/max values
+                let ranges = Arc::new(
+                    column_index
+                        .null_pages
+                        .iter()
+                        .zip(column_index.min_values.iter().zip(&column_index.max_values))
+                        .map(|(is_null_page, (min, max))| {
+                            (!is_null_page).then(|| PageRange::new(min, max, field.as_ref()))
+                        })
+                        .collect::<Vec<_>>(),
+                );
+                ui.data_mut(|d| d.insert_temp(id, ranges.clone()));
+                ranges
+            }
+        };
+
+        Self::render_boundary_order(ui, column_index, &ranges);
+        ui.separator();
+        DataRenderer::render_header_collapsible(ui, "Pages", |ui| {
+            Self::render_page_table(ui, column_index, offset_index, &ranges);
+        });
+        ui.separator();
+        DataRenderer::render_header_collapsible(ui, "Min/Max Ranges", |ui| {
+            Self::render_range_chart(ui, &ranges);
+        });
+        for (label, histograms, max_level, kind) in [
+            (
+                "Repetition Level Histograms",
+                &column_index.repetition_level_histograms,
+                column.map(|column| column.max_rep_level()),
+                LevelKind::Repetition,
+            ),
+            (
+                "Definition Level Histograms",
+                &column_index.definition_level_histograms,
+                column.map(|column| column.max_def_level()),
+                LevelKind::Definition,
+            ),
+        ] {
+            ui.separator();
+            DataRenderer::render_header_collapsible(ui, label, |ui| {
+                match (histograms, max_level) {
+                    (Some(histograms), Some(max_level)) => {
+                        LevelHistogramChart::show(ui, histograms, max_level, kind, "Page")
+                    }
+                    (Some(histograms), None) => {
+                        DataRenderer::render_header_value(ui, format!("{histograms:?}"))
+                    }
+                    (None, _) => DataRenderer::render_header_value(ui, "N/A"),
+                }
+            });
+        }
+    }
+
+    /// Show the declared boundary order next to the order the page ranges actually have, as
+    /// readers only binary search the column index when the boundary order says they can.
+    fn render_boundary_order(
+        ui: &mut Ui,
+        column_index: &parquet::format::ColumnIndex,
+        ranges: &[Option<PageRange>],
+    ) {
+        let boundary_order = match column_index.boundary_order {
+            parquet::format::BoundaryOrder::UNORDERED => "UNORDERED",
+            parquet::format::BoundaryOrder::ASCENDING => "ASCENDING",
+            parquet::format::BoundaryOrder::DESCENDING => "DESCENDING",
+            _ => "UNKNOWN",
+        };
+        DataRenderer::render_header_labeled_value(ui, "Boundary Order", boundary_order);
+
+        let keys = ranges
+            .iter()
+            .flatten()
+            .map(|range| range.keys)
+            .collect::<Option<Vec<_>>>();
+        let Some(keys) = keys.filter(|keys| keys.len() > 1) else {
+            return;
+        };
+        let is_ascending = keys
+            .windows(2)
+            .all(|w| w[0].0 <= w[1].0 && w[0].1 <= w[1].1);
+        let is_descending = keys
+            .windows(2)
+            .all(|w| w[0].0 >= w[1].0 && w[0].1 >= w[1].1);
+        let overlaps = keys
+            .windows(2)
+            .filter(|w| w[0].0.max(w[1].0) <= w[0].1.min(w[1].1))
+            .count();
+        let actual_order = match (is_ascending, is_descending) {
+            (true, _) => "ascending",
+            (_, true) => "descending",
+            _ => "unordered",
+        };
+
+        ui.separator();
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Page Ranges",
+            format!(
+                "{actual_order}, {overlaps} of {} adjacent non-null pages overlap",
+                keys.len() - 1
+            ),
+        );
+    }
+
+    fn render_page_table(
+        ui: &mut Ui,
+        column_index: &parquet::format::ColumnIndex,
+        offset_index: Option<&parquet::format::OffsetIndex>,
+        ranges: &[Option<PageRange>],
+    ) {
+        let cell = |ui: &mut Ui, text: String| {
+            Label::new(RichText::new(text).monospace().size(HEADER_VALUE_SIZE)).ui(ui)
+        };
+
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .max_scroll_height(TABLE_MAX_HEIGHT)
+            .columns(Column::auto(), 6)
+            .header(TABLE_ROW_HEIGHT, |mut header| {
+                for title in [
+                    "Page",
+                    "Null Page",
+                    "Min",
+                    "Max",
+                    "Null Count",
+                    "First Row Index",
+                ] {
+                    header.col(|ui| {
+                        Label::new(
+                            RichText::new(title)
+                                .monospace()
+                                .size(HEADER_VALUE_SIZE)
+                                .strong(),
+                        )
+                        .ui(ui);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(TABLE_ROW_HEIGHT, ranges.len(), |mut row| {
+                    let idx = row.index();
+                    row.col(|ui| {
+                        cell(ui, idx.to_string());
+                    });
+                    row.col(|ui| {
+                        cell(ui, column_index.null_pages[idx].to_string());
+                    });
+                    match &ranges[idx] {
+                        Some(range) => {
+                            row.col(|ui| {
+                                cell(ui, range.min.clone()).on_hover_text(&range.raw_min);
+                            });
+                            row.col(|ui| {
+                                cell(ui, range.max.clone()).on_hover_text(&range.raw_max);
+                            });
+                        }
+                        None => {
+                            row.col(|ui| {
+                                cell(ui, String::from("N/A"));
+                            });
+                            row.col(|ui| {
+                                cell(ui, String::from("N/A"));
+                            });
+                        }
+                    }
+                    row.col(|ui| {
+                        let null_count = column_index
+                            .null_counts
+                            .as_ref()
+                            .and_then(|null_counts| null_counts.get(idx));
+                        cell(
+                            ui,
+                            null_count
+                                .map(|v| v.to_string())
+                                .unwrap_or(String::from("N/A")),
+                        );
+                    });
+                    row.col(|ui| {
+                        let first_row_index = offset_index
+                            .and_then(|offset_index| offset_index.page_locations.get(idx))
+                            .map(|page_location| page_location.first_row_index);
+                        cell(
+                            ui,
+                            first_row_index
+                                .map(|v| v.to_string())
+                                .unwrap_or(String::from("N/A")),
+                        );
+                    });
+                });
+            });
+    }
+
+    /// One vertical bar per page spanning its min to max value. Pages whose ranges don't overlap
+    /// the predicate's range can be skipped, so tall or overlapping bars mean little pruning.
+    fn render_range_chart(ui: &mut Ui, ranges: &[Option<PageRange>]) {
+        let keyed_ranges = ranges
+            .iter()
+            .flatten()
+            .map(|range| range.keys.map(|keys| (keys, range)))
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        let lowest = keyed_ranges
+            .iter()
+            .min_by(|a, b| a.0.0.total_cmp(&b.0.0))
+            .map(|((min, _), range)| (*min, &range.min));
+        let highest = keyed_ranges
+            .iter()
+            .max_by(|a, b| a.0.1.total_cmp(&b.0.1))
+            .map(|((_, max), range)| (*max, &range.max));
+        let (Some((lowest, lowest_label)), Some((highest, highest_label))) = (lowest, highest)
+        else {
+            DataRenderer::render_header_value(ui, "N/A");
+            return;
+        };
+        let span = if highest > lowest {
+            highest - lowest
+        } else {
+            1.
+        };
+
+        DataRenderer::render_header_value(ui, format!("▲ {highest_label}"));
+        let (rect, response) = ui.allocate_exact_size(
+            vec2(ui.available_width(), RANGE_CHART_HEIGHT),
+            Sense::hover(),
+        );
+        DataRenderer::render_header_value(ui, format!("▼ {lowest_label}"));
+
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 2., RANGE_CHART_BACKGROUND_COLOR);
+
+        let page_width = rect.width() / ranges.len() as f32;
+        let y = |key: f64| rect.bottom() - ((key - lowest) / span) as f32 * rect.height();
+        let hovered_page = response
+            .hover_pos()
+            .map(|pos| (((pos.x - rect.left()) / page_width) as usize).min(ranges.len() - 1));
+
+        for (idx, range) in ranges.iter().enumerate() {
+            let left = rect.left() + idx as f32 * page_width;
+            let bar_width = (page_width * 0.8).max(1.);
+            let color = if hovered_page == Some(idx) {
+                RANGE_CHART_HOVER_COLOR
+            } else {
+                RANGE_CHART_BAR_COLOR
+            };
+            match range.as_ref().and_then(|range| range.keys) {
+                Some((min, max)) => {
+                    let bar = Rect::from_min_max(
+                        Pos2::new(left, y(max)),
+                        Pos2::new(left + bar_width, y(min).max(y(max) + 2.)),
+                    );
+                    painter.rect_filled(bar, 0., color);
+                }
+                None => {
+                    let bar = Rect::from_min_max(
+                        Pos2::new(left, rect.top()),
+                        Pos2::new(left + bar_width, rect.bottom()),
+                    );
+                    painter.rect_filled(bar, 0., RANGE_CHART_NULL_PAGE_COLOR);
+                }
+            }
+        }
+        painter.rect_stroke(
+            rect,
+            2.,
+            Stroke::new(1., Color32::LIGHT_GRAY),
+            egui::StrokeKind::Inside,
+        );
+
+        if let Some(idx) = hovered_page {
+            response.on_hover_ui_at_pointer(|ui| {
+                DataRenderer::render_header_value(ui, format!("Page {idx}"));
+                match &ranges[idx] {
+                    Some(range) => {
+                        DataRenderer::render_header_value(ui, format!("Min: {}", range.min));
+                        DataRenderer::render_header_value(ui, format!("Max: {}", range.max));
+                    }
+                    None => DataRenderer::render_header_value(ui, "Null page"),
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use egui::{Color32, Label, RichText, ScrollArea, Sense, Ui, Widget};
+use egui_extras::{Column, TableBuilder};
+
+use crate::views::layout::navigation::Navigator;
+
+use super::{DataRenderer, HEADER_VALUE_SIZE};
+
+const TABLE_MAX_HEIGHT: f32 = 300.;
+const TABLE_ROW_HEIGHT: f32 = 18.;
+const EVEN_PAGE_COLOR: Color32 = Color32::from_rgb(220, 232, 245);
+const ODD_PAGE_COLOR: Color32 = Color32::from_rgb(245, 232, 210);
+const SELECTED_PAGE_COLOR: Color32 = Color32::from_rgb(240, 190, 60);
+
+/// A data page and the half-open range of rows it holds.
+struct PageSpan {
+    first_row: i64,
+    end_row: i64,
+    offset: i64,
+    compressed_page_size: i32,
+}
+
+/// The pages of a leaf column in a row group, if it has an offset index.
+pub struct ColumnPages {
+    name: String,
+    pages: Option<Vec<PageSpan>>,
+}
+
+impl ColumnPages {
+    pub fn new(
+        name: String,
+        offset_index: Option<&parquet::format::OffsetIndex>,
+        num_rows: i64,
+    ) -> Self {
+        let pages = offset_index.map(|offset_index| {
+            let locations = &offset_index.page_locations;
+            locations
+                .iter()
+                .enumerate()
+                .map(|(page_idx, location)| PageSpan {
+                    first_row: location.first_row_index,
+                    end_row: locations
+                        .get(page_idx + 1)
+                        .map_or(num_rows, |next| next.first_row_index),
+                    offset: location.offset,
+                    compressed_page_size: location.compressed_page_size,
+                })
+                .collect()
+        });
+        Self { name, pages }
+    }
+
+    /// Index of the page holding the row.
+    fn page_of(&self, row: i64) -> Option<usize> {
+        let pages = self.pages.as_ref()?;
+        let page_idx = pages.partition_point(|page| page.end_row <= row);
+        (page_idx < pages.len()).then_some(page_idx)
+    }
+}
+
+/// A grid of the row ranges between every page boundary of every column, showing the page of
+/// each column that covers each range.
+pub struct PageAlignmentRenderer;
+impl PageAlignmentRenderer {
+    pub fn render(ui: &mut Ui, columns: &[ColumnPages], num_rows: i64, navigator: &mut Navigator) {
+        // Every row at which a page of some column starts
+        let mut boundaries = columns
+            .iter()
+            .filter_map(|column| column.pages.as_ref())
+            .flatten()
+            .map(|page| page.first_row)
+            .filter(|row| (0..num_rows).contains(row))
+            .collect::<Vec<_>>();
+        boundaries.push(0);
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        let row_ranges = boundaries
+            .iter()
+            .enumerate()
+            .map(|(idx, start)| (*start, boundaries.get(idx + 1).copied().unwrap_or(num_rows)))
+            .collect::<Vec<_>>();
+
+        let indexed_columns = columns
+            .iter()
+            .filter(|column| column.pages.is_some())
+            .count();
+        if indexed_columns == 0 {
+            DataRenderer::render_header_value(ui, "No column chunk has an offset index");
+            return;
+        }
+        let aligned_columns = columns
+            .iter()
+            .filter_map(|column| column.pages.as_ref())
+            .filter(|pages| pages.len() == row_ranges.len())
+            .count();
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Row Ranges",
+            format!(
+                "{} ranges between page boundaries, {aligned_columns} of {indexed_columns} columns \
+                 have a page boundary at every one of them",
+                row_ranges.len()
+            ),
+        );
+
+        let id = ui.make_persistent_id("page_alignment_selection");
+        let mut selected = ui.data_mut(|d| d.get_temp::<usize>(id));
+        if let Some(range_idx) = selected.filter(|idx| *idx < row_ranges.len()) {
+            Self::render_selection_cost(ui, columns, row_ranges[range_idx]);
+        } else {
+            DataRenderer::render_header_value(
+                ui,
+                "Select a row range to see which pages reading it requires",
+            );
+        }
+
+        ScrollArea::horizontal().show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt(id)
+                .striped(false)
+                .resizable(true)
+                .max_scroll_height(TABLE_MAX_HEIGHT)
+                .column(Column::auto())
+                .columns(Column::auto(), columns.len())
+                .header(TABLE_ROW_HEIGHT, |mut header| {
+                    let titles = std::iter::once("Rows").chain(columns.iter().map(|c| &*c.name));
+                    for title in titles {
+                        header.col(|ui| {
+                            Label::new(
+                                RichText::new(title)
+                                    .monospace()
+                                    .size(HEADER_VALUE_SIZE)
+                                    .strong(),
+                            )
+                            .ui(ui);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(TABLE_ROW_HEIGHT, row_ranges.len(), |mut row| {
+                        let range_idx = row.index();
+                        let (start, end) = row_ranges[range_idx];
+                        let is_selected = selected == Some(range_idx);
+                        row.col(|ui| {
+                            let response = ui
+                                .selectable_label(
+                                    is_selected,
+                                    RichText::new(format!("{start}..{end}"))
+                                        .monospace()
+                                        .size(HEADER_VALUE_SIZE),
+                                )
+                                .on_hover_text(format!("{} rows", end - start));
+                            if response.clicked() {
+                                selected = (!is_selected).then_some(range_idx);
+                            }
+                        });
+                        for column in columns {
+                            row.col(|ui| {
+                                Self::render_cell(ui, column, start, is_selected, navigator);
+                            });
+                        }
+                    });
+                });
+        });
+
+        ui.data_mut(|d| match selected {
+            Some(range_idx) => d.insert_temp(id, range_idx),
+            None => d.remove::<usize>(id),
+        });
+    }
+
+    fn render_cell(
+        ui: &mut Ui,
+        column: &ColumnPages,
+        row: i64,
+        is_selected: bool,
+        navigator: &mut Navigator,
+    ) {
+        let (Some(page_idx), Some(pages)) = (column.page_of(row), column.pages.as_ref()) else {
+            Label::new(RichText::new("N/A").monospace().size(HEADER_VALUE_SIZE)).ui(ui);
+            return;
+        };
+        let page = &pages[page_idx];
+        let color = match (is_selected, page_idx % 2) {
+            (true, _) => SELECTED_PAGE_COLOR,
+            (false, 0) => EVEN_PAGE_COLOR,
+            (false, _) => ODD_PAGE_COLOR,
+        };
+        ui.painter().rect_filled(ui.max_rect(), 0., color);
+
+        // Only label the row range a page starts at, so the rest reads as its continuation
+        let text = if page.first_row == row {
+            RichText::new(format!("Page {page_idx}")).strong()
+        } else {
+            RichText::new("┆").weak()
+        };
+        let response = Label::new(text.monospace().size(HEADER_VALUE_SIZE))
+            .sense(Sense::click())
+            .ui(ui)
+            .on_hover_text(format!(
+                "{} page {page_idx}\nRows {}..{} ({} rows)\nOffset {} ({} bytes with header)",
+                column.name,
+                page.first_row,
+                page.end_row,
+                page.end_row - page.first_row,
+                page.offset,
+                page.compressed_page_size
+            ));
+        if response.clicked() {
+            navigator.request_offset(ui, page.offset as u64);
+        }
+    }
+
+    /// The pages of every column that a reader needs for the row range, and the rows it decodes
+    /// outside of it.
+    fn render_selection_cost(ui: &mut Ui, columns: &[ColumnPages], (start, end): (i64, i64)) {
+        let pages = columns
+            .iter()
+            .filter_map(|column| {
+                let pages = column.pages.as_ref()?;
+                Some(&pages[column.page_of(start)?])
+            })
+            .collect::<Vec<_>>();
+        let bytes = pages
+            .iter()
+            .map(|page| page.compressed_page_size as i64)
+            .sum::<i64>();
+        let extra_rows = pages
+            .iter()
+            .map(|page| (page.end_row - page.first_row) - (end - start))
+            .sum::<i64>();
+        let partial_pages = pages
+            .iter()
+            .filter(|page| page.first_row != start || page.end_row != end)
+            .count();
+        DataRenderer::render_header_labeled_value(
+            ui,
+            format!("Rows {start}..{end}"),
+            format!(
+                "{} pages ({bytes} bytes), {partial_pages} of them partially selected, \
+                 {extra_rows} rows decoded outside the range",
+                pages.len()
+            ),
+        );
+    }
+}
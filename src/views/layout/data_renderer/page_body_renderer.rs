@@ -0,0 +1,657 @@
+use std::ops::Range;
+
+use egui::{Id, Label, RichText, ScrollArea, Ui, Widget};
+use egui_extras::{Column, TableBuilder};
+use parquet::basic::Encoding;
+
+use crate::file::{
+    ByteStreamSplit, DeltaBinaryPacked, DeltaValues, HybridStream, PageBody, RunKind,
+};
+
+use super::{DataRenderer, HEADER_VALUE_SIZE};
+
+const TABLE_MAX_HEIGHT: f32 = 240.;
+const TABLE_ROW_HEIGHT: f32 = 18.;
+/// Bit-packed values listed in the runs table, the rest are shown on hover
+const MAX_LISTED_VALUES: usize = 16;
+/// Bytes of the selected run or block shown as hex, as runs can span a whole page
+const MAX_HEX_BYTES: usize = 256;
+
+/// The level, dictionary index and value streams of a decompressed data page: RLE/bit-packed
+/// hybrid streams run by run, delta encoded values block by block, and split byte streams lane by
+/// lane.
+pub struct PageBodyRenderer;
+impl PageBodyRenderer {
+    fn selection_id(id: Id) -> Id {
+        id.with("selected_bytes")
+    }
+
+    /// Bytes of the selected run or block within the page, if they are stored as is in the file.
+    pub fn selected_bytes(ui: &Ui, id: Id, body: &PageBody) -> Option<Range<usize>> {
+        let selected = ui.data(|d| d.get_temp::<Range<usize>>(Self::selection_id(id)))?;
+        (selected.end <= body.file_mapped_length).then_some(selected)
+    }
+
+    pub fn render(ui: &mut Ui, id: Id, body: &PageBody) {
+        DataRenderer::render_header_labeled_value(ui, "Codec", body.codec.to_string());
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Decompressed Size",
+            format!("{} bytes", body.bytes.len()),
+        );
+        // Level segments of V2 pages have no values
+        if !body.values.is_empty() {
+            DataRenderer::render_header_labeled_value(
+                ui,
+                "Values",
+                format!(
+                    "{}, bytes {}..{}, {} not null",
+                    body.encoding, body.values.start, body.values.end, body.num_non_null
+                ),
+            );
+        }
+        if body.file_mapped_length < body.bytes.len() {
+            DataRenderer::render_header_value(
+                ui,
+                format!(
+                    "Bytes from {} on are decompressed, so runs after them are only shown below",
+                    body.file_mapped_length
+                ),
+            );
+        }
+        if body.streams.is_empty() && body.delta.is_none() && body.byte_stream_split.is_none() {
+            DataRenderer::render_header_value(
+                ui,
+                "The page has no RLE/bit-packed hybrid, delta encoded or split byte streams",
+            );
+            return;
+        }
+
+        let selection_id = Self::selection_id(id);
+        let mut selected = ui.data(|d| d.get_temp::<Range<usize>>(selection_id));
+        let mut clicked = None;
+        for (stream_idx, stream) in body.streams.iter().enumerate() {
+            ui.separator();
+            DataRenderer::render_header_collapsible(ui, stream.name, |ui| {
+                if let Some(bytes) =
+                    Self::render_stream(ui, id.with(stream_idx), stream, selected.as_ref())
+                {
+                    clicked = Some(bytes);
+                }
+            });
+        }
+        if let Some(delta) = &body.delta {
+            ui.separator();
+            DataRenderer::render_header_collapsible(ui, "Delta Encoding", |ui| {
+                if let Some(bytes) =
+                    Self::render_delta(ui, id.with("delta"), body, delta, selected.as_ref())
+                {
+                    clicked = Some(bytes);
+                }
+            });
+        }
+        if let Some(split) = &body.byte_stream_split {
+            ui.separator();
+            DataRenderer::render_header_collapsible(ui, "Byte Stream Split", |ui| {
+                if let Some(bytes) = Self::render_byte_stream_split(
+                    ui,
+                    id.with("split"),
+                    body,
+                    split,
+                    selected.as_ref(),
+                ) {
+                    clicked = Some(bytes);
+                }
+            });
+        }
+        if let Some(bytes) = clicked {
+            selected = (selected.as_ref() != Some(&bytes)).then_some(bytes);
+        }
+
+        if let Some(selected) = &selected {
+            ui.separator();
+            let run = body
+                .streams
+                .iter()
+                .flat_map(|stream| &stream.runs)
+                .find(|run| run.bytes == *selected);
+            let block = body
+                .delta
+                .iter()
+                .flat_map(|delta| &delta.streams)
+                .flat_map(|stream| &stream.blocks)
+                .find(|block| block.bytes == *selected);
+            let header_length = run
+                .map(|run| run.header_length)
+                .or(block.map(|block| block.header_length));
+            if let Some(header_length) = header_length {
+                let (header, payload) = body.bytes[selected.clone()].split_at(header_length);
+                let kind = if run.is_some() { "Run" } else { "Block" };
+                DataRenderer::render_header_labeled_value(
+                    ui,
+                    format!("{kind} Header"),
+                    Self::hex(header),
+                );
+                DataRenderer::render_header_labeled_value(
+                    ui,
+                    format!("{kind} Payload"),
+                    Self::hex(payload),
+                );
+            } else if let Some(lane) = body
+                .byte_stream_split
+                .iter()
+                .flat_map(|split| &split.streams)
+                .position(|stream| stream.bytes == *selected)
+            {
+                // A lane holds one byte of every value, so only its start is shown
+                DataRenderer::render_header_labeled_value(
+                    ui,
+                    format!("Byte {lane} Stream"),
+                    Self::hex(&body.bytes[selected.clone()]),
+                );
+            } else {
+                DataRenderer::render_header_labeled_value(
+                    ui,
+                    "Selected Bytes",
+                    Self::hex(&body.bytes[selected.clone()]),
+                );
+            }
+        }
+
+        ui.data_mut(|d| match selected {
+            Some(selected) => d.insert_temp(selection_id, selected),
+            None => d.remove::<Range<usize>>(selection_id),
+        });
+    }
+
+    /// Render the stream's runs, returning the bytes of a clicked run.
+    fn render_stream(
+        ui: &mut Ui,
+        id: Id,
+        stream: &HybridStream,
+        selected: Option<&Range<usize>>,
+    ) -> Option<Range<usize>> {
+        let num_rle = stream
+            .runs
+            .iter()
+            .filter(|run| matches!(run.kind, RunKind::Rle { .. }))
+            .count();
+        DataRenderer::render_header_labeled_value(ui, "Bit Width", stream.bit_width.to_string());
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Bytes",
+            format!(
+                "{}..{} ({} bytes)",
+                stream.bytes.start,
+                stream.bytes.end,
+                stream.bytes.len()
+            ),
+        );
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Runs",
+            format!(
+                "{} ({num_rle} RLE, {} bit-packed), {} values",
+                stream.runs.len(),
+                stream.runs.len() - num_rle,
+                stream.num_values()
+            ),
+        );
+        if let Some(error) = &stream.error {
+            DataRenderer::render_header_labeled_value(ui, "Error", error);
+        }
+
+        let text = |text: String| RichText::new(text).monospace().size(HEADER_VALUE_SIZE);
+        let mut clicked = None;
+        ScrollArea::horizontal().id_salt(id).show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt(id)
+                .striped(true)
+                .max_scroll_height(TABLE_MAX_HEIGHT)
+                .columns(Column::auto(), 4)
+                .column(Column::remainder().clip(true))
+                .header(TABLE_ROW_HEIGHT, |mut header| {
+                    for title in ["Run", "Type", "Values", "Bytes", "Decoded"] {
+                        header.col(|ui| {
+                            Label::new(text(String::from(title)).strong()).ui(ui);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(TABLE_ROW_HEIGHT, stream.runs.len(), |mut row| {
+                        let run_idx = row.index();
+                        let run = &stream.runs[run_idx];
+                        row.col(|ui| {
+                            let is_selected = selected == Some(&run.bytes);
+                            if ui
+                                .selectable_label(is_selected, text(run_idx.to_string()))
+                                .on_hover_text("Show the run's bytes")
+                                .clicked()
+                            {
+                                clicked = Some(run.bytes.clone());
+                            }
+                        });
+                        let (kind, decoded, full) = match &run.kind {
+                            RunKind::Rle { value, count } => {
+                                let decoded = format!("{value} × {count}");
+                                ("RLE", decoded.clone(), decoded)
+                            }
+                            RunKind::BitPacked { values, padding } => {
+                                let list = |values: &[u64]| {
+                                    values
+                                        .iter()
+                                        .map(u64::to_string)
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                };
+                                let mut decoded =
+                                    list(&values[..values.len().min(MAX_LISTED_VALUES)]);
+                                if values.len() > MAX_LISTED_VALUES {
+                                    decoded.push_str(", …");
+                                }
+                                let mut full = list(values);
+                                if *padding > 0 {
+                                    full.push_str(&format!("\n{padding} padding values"));
+                                }
+                                ("Bit-packed", decoded, full)
+                            }
+                        };
+                        row.col(|ui| {
+                            Label::new(text(String::from(kind))).ui(ui);
+                        });
+                        row.col(|ui| {
+                            Label::new(text(run.num_values().to_string())).ui(ui);
+                        });
+                        row.col(|ui| {
+                            Label::new(text(format!(
+                                "{}..{} ({}+{})",
+                                run.bytes.start,
+                                run.bytes.end,
+                                run.header_length,
+                                run.bytes.len() - run.header_length
+                            )))
+                            .ui(ui)
+                            .on_hover_text("Header and payload bytes");
+                        });
+                        row.col(|ui| {
+                            Label::new(text(decoded))
+                                .truncate()
+                                .ui(ui)
+                                .on_hover_text(full);
+                        });
+                    });
+                });
+        });
+        clicked
+    }
+
+    /// Render the delta encoded streams and the values they decode to, returning the bytes of a
+    /// clicked block.
+    fn render_delta(
+        ui: &mut Ui,
+        id: Id,
+        body: &PageBody,
+        delta: &DeltaValues,
+        selected: Option<&Range<usize>>,
+    ) -> Option<Range<usize>> {
+        let mut clicked = None;
+        for (stream_idx, stream) in delta.streams.iter().enumerate() {
+            DataRenderer::render_header_collapsible(ui, stream.name, |ui| {
+                if let Some(bytes) =
+                    Self::render_delta_stream(ui, id.with(stream_idx), stream, selected)
+                {
+                    clicked = Some(bytes);
+                }
+            });
+        }
+        if let Some(data) = &delta.data {
+            let name = if body.encoding == Encoding::DELTA_BYTE_ARRAY {
+                "Suffix Bytes"
+            } else {
+                "Byte Array Bytes"
+            };
+            DataRenderer::render_header_labeled_value(
+                ui,
+                name,
+                format!("{}..{} ({} bytes)", data.start, data.end, data.len()),
+            );
+        }
+        if let Some(error) = &delta.error {
+            DataRenderer::render_header_labeled_value(ui, "Error", error);
+        }
+
+        // Byte arrays also list the lengths they were split and reconstructed with
+        let length_streams = if body.encoding == Encoding::DELTA_BINARY_PACKED {
+            &[][..]
+        } else {
+            &delta.streams[..]
+        };
+        let text = |text: String| RichText::new(text).monospace().size(HEADER_VALUE_SIZE);
+        DataRenderer::render_header_collapsible(ui, "Decoded Values", |ui| {
+            let num_rows = length_streams
+                .iter()
+                .map(|stream| stream.values.len())
+                .chain([delta.values.len()])
+                .max()
+                .unwrap_or_default();
+            ScrollArea::horizontal()
+                .id_salt(id.with("values"))
+                .show(ui, |ui| {
+                    TableBuilder::new(ui)
+                        .id_salt(id.with("values"))
+                        .striped(true)
+                        .max_scroll_height(TABLE_MAX_HEIGHT)
+                        .columns(Column::auto(), 1 + length_streams.len())
+                        .column(Column::remainder().clip(true))
+                        .header(TABLE_ROW_HEIGHT, |mut header| {
+                            let titles = ["#"]
+                                .into_iter()
+                                .chain(length_streams.iter().map(|stream| stream.name))
+                                .chain(["Value"]);
+                            for title in titles {
+                                header.col(|ui| {
+                                    Label::new(text(String::from(title)).strong()).ui(ui);
+                                });
+                            }
+                        })
+                        .body(|body| {
+                            body.rows(TABLE_ROW_HEIGHT, num_rows, |mut row| {
+                                let idx = row.index();
+                                row.col(|ui| {
+                                    Label::new(text(idx.to_string())).ui(ui);
+                                });
+                                for stream in length_streams {
+                                    row.col(|ui| {
+                                        if let Some(length) = stream.values.get(idx) {
+                                            Label::new(text(length.to_string())).ui(ui);
+                                        }
+                                    });
+                                }
+                                row.col(|ui| {
+                                    if let Some(value) = delta.values.get(idx) {
+                                        Label::new(text(value.clone()))
+                                            .truncate()
+                                            .ui(ui)
+                                            .on_hover_text(value);
+                                    }
+                                });
+                            });
+                        });
+                });
+        });
+        clicked
+    }
+
+    /// Render a DELTA_BINARY_PACKED stream block by block, returning the bytes of a clicked block.
+    fn render_delta_stream(
+        ui: &mut Ui,
+        id: Id,
+        stream: &DeltaBinaryPacked,
+        selected: Option<&Range<usize>>,
+    ) -> Option<Range<usize>> {
+        DataRenderer::render_header_labeled_value(ui, "Block Size", stream.block_size.to_string());
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Miniblocks Per Block",
+            stream.miniblocks_per_block.to_string(),
+        );
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Total Values",
+            stream.total_values.to_string(),
+        );
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "First Value",
+            stream.first_value.to_string(),
+        );
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Bytes",
+            format!(
+                "{}..{} ({} bytes, header {}..{})",
+                stream.bytes.start,
+                stream.bytes.end,
+                stream.bytes.len(),
+                stream.header.start,
+                stream.header.end
+            ),
+        );
+        if let Some(error) = &stream.error {
+            DataRenderer::render_header_labeled_value(ui, "Error", error);
+        }
+        if stream.blocks.is_empty() {
+            return None;
+        }
+
+        let text = |text: String| RichText::new(text).monospace().size(HEADER_VALUE_SIZE);
+        let mut clicked = None;
+        ScrollArea::horizontal().id_salt(id).show(ui, |ui| {
+            TableBuilder::new(ui)
+                .id_salt(id)
+                .striped(true)
+                .max_scroll_height(TABLE_MAX_HEIGHT)
+                .columns(Column::auto(), 2)
+                .column(Column::auto().clip(true))
+                .column(Column::remainder())
+                .header(TABLE_ROW_HEIGHT, |mut header| {
+                    for title in ["Block", "Min Delta", "Miniblock Bit Widths", "Bytes"] {
+                        header.col(|ui| {
+                            Label::new(text(String::from(title)).strong()).ui(ui);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(TABLE_ROW_HEIGHT, stream.blocks.len(), |mut row| {
+                        let block_idx = row.index();
+                        let block = &stream.blocks[block_idx];
+                        row.col(|ui| {
+                            let is_selected = selected == Some(&block.bytes);
+                            if ui
+                                .selectable_label(is_selected, text(block_idx.to_string()))
+                                .on_hover_text("Show the block's bytes")
+                                .clicked()
+                            {
+                                clicked = Some(block.bytes.clone());
+                            }
+                        });
+                        row.col(|ui| {
+                            Label::new(text(block.min_delta.to_string())).ui(ui);
+                        });
+                        row.col(|ui| {
+                            let bit_widths = block
+                                .miniblocks
+                                .iter()
+                                .map(|miniblock| miniblock.bit_width.to_string())
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            let spans = block
+                                .miniblocks
+                                .iter()
+                                .enumerate()
+                                .map(|(idx, miniblock)| {
+                                    if miniblock.num_values == 0 {
+                                        format!("Miniblock {idx}: not stored, past the last value")
+                                    } else {
+                                        format!(
+                                            "Miniblock {idx}: {} bits, {} values, bytes {}..{}",
+                                            miniblock.bit_width,
+                                            miniblock.num_values,
+                                            miniblock.bytes.start,
+                                            miniblock.bytes.end
+                                        )
+                                    }
+                                })
+                                .collect::<Vec<_>>()
+                                .join("\n");
+                            Label::new(text(bit_widths))
+                                .truncate()
+                                .ui(ui)
+                                .on_hover_text(spans);
+                        });
+                        row.col(|ui| {
+                            Label::new(text(format!(
+                                "{}..{} ({}+{})",
+                                block.bytes.start,
+                                block.bytes.end,
+                                block.header_length,
+                                block.bytes.len() - block.header_length
+                            )))
+                            .ui(ui)
+                            .on_hover_text("Header and miniblock bytes");
+                        });
+                    });
+                });
+        });
+        clicked
+    }
+
+    /// Render the byte streams as parallel lanes next to the values they reassemble into,
+    /// returning the bytes of a clicked stream.
+    fn render_byte_stream_split(
+        ui: &mut Ui,
+        id: Id,
+        page_body: &PageBody,
+        split: &ByteStreamSplit,
+        selected: Option<&Range<usize>>,
+    ) -> Option<Range<usize>> {
+        let mean_entropy = split
+            .streams
+            .iter()
+            .map(|stream| stream.entropy)
+            .sum::<f64>()
+            / split.streams.len() as f64;
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Value Width",
+            format!("{} bytes", split.value_width),
+        );
+        DataRenderer::render_header_labeled_value(ui, "Values", split.num_values.to_string());
+        DataRenderer::render_header_labeled_value(
+            ui,
+            "Entropy",
+            format!(
+                "{mean_entropy:.2} bits/byte per stream on average, {:.2} bits/byte without \
+                 splitting by byte position",
+                split.entropy
+            ),
+        );
+        if let Some(error) = &split.error {
+            DataRenderer::render_header_labeled_value(ui, "Error", error);
+        }
+
+        let text = |text: String| RichText::new(text).monospace().size(HEADER_VALUE_SIZE);
+        let mut clicked = None;
+        DataRenderer::render_header_collapsible(ui, "Streams", |ui| {
+            TableBuilder::new(ui)
+                .id_salt(id.with("streams"))
+                .striped(true)
+                .max_scroll_height(TABLE_MAX_HEIGHT)
+                .columns(Column::auto(), 2)
+                .column(Column::remainder())
+                .header(TABLE_ROW_HEIGHT, |mut header| {
+                    for title in ["Stream", "Bytes", "Entropy"] {
+                        header.col(|ui| {
+                            Label::new(text(String::from(title)).strong()).ui(ui);
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(TABLE_ROW_HEIGHT, split.streams.len(), |mut row| {
+                        let stream_idx = row.index();
+                        let stream = &split.streams[stream_idx];
+                        row.col(|ui| {
+                            let is_selected = selected == Some(&stream.bytes);
+                            if ui
+                                .selectable_label(is_selected, text(format!("Byte {stream_idx}")))
+                                .on_hover_text("Show the stream's bytes")
+                                .clicked()
+                            {
+                                clicked = Some(stream.bytes.clone());
+                            }
+                        });
+                        row.col(|ui| {
+                            Label::new(text(format!(
+                                "{}..{}",
+                                stream.bytes.start, stream.bytes.end
+                            )))
+                            .ui(ui);
+                        });
+                        row.col(|ui| {
+                            Label::new(text(format!("{:.2} bits/byte", stream.entropy)))
+                                .ui(ui)
+                                .on_hover_text(
+                                    "Shannon entropy of the stream's bytes, out of 8 bits/byte",
+                                );
+                        });
+                    });
+                });
+        });
+
+        DataRenderer::render_header_collapsible(ui, "Lanes", |ui| {
+            ScrollArea::horizontal()
+                .id_salt(id.with("lanes"))
+                .show(ui, |ui| {
+                    TableBuilder::new(ui)
+                        .id_salt(id.with("lanes"))
+                        .striped(true)
+                        .max_scroll_height(TABLE_MAX_HEIGHT)
+                        .columns(Column::auto(), 1 + split.value_width)
+                        .column(Column::remainder().clip(true))
+                        .header(TABLE_ROW_HEIGHT, |mut header| {
+                            header.col(|ui| {
+                                Label::new(text(String::from("#")).strong()).ui(ui);
+                            });
+                            for stream_idx in 0..split.value_width {
+                                header.col(|ui| {
+                                    Label::new(text(format!("B{stream_idx}")).strong())
+                                        .ui(ui)
+                                        .on_hover_text(format!("Byte stream {stream_idx}"));
+                                });
+                            }
+                            header.col(|ui| {
+                                Label::new(text(String::from("Value")).strong()).ui(ui);
+                            });
+                        })
+                        .body(|body| {
+                            body.rows(TABLE_ROW_HEIGHT, split.num_values, |mut row| {
+                                let idx = row.index();
+                                row.col(|ui| {
+                                    Label::new(text(idx.to_string())).ui(ui);
+                                });
+                                for stream in &split.streams {
+                                    row.col(|ui| {
+                                        Label::new(text(format!(
+                                            "{:02x}",
+                                            page_body.bytes[stream.bytes.start + idx]
+                                        )))
+                                        .ui(ui);
+                                    });
+                                }
+                                row.col(|ui| {
+                                    let value = &split.values[idx];
+                                    Label::new(text(value.clone()))
+                                        .truncate()
+                                        .ui(ui)
+                                        .on_hover_text(value);
+                                });
+                            });
+                        });
+                });
+        });
+        clicked
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        let mut hex = bytes
+            .iter()
+            .take(MAX_HEX_BYTES)
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        if bytes.len() > MAX_HEX_BYTES {
+            hex += &format!(" … ({} bytes)", bytes.len());
+        }
+        hex
+    }
+}
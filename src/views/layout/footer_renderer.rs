@@ -1,16 +1,27 @@
-use egui::{Color32, Frame, Grid, Label, Margin, RichText, ScrollArea, Ui, Widget};
+use std::{collections::BTreeSet, sync::mpsc::Sender};
+
+use egui::{
+    Align, Color32, Frame, Grid, Label, Margin, RichText, ScrollArea, Stroke, StrokeKind, Ui,
+    Widget,
+};
 use row_groups_renderer::RowGroupsRenderer;
 use schema_renderer::SchemaRenderer;
+use size_renderer::FooterSizeRenderer;
 
-use crate::file::{Field, ParkhayFooter};
+use crate::file::{
+    ByteInterval, FILE_METADATA, Field, ParkhayFooter, ReadRequest, ThriftNode, ThriftTrace,
+};
 
 use super::{
-    CORNER_RADIUS,
+    CORNER_RADIUS, FOCUS_HIGHLIGHT_COLOR, MATCH_HIGHLIGHT_COLOR,
     components::{CollapsibleSection, CollapsibleSectionIcon, LabeledValue},
+    navigation::{LayoutRegion, Navigator, SectionHighlight},
+    thrift_renderer::ThriftRenderer,
 };
 
 mod row_groups_renderer;
 mod schema_renderer;
+mod size_renderer;
 mod ui_helpers;
 
 const LAYOUT_LABEL_SIZE: f32 = 19.;
@@ -23,7 +34,24 @@ const INNER_MARGIN: (i8, i8) = (10, 10);
 
 pub struct FooterRenderer;
 impl FooterRenderer {
-    pub fn render(ui: &mut Ui, footer: &ParkhayFooter) {
+    pub fn render(
+        ui: &mut Ui,
+        footer: &ParkhayFooter,
+        footer_interval: ByteInterval,
+        reader_tx: &Sender<ReadRequest>,
+        navigator: &mut Navigator,
+        projection: &mut BTreeSet<usize>,
+    ) {
+        // Offsets in the footer are shown in its encoded bytes
+        let highlight = navigator.layout_highlight(LayoutRegion::Footer);
+        let focused_byte = highlight
+            .focused_byte
+            .filter(|byte| (footer_interval.0..=footer_interval.1).contains(byte))
+            .map(|byte| (byte - footer_interval.0) as usize);
+        let should_reveal = highlight.should_scroll && focused_byte.is_some();
+        // The bytes and trace of the encoded footer are shared by the sections that use them
+        let encoded_footer_id = ui.make_persistent_id("encoded_footer");
+
         Frame::canvas(ui.style())
             .fill(Color32::from_rgb(246, 246, 246))
             .corner_radius(CORNER_RADIUS)
@@ -43,28 +71,97 @@ impl FooterRenderer {
                 );
                 ui.separator();
                 Self::render_header_collapsible(ui, "Schema", |ui| {
-                    SchemaRenderer::render(ui, &footer.schema_root)
+                    SchemaRenderer::render(ui, &footer.schema_root, projection)
                 });
                 ui.separator();
                 Self::render_header_collapsible(ui, "Column Orders", |ui| {
                     if let Some(column_orders) = &footer.column_orders {
-                        Self::render_column_orders(ui, column_orders, &footer.leaves());
+                        Self::render_column_orders(ui, column_orders, footer.leaves());
                     } else {
                         Self::render_header_value(ui, "N/A");
                     }
                 });
                 ui.separator();
-                Self::render_header_collapsible(ui, "Key Value Metadata", |ui| {
-                    if let Some(kv_metadata) = &footer.key_value_metadata {
-                        Self::render_key_value_metadata(ui, kv_metadata);
-                    } else {
+                Self::render_header_highlighted_collapsible(
+                    ui,
+                    "Key Value Metadata",
+                    navigator.footer_key_value_metadata_highlight(),
+                    |ui| {
+                        if let Some(kv_metadata) = &footer.key_value_metadata {
+                            let trace = ThriftRenderer::read_encoded(
+                                ui,
+                                encoded_footer_id,
+                                footer_interval,
+                                &FILE_METADATA,
+                                reader_tx,
+                            )
+                            .map(|(_, trace)| trace);
+                            Self::render_key_value_metadata(
+                                ui,
+                                kv_metadata,
+                                trace.as_deref(),
+                                footer_interval.0,
+                                navigator,
+                            );
+                        } else {
+                            Self::render_header_value(ui, "N/A");
+                        }
+                    },
+                );
+                ui.separator();
+                CollapsibleSection::new(
+                    RichText::new("Row Group Metadata")
+                        .monospace()
+                        .size(HEADER_LABEL_SIZE),
+                    CollapsibleSectionIcon::Default,
+                    true,
+                )
+                .open(navigator.should_reveal_footer())
+                .show(ui, |ui| {
+                    RowGroupsRenderer::render(
+                        ui,
+                        &footer.row_groups,
+                        footer.column_descriptors(),
+                        navigator,
+                    );
+                });
+                ui.separator();
+                Self::render_header_collapsible(ui, "Unknown Fields", |ui| {
+                    if footer.unknown_fields.is_empty() {
                         Self::render_header_value(ui, "N/A");
+                    } else {
+                        ThriftRenderer::render_unknown_fields(
+                            ui,
+                            ui.make_persistent_id("unknown_footer_fields"),
+                            &footer.unknown_fields,
+                        );
                     }
                 });
                 ui.separator();
-                Self::render_header_collapsible(ui, "Row Group Metadata", |ui| {
-                    RowGroupsRenderer::render(ui, &footer.row_groups);
+                Self::render_header_collapsible(ui, "Size Breakdown", |ui| {
+                    FooterSizeRenderer::render(ui, encoded_footer_id, footer_interval, reader_tx);
                 });
+                ui.separator();
+                Self::render_header_highlighted_collapsible(
+                    ui,
+                    "Encoded Footer",
+                    SectionHighlight {
+                        should_reveal,
+                        should_scroll: should_reveal,
+                        ..Default::default()
+                    },
+                    |ui| {
+                        ThriftRenderer::render_encoded(
+                            ui,
+                            encoded_footer_id,
+                            footer_interval,
+                            &FILE_METADATA,
+                            focused_byte,
+                            reader_tx,
+                            navigator,
+                        );
+                    },
+                );
             });
     }
 
@@ -115,10 +212,47 @@ impl FooterRenderer {
         .show(ui, content);
     }
 
-    fn render_key_value_metadata(ui: &mut Ui, kv_metadata: &[parquet::format::KeyValue]) {
+    fn render_header_highlighted_collapsible(
+        ui: &mut Ui,
+        header: impl AsRef<str>,
+        highlight: SectionHighlight,
+        content: impl FnOnce(&mut Ui),
+    ) {
+        let mut header = RichText::new(header.as_ref())
+            .monospace()
+            .size(HEADER_LABEL_SIZE);
+        if highlight.is_match {
+            header = header.background_color(MATCH_HIGHLIGHT_COLOR.gamma_multiply(0.15));
+        }
+        let response = CollapsibleSection::new(header, CollapsibleSectionIcon::Default, true)
+            .open(highlight.should_reveal)
+            .show(ui, content);
+
+        if highlight.is_focused {
+            ui.painter().rect_stroke(
+                response.rect.expand(1.),
+                2.,
+                Stroke::new(2., FOCUS_HIGHLIGHT_COLOR),
+                StrokeKind::Outside,
+            );
+        }
+        if highlight.should_scroll {
+            response.scroll_to_me(Some(Align::Min));
+        }
+    }
+
+    /// The key value pairs, whose values can be decoded as thrift once the footer's trace, whose
+    /// bytes start at `footer_start`, is known.
+    fn render_key_value_metadata(
+        ui: &mut Ui,
+        kv_metadata: &[parquet::format::KeyValue],
+        trace: Option<&ThriftTrace>,
+        footer_start: u64,
+        navigator: &mut Navigator,
+    ) {
         ScrollArea::horizontal().show(ui, |ui| {
             Grid::new("Key Value Metadata")
-                .num_columns(2)
+                .num_columns(3)
                 .spacing([10., 5.])
                 .striped(true)
                 .show(ui, |ui| {
@@ -136,8 +270,9 @@ impl FooterRenderer {
                             .strong(),
                     )
                     .ui(ui);
+                    ui.label("");
                     ui.end_row();
-                    for kv in kv_metadata {
+                    for (idx, kv) in kv_metadata.iter().enumerate() {
                         Label::new(
                             RichText::new(&kv.key)
                                 .monospace()
@@ -151,6 +286,28 @@ impl FooterRenderer {
                             Label::new(RichText::new("N/A").monospace().size(SUBHEADER_VALUE_SIZE))
                                 .ui(ui);
                         }
+                        // FileMetaData.key_value_metadata is field 5, KeyValue.value is field 2
+                        let value_bytes = trace
+                            .and_then(|trace| trace.find(&[(5, Some(idx)), (2, None)]))
+                            .and_then(ThriftNode::binary_bytes)
+                            .filter(|bytes| !bytes.is_empty());
+                        if let Some(bytes) = value_bytes {
+                            let clicked = ui
+                                .small_button(RichText::new("Decode").monospace())
+                                .on_hover_text("Decode the value as a thrift struct")
+                                .clicked();
+                            if clicked {
+                                navigator.request_thrift(
+                                    ui,
+                                    (
+                                        footer_start + bytes.start as u64,
+                                        footer_start + bytes.end as u64 - 1,
+                                    ),
+                                );
+                            }
+                        } else {
+                            ui.label("");
+                        }
                         ui.end_row();
                     }
                 });
@@ -1,105 +1,159 @@
 use egui::{Grid, Label, RichText, ScrollArea, Ui, Widget};
+use parquet::schema::types::ColumnDescPtr;
+
+use crate::{
+    file::Field,
+    views::layout::{
+        components::{LevelHistogramChart, LevelKind},
+        navigation::{FooterLocation, Navigator, SectionHighlight},
+    },
+};
 
 use super::{SUBHEADER_VALUE_SIZE, ui_helpers::UiHelpers};
 
 pub struct RowGroupsRenderer;
 
 impl RowGroupsRenderer {
-    pub fn render(ui: &mut Ui, row_groups: &[parquet::format::RowGroup]) {
+    pub fn render(
+        ui: &mut Ui,
+        row_groups: &[parquet::format::RowGroup],
+        columns: &[ColumnDescPtr],
+        navigator: &mut Navigator,
+    ) {
         for (idx, row_group) in row_groups.iter().enumerate() {
             if idx > 0 {
                 ui.separator();
             }
-            Self::render_row_group(ui, row_group, idx);
+            Self::render_row_group(ui, row_group, idx, columns, navigator);
         }
     }
 
-    fn render_row_group(ui: &mut Ui, row_group: &parquet::format::RowGroup, rg_idx: usize) {
-        UiHelpers::render_subheader_collapsible(ui, format!("Row Group: {rg_idx}"), |ui| {
-            Self::render_column_chunks(ui, row_group, rg_idx);
-            ui.separator();
-            UiHelpers::render_subheader_labeled_value(
-                ui,
-                "Total Byte Size",
-                row_group.total_byte_size.to_string(),
-            );
-            ui.separator();
-            UiHelpers::render_subheader_labeled_value(
-                ui,
-                "Num Rows",
-                row_group.num_rows.to_string(),
-            );
-            ui.separator();
-            UiHelpers::render_subheader_collapsible(ui, "Sorting Columns", |ui| {
-                if let Some(ref sorting_columns) = row_group.sorting_columns {
-                    for sorting_column in sorting_columns {
-                        UiHelpers::render_subheader_labeled_value(
-                            ui,
-                            "Column Index",
-                            sorting_column.column_idx.to_string(),
-                        );
-                        ui.separator();
-                        UiHelpers::render_subheader_labeled_value(
-                            ui,
-                            "Descending",
-                            sorting_column.descending.to_string(),
-                        );
-                        ui.separator();
-                        UiHelpers::render_subheader_labeled_value(
-                            ui,
-                            "Nulls First",
-                            sorting_column.nulls_first.to_string(),
-                        );
+    fn render_row_group(
+        ui: &mut Ui,
+        row_group: &parquet::format::RowGroup,
+        rg_idx: usize,
+        columns: &[ColumnDescPtr],
+        navigator: &mut Navigator,
+    ) {
+        let highlight = navigator.footer_highlight(FooterLocation {
+            row_group: rg_idx,
+            column: None,
+        });
+        UiHelpers::render_subheader_highlighted_collapsible(
+            ui,
+            format!("Row Group: {rg_idx}"),
+            highlight,
+            |ui| {
+                Self::render_column_chunks(ui, row_group, rg_idx, columns, navigator);
+                ui.separator();
+                UiHelpers::render_subheader_labeled_value(
+                    ui,
+                    "Total Byte Size",
+                    row_group.total_byte_size.to_string(),
+                );
+                ui.separator();
+                UiHelpers::render_subheader_labeled_value(
+                    ui,
+                    "Num Rows",
+                    row_group.num_rows.to_string(),
+                );
+                ui.separator();
+                UiHelpers::render_subheader_collapsible(ui, "Sorting Columns", |ui| {
+                    if let Some(ref sorting_columns) = row_group.sorting_columns {
+                        for sorting_column in sorting_columns {
+                            UiHelpers::render_subheader_labeled_value(
+                                ui,
+                                "Column Index",
+                                sorting_column.column_idx.to_string(),
+                            );
+                            ui.separator();
+                            UiHelpers::render_subheader_labeled_value(
+                                ui,
+                                "Descending",
+                                sorting_column.descending.to_string(),
+                            );
+                            ui.separator();
+                            UiHelpers::render_subheader_labeled_value(
+                                ui,
+                                "Nulls First",
+                                sorting_column.nulls_first.to_string(),
+                            );
+                        }
+                    } else {
+                        UiHelpers::render_subheader_value(ui, "N/A");
                     }
-                } else {
-                    UiHelpers::render_subheader_value(ui, "N/A");
-                }
-            });
+                });
 
-            ui.separator();
+                ui.separator();
 
-            UiHelpers::render_subheader_labeled_value(
-                ui,
-                "File Offset",
-                row_group
-                    .file_offset
-                    .map(|v| v.to_string())
-                    .unwrap_or("N/A".to_string()),
-            );
+                UiHelpers::render_subheader_offset_link(
+                    ui,
+                    "File Offset",
+                    row_group.file_offset,
+                    navigator,
+                );
 
-            ui.separator();
+                ui.separator();
 
-            UiHelpers::render_subheader_labeled_value(
-                ui,
-                "Total Compressed Size",
-                row_group
-                    .total_compressed_size
-                    .map(|v| v.to_string())
-                    .unwrap_or("N/A".to_string()),
-            );
+                UiHelpers::render_subheader_labeled_value(
+                    ui,
+                    "Total Compressed Size",
+                    row_group
+                        .total_compressed_size
+                        .map(|v| v.to_string())
+                        .unwrap_or("N/A".to_string()),
+                );
 
-            ui.separator();
+                ui.separator();
 
-            UiHelpers::render_subheader_labeled_value(
-                ui,
-                "Ordinal",
-                row_group
-                    .ordinal
-                    .map(|v| v.to_string())
-                    .unwrap_or("N/A".to_string()),
-            );
-        });
+                UiHelpers::render_subheader_labeled_value(
+                    ui,
+                    "Ordinal",
+                    row_group
+                        .ordinal
+                        .map(|v| v.to_string())
+                        .unwrap_or("N/A".to_string()),
+                );
+            },
+        );
     }
 
-    fn render_column_chunks(ui: &mut Ui, row_group: &parquet::format::RowGroup, rg_idx: usize) {
-        UiHelpers::render_subheader_collapsible(ui, "Column Chunks", |ui| {
-            for (cc_idx, column_chunk) in row_group.columns.iter().enumerate() {
-                if cc_idx != 0 {
-                    ui.separator();
+    fn render_column_chunks(
+        ui: &mut Ui,
+        row_group: &parquet::format::RowGroup,
+        rg_idx: usize,
+        columns: &[ColumnDescPtr],
+        navigator: &mut Navigator,
+    ) {
+        let should_reveal = navigator
+            .footer_highlight(FooterLocation {
+                row_group: rg_idx,
+                column: None,
+            })
+            .should_reveal;
+        UiHelpers::render_subheader_highlighted_collapsible(
+            ui,
+            "Column Chunks",
+            SectionHighlight {
+                should_reveal,
+                ..Default::default()
+            },
+            |ui| {
+                for (cc_idx, column_chunk) in row_group.columns.iter().enumerate() {
+                    if cc_idx != 0 {
+                        ui.separator();
+                    }
+                    Self::render_column_chunk(
+                        ui,
+                        rg_idx,
+                        column_chunk,
+                        cc_idx,
+                        columns.get(cc_idx),
+                        navigator,
+                    );
                 }
-                Self::render_column_chunk(ui, rg_idx, column_chunk, cc_idx);
-            }
-        });
+            },
+        );
     }
 
     fn render_column_chunk(
@@ -107,13 +161,21 @@ impl RowGroupsRenderer {
         rg_idx: usize,
         column_chunk: &parquet::format::ColumnChunk,
         cc_idx: usize,
+        column: Option<&ColumnDescPtr>,
+        navigator: &mut Navigator,
     ) {
+        let field = column.map(|column| column.self_type_ptr());
+        let highlight = navigator.footer_highlight(FooterLocation {
+            row_group: rg_idx,
+            column: Some(cc_idx),
+        });
         ui.push_id(
             format!("Row Group: {rg_idx}, Column Chunk: {cc_idx}"),
             |ui| {
-                UiHelpers::render_subheader_collapsible(
+                UiHelpers::render_subheader_highlighted_collapsible(
                     ui,
                     format!("Column Chunk: {cc_idx}"),
+                    highlight,
                     |ui| {
                         UiHelpers::render_subheader_labeled_value(
                             ui,
@@ -221,46 +283,62 @@ impl RowGroupsRenderer {
 
                                 ui.separator();
 
-                                UiHelpers::render_subheader_labeled_value(
+                                UiHelpers::render_subheader_offset_link(
                                     ui,
                                     "Data Page Offset",
-                                    metadata.data_page_offset.to_string(),
+                                    Some(metadata.data_page_offset),
+                                    navigator,
                                 );
 
                                 ui.separator();
 
-                                UiHelpers::render_subheader_labeled_value(
+                                UiHelpers::render_subheader_offset_link(
                                     ui,
                                     "Index Page Offset",
-                                    metadata
-                                        .index_page_offset
-                                        .map(|v| v.to_string())
-                                        .unwrap_or(String::from("N/A")),
+                                    metadata.index_page_offset,
+                                    navigator,
                                 );
 
                                 ui.separator();
 
-                                UiHelpers::render_subheader_labeled_value(
+                                UiHelpers::render_subheader_offset_link(
                                     ui,
                                     "Dictionary Page Offset",
-                                    metadata
-                                        .dictionary_page_offset
-                                        .map(|v| v.to_string())
-                                        .unwrap_or(String::from("N/A")),
+                                    metadata.dictionary_page_offset,
+                                    navigator,
                                 );
 
                                 ui.separator();
 
-                                // TODO statistics
-                                // TODO encoding stats
+                                UiHelpers::render_subheader_collapsible(ui, "Statistics", |ui| {
+                                    if let Some(ref statistics) = metadata.statistics {
+                                        Self::render_statistics(ui, statistics, field.as_ref());
+                                    } else {
+                                        UiHelpers::render_subheader_value(ui, "N/A");
+                                    }
+                                });
 
-                                UiHelpers::render_subheader_labeled_value(
+                                ui.separator();
+
+                                UiHelpers::render_subheader_collapsible(
+                                    ui,
+                                    "Encoding Stats",
+                                    |ui| {
+                                        if let Some(ref encoding_stats) = metadata.encoding_stats {
+                                            Self::render_encoding_stats(ui, encoding_stats);
+                                        } else {
+                                            UiHelpers::render_subheader_value(ui, "N/A");
+                                        }
+                                    },
+                                );
+
+                                ui.separator();
+
+                                UiHelpers::render_subheader_offset_link(
                                     ui,
                                     "Bloom Filter Offset",
-                                    metadata
-                                        .bloom_filter_offset
-                                        .map(|v| v.to_string())
-                                        .unwrap_or(String::from("N/A")),
+                                    metadata.bloom_filter_offset,
+                                    navigator,
                                 );
 
                                 ui.separator();
@@ -274,8 +352,27 @@ impl RowGroupsRenderer {
                                         .unwrap_or(String::from("N/A")),
                                 );
 
-                                // TODO size statistics
-                                // TODO geospatial statistics
+                                ui.separator();
+
+                                UiHelpers::render_subheader_collapsible(
+                                    ui,
+                                    "Size Statistics",
+                                    |ui| {
+                                        if let Some(ref size_statistics) = metadata.size_statistics
+                                        {
+                                            Self::render_size_statistics(
+                                                ui,
+                                                size_statistics,
+                                                column,
+                                            );
+                                        } else {
+                                            UiHelpers::render_subheader_value(ui, "N/A");
+                                        }
+                                    },
+                                );
+
+                                // TODO geospatial statistics, which the parquet-format version
+                                // used by the parquet crate doesn't define yet
                             } else {
                                 UiHelpers::render_subheader_value(ui, "N/A");
                             }
@@ -283,13 +380,11 @@ impl RowGroupsRenderer {
 
                         ui.separator();
 
-                        UiHelpers::render_subheader_labeled_value(
+                        UiHelpers::render_subheader_offset_link(
                             ui,
                             "Offset Index Offset",
-                            column_chunk
-                                .offset_index_offset
-                                .map(|v| v.to_string())
-                                .unwrap_or("N/A".to_string()),
+                            column_chunk.offset_index_offset,
+                            navigator,
                         );
 
                         ui.separator();
@@ -305,13 +400,11 @@ impl RowGroupsRenderer {
 
                         ui.separator();
 
-                        UiHelpers::render_subheader_labeled_value(
+                        UiHelpers::render_subheader_offset_link(
                             ui,
                             "Column Index Offset",
-                            column_chunk
-                                .column_index_offset
-                                .map(|v| v.to_string())
-                                .unwrap_or("N/A".to_string()),
+                            column_chunk.column_index_offset,
+                            navigator,
                         );
 
                         ui.separator();
@@ -332,6 +425,183 @@ impl RowGroupsRenderer {
         );
     }
 
+    fn render_statistics(
+        ui: &mut Ui,
+        statistics: &parquet::format::Statistics,
+        column: Option<&Field>,
+    ) {
+        UiHelpers::render_subheader_plain_value(
+            ui,
+            "Min Value",
+            statistics.min_value.as_deref(),
+            column,
+        );
+        ui.separator();
+        UiHelpers::render_subheader_plain_value(
+            ui,
+            "Max Value",
+            statistics.max_value.as_deref(),
+            column,
+        );
+        ui.separator();
+        UiHelpers::render_subheader_labeled_value(
+            ui,
+            "Is Min Value Exact",
+            statistics
+                .is_min_value_exact
+                .map(|v| v.to_string())
+                .unwrap_or(String::from("N/A")),
+        );
+        ui.separator();
+        UiHelpers::render_subheader_labeled_value(
+            ui,
+            "Is Max Value Exact",
+            statistics
+                .is_max_value_exact
+                .map(|v| v.to_string())
+                .unwrap_or(String::from("N/A")),
+        );
+        ui.separator();
+        UiHelpers::render_subheader_labeled_value(
+            ui,
+            "Null Count",
+            statistics
+                .null_count
+                .map(|v| v.to_string())
+                .unwrap_or(String::from("N/A")),
+        );
+        ui.separator();
+        UiHelpers::render_subheader_labeled_value(
+            ui,
+            "Distinct Count",
+            statistics
+                .distinct_count
+                .map(|v| v.to_string())
+                .unwrap_or(String::from("N/A")),
+        );
+        ui.separator();
+        UiHelpers::render_subheader_plain_value(
+            ui,
+            "Min (deprecated)",
+            statistics.min.as_deref(),
+            column,
+        );
+        ui.separator();
+        UiHelpers::render_subheader_plain_value(
+            ui,
+            "Max (deprecated)",
+            statistics.max.as_deref(),
+            column,
+        );
+    }
+
+    fn render_encoding_stats(ui: &mut Ui, encoding_stats: &[parquet::format::PageEncodingStats]) {
+        // Writers fall back to a non-dictionary encoding once the dictionary grows too large
+        let is_data_page = |stats: &&parquet::format::PageEncodingStats| {
+            matches!(
+                stats.page_type,
+                parquet::format::PageType::DATA_PAGE | parquet::format::PageType::DATA_PAGE_V2
+            )
+        };
+        let is_dictionary_encoded = |stats: &&parquet::format::PageEncodingStats| {
+            matches!(
+                stats.encoding,
+                parquet::format::Encoding::PLAIN_DICTIONARY
+                    | parquet::format::Encoding::RLE_DICTIONARY
+            )
+        };
+        let data_pages = encoding_stats
+            .iter()
+            .filter(is_data_page)
+            .map(|stats| i64::from(stats.count))
+            .sum::<i64>();
+        let dictionary_pages = encoding_stats
+            .iter()
+            .filter(is_data_page)
+            .filter(is_dictionary_encoded)
+            .map(|stats| i64::from(stats.count))
+            .sum::<i64>();
+        let has_dictionary = encoding_stats
+            .iter()
+            .any(|stats| stats.page_type == parquet::format::PageType::DICTIONARY_PAGE);
+        let dictionary_usage = match (has_dictionary, dictionary_pages) {
+            (false, _) => String::from("No dictionary"),
+            (true, n) if n == data_pages => String::from("All data pages dictionary encoded"),
+            (true, n) => format!(
+                "Dictionary fallback: {} of {data_pages} data pages not dictionary encoded",
+                data_pages - n
+            ),
+        };
+        UiHelpers::render_subheader_labeled_value(ui, "Dictionary Usage", dictionary_usage);
+
+        ui.separator();
+
+        UiHelpers::render_subheader_table(
+            ui,
+            "Encoding Stats",
+            &["Page Type", "Encoding", "Count"],
+            encoding_stats.iter().map(|stats| {
+                vec![
+                    parquet::basic::PageType::try_from(stats.page_type)
+                        .map(|v| v.to_string())
+                        .unwrap_or(format!("{:?}", stats.page_type)),
+                    parquet::basic::Encoding::try_from(stats.encoding)
+                        .map(|v| v.to_string())
+                        .unwrap_or(format!("{:?}", stats.encoding)),
+                    stats.count.to_string(),
+                ]
+            }),
+        );
+    }
+
+    fn render_size_statistics(
+        ui: &mut Ui,
+        size_statistics: &parquet::format::SizeStatistics,
+        column: Option<&ColumnDescPtr>,
+    ) {
+        UiHelpers::render_subheader_labeled_value(
+            ui,
+            "Unencoded Byte Array Data Bytes",
+            size_statistics
+                .unencoded_byte_array_data_bytes
+                .map(|v| v.to_string())
+                .unwrap_or(String::from("N/A")),
+        );
+        for (label, histogram, max_level, kind) in [
+            (
+                "Repetition Level Histogram",
+                &size_statistics.repetition_level_histogram,
+                column.map(|column| column.max_rep_level()),
+                LevelKind::Repetition,
+            ),
+            (
+                "Definition Level Histogram",
+                &size_statistics.definition_level_histogram,
+                column.map(|column| column.max_def_level()),
+                LevelKind::Definition,
+            ),
+        ] {
+            ui.separator();
+            UiHelpers::render_subheader_collapsible(ui, label, |ui| match histogram {
+                Some(histogram) => {
+                    if let Some(max_level) = max_level {
+                        LevelHistogramChart::show(ui, histogram, max_level, kind, "Column Chunk");
+                    }
+                    UiHelpers::render_subheader_table(
+                        ui,
+                        label,
+                        &["Level", "Count"],
+                        histogram
+                            .iter()
+                            .enumerate()
+                            .map(|(level, count)| vec![level.to_string(), count.to_string()]),
+                    );
+                }
+                None => UiHelpers::render_subheader_value(ui, "N/A"),
+            });
+        }
+    }
+
     fn render_key_value_metadata(ui: &mut Ui, kv_metadata: &Vec<parquet::format::KeyValue>) {
         ScrollArea::horizontal().show(ui, |ui| {
             Grid::new("Key Value Metadata")
@@ -1,4 +1,6 @@
-use egui::{Grid, RichText, Ui};
+use std::collections::BTreeSet;
+
+use egui::{Grid, Response, RichText, Ui};
 
 use crate::{
     file::Field,
@@ -17,8 +19,10 @@ pub struct SchemaRenderer;
 
 impl SchemaRenderer {
     const SCHEMA_MODE_DATA_KEY: &str = "schema_mode";
+    const PROJECTION_HINT: &str = "Include in the I/O plan";
 
-    pub fn render(ui: &mut Ui, schema_root: &Field) {
+    /// Leaves are checked when they are part of the projection, given as leaf indexes.
+    pub fn render(ui: &mut Ui, schema_root: &Field, projection: &mut BTreeSet<usize>) {
         ui.add_space(5.);
 
         let id = ui.make_persistent_id(Self::SCHEMA_MODE_DATA_KEY);
@@ -40,7 +44,7 @@ impl SchemaRenderer {
         });
         ui.add_space(10.);
         match selected {
-            SchemaMode::Tree => Self::render_schema_field(ui, schema_root),
+            SchemaMode::Tree => Self::render_schema_field(ui, schema_root, projection, &mut 0),
             SchemaMode::Message => {
                 ui.label(
                     RichText::new(Self::schema_message_string(schema_root))
@@ -68,118 +72,177 @@ impl SchemaRenderer {
         ui.end_row();
     }
 
-    fn render_schema_field(ui: &mut Ui, field: &Field) {
+    /// `next_leaf` is the index of the first leaf within the field, and is advanced past its leaves.
+    fn render_schema_field(
+        ui: &mut Ui,
+        field: &Field,
+        projection: &mut BTreeSet<usize>,
+        next_leaf: &mut usize,
+    ) {
         ui.style_mut().visuals.indent_has_left_vline = false;
         if field.is_primitive() {
-            Self::render_primitive_field(ui, field);
+            Self::render_primitive_field(ui, field, projection, next_leaf);
         } else if field.is_group() {
-            Self::render_group_field(ui, field);
+            Self::render_group_field(ui, field, projection, next_leaf);
         }
         ui.style_mut().visuals.indent_has_left_vline = false;
     }
 
-    fn render_primitive_field(ui: &mut Ui, field: &Field) {
-        Self::render_schema_primitive_collapsible(ui, field.name(), |ui| {
-            ui.add_space(15.);
-            Grid::new(format!("field_metadata_{}", field.name()))
-                .num_columns(2)
-                .spacing([10., 5.])
-                .striped(true)
-                .show(ui, |ui| {
-                    Self::render_schema_table_row(ui, "Name", field.name());
-                    if field.get_basic_info().has_repetition() {
+    fn render_primitive_field(
+        ui: &mut Ui,
+        field: &Field,
+        projection: &mut BTreeSet<usize>,
+        next_leaf: &mut usize,
+    ) {
+        let column = *next_leaf;
+        *next_leaf += 1;
+        let mut is_selected = projection.contains(&column);
+        let response =
+            Self::render_schema_primitive_collapsible(ui, field.name(), &mut is_selected, |ui| {
+                ui.add_space(15.);
+                Grid::new(format!("field_metadata_{}", field.name()))
+                    .num_columns(2)
+                    .spacing([10., 5.])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        Self::render_schema_table_row(ui, "Name", field.name());
+                        if field.get_basic_info().has_repetition() {
+                            Self::render_schema_table_row(
+                                ui,
+                                "Repetition",
+                                field.get_basic_info().repetition().to_string(),
+                            );
+                        } else {
+                            Self::render_schema_table_row(ui, "Repetition", "N/A");
+                        }
+
                         Self::render_schema_table_row(
                             ui,
-                            "Repetition",
-                            field.get_basic_info().repetition().to_string(),
+                            "Physical Type",
+                            field.get_physical_type().to_string(),
+                        );
+
+                        Self::render_schema_table_row(
+                            ui,
+                            "Logical Type",
+                            field
+                                .get_basic_info()
+                                .logical_type()
+                                .map_or(String::from("N/A"), |v| format!("{v:?}")),
+                        );
+
+                        Self::render_schema_table_row(
+                            ui,
+                            "Converted Type",
+                            field.get_basic_info().converted_type().to_string(),
                         );
-                    } else {
-                        Self::render_schema_table_row(ui, "Repetition", "N/A");
-                    }
-
-                    Self::render_schema_table_row(
-                        ui,
-                        "Physical Type",
-                        field.get_physical_type().to_string(),
-                    );
-
-                    Self::render_schema_table_row(
-                        ui,
-                        "Logical Type",
-                        field
-                            .get_basic_info()
-                            .logical_type()
-                            .map_or(String::from("N/A"), |v| format!("{v:?}")),
-                    );
-
-                    Self::render_schema_table_row(
-                        ui,
-                        "Converted Type",
-                        field.get_basic_info().converted_type().to_string(),
-                    );
-
-                    Self::render_schema_table_row(ui, "Scale", field.get_scale().to_string());
-
-                    Self::render_schema_table_row(
-                        ui,
-                        "Precision",
-                        field.get_precision().to_string(),
-                    );
-                });
-            ui.add_space(15.);
-        });
-    }
 
-    fn render_group_field(ui: &mut Ui, field: &Field) {
-        Self::render_schema_group_collapsible(ui, field.name(), |ui| {
-            Self::render_schema_metadata_collapsible(ui, "[metadata]", |ui| {
+                        Self::render_schema_table_row(ui, "Scale", field.get_scale().to_string());
+
+                        Self::render_schema_table_row(
+                            ui,
+                            "Precision",
+                            field.get_precision().to_string(),
+                        );
+                    });
                 ui.add_space(15.);
-                ui.indent("indent", |ui| {
-                    Grid::new(field.name())
-                        .num_columns(2)
-                        .spacing([10., 5.])
-                        .striped(true)
-                        .show(ui, |ui| {
-                            Self::render_schema_table_row(ui, "Name", field.name());
-                            if field.get_basic_info().has_repetition() {
+            });
+        if response.on_hover_text(Self::PROJECTION_HINT).changed() {
+            if is_selected {
+                projection.insert(column);
+            } else {
+                projection.remove(&column);
+            }
+        }
+    }
+
+    fn render_group_field(
+        ui: &mut Ui,
+        field: &Field,
+        projection: &mut BTreeSet<usize>,
+        next_leaf: &mut usize,
+    ) {
+        let leaves = *next_leaf..*next_leaf + Self::num_leaves(field);
+        let num_selected = leaves
+            .clone()
+            .filter(|column| projection.contains(column))
+            .count();
+        let mut is_selected = num_selected > 0 && num_selected == leaves.len();
+        let is_partially_selected = num_selected > 0 && !is_selected;
+        let mut child_leaf = leaves.start;
+        let response = Self::render_schema_group_collapsible(
+            ui,
+            field.name(),
+            &mut is_selected,
+            is_partially_selected,
+            |ui| {
+                Self::render_schema_metadata_collapsible(ui, "[metadata]", |ui| {
+                    ui.add_space(15.);
+                    ui.indent("indent", |ui| {
+                        Grid::new(field.name())
+                            .num_columns(2)
+                            .spacing([10., 5.])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                Self::render_schema_table_row(ui, "Name", field.name());
+                                if field.get_basic_info().has_repetition() {
+                                    Self::render_schema_table_row(
+                                        ui,
+                                        "Repetition",
+                                        field.get_basic_info().repetition().to_string(),
+                                    );
+                                } else {
+                                    Self::render_schema_table_row(ui, "Repetition", "N/A");
+                                }
+
                                 Self::render_schema_table_row(
                                     ui,
-                                    "Repetition",
-                                    field.get_basic_info().repetition().to_string(),
+                                    "Logical Type",
+                                    field
+                                        .get_basic_info()
+                                        .logical_type()
+                                        .map_or(String::from("N/A"), |v| format!("{v:?}")),
                                 );
-                            } else {
-                                Self::render_schema_table_row(ui, "Repetition", "N/A");
-                            }
-
-                            Self::render_schema_table_row(
-                                ui,
-                                "Logical Type",
-                                field
-                                    .get_basic_info()
-                                    .logical_type()
-                                    .map_or(String::from("N/A"), |v| format!("{v:?}")),
-                            );
 
-                            Self::render_schema_table_row(
-                                ui,
-                                "Converted Type",
-                                field.get_basic_info().converted_type().to_string(),
-                            );
-                        });
+                                Self::render_schema_table_row(
+                                    ui,
+                                    "Converted Type",
+                                    field.get_basic_info().converted_type().to_string(),
+                                );
+                            });
+                    });
+                    ui.add_space(15.);
                 });
-                ui.add_space(15.);
-            });
-            for child_field in field.get_fields() {
-                Self::render_schema_field(ui, child_field);
+                for child_field in field.get_fields() {
+                    Self::render_schema_field(ui, child_field, projection, &mut child_leaf);
+                }
+            },
+        );
+        if response.on_hover_text(Self::PROJECTION_HINT).changed() {
+            if is_selected {
+                projection.extend(leaves.clone());
+            } else {
+                projection.retain(|column| !leaves.contains(column));
             }
-        });
+        }
+        // The children aren't rendered while the group is collapsed
+        *next_leaf = leaves.end;
+    }
+
+    fn num_leaves(field: &Field) -> usize {
+        if field.is_primitive() {
+            1
+        } else {
+            field.get_fields().iter().map(Self::num_leaves).sum()
+        }
     }
 
     fn render_schema_primitive_collapsible(
         ui: &mut Ui,
         header: impl AsRef<str>,
+        is_selected: &mut bool,
         content: impl FnOnce(&mut Ui),
-    ) {
+    ) -> Response {
         CollapsibleSection::new(
             RichText::new(header.as_ref())
                 .monospace()
@@ -187,14 +250,16 @@ impl SchemaRenderer {
             CollapsibleSectionIcon::Circle,
             true,
         )
-        .show(ui, content);
+        .show_with_checkbox(ui, is_selected, false, content)
     }
 
     fn render_schema_group_collapsible(
         ui: &mut Ui,
         header: impl AsRef<str>,
+        is_selected: &mut bool,
+        is_partially_selected: bool,
         content: impl FnOnce(&mut Ui),
-    ) {
+    ) -> Response {
         CollapsibleSection::new(
             RichText::new(header.as_ref())
                 .monospace()
@@ -202,7 +267,7 @@ impl SchemaRenderer {
             CollapsibleSectionIcon::Default,
             true,
         )
-        .show(ui, content);
+        .show_with_checkbox(ui, is_selected, is_partially_selected, content)
     }
 
     fn render_schema_metadata_collapsible(
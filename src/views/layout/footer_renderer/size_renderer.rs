@@ -0,0 +1,319 @@
+use std::sync::{Arc, mpsc::Sender};
+
+use egui::{Color32, Id, Label, RichText, Sense, Ui, Widget};
+use egui_extras::{Column, TableBuilder};
+
+use crate::{
+    file::{
+        ByteInterval, ColumnFooterSize, FILE_METADATA, FooterComponent, FooterSize, ReadRequest,
+    },
+    views::layout::{
+        components::{Treemap, TreemapNode},
+        thrift_renderer::ThriftRenderer,
+    },
+};
+
+use super::{SUBHEADER_TABLE_TEXT_SIZE, ui_helpers::UiHelpers};
+
+const TABLE_MAX_HEIGHT: f32 = 300.;
+const TABLE_ROW_HEIGHT: f32 = 18.;
+/// Columns shown in the treemap for each kind of column metadata, the rest are merged
+const MAX_TREEMAP_COLUMNS: usize = 20;
+
+/// A table cell, sorted by its key if it has one and by its text otherwise.
+struct SizeCell {
+    text: String,
+    key: Option<f64>,
+}
+
+impl SizeCell {
+    fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            key: None,
+        }
+    }
+
+    fn number(number: usize) -> Self {
+        Self {
+            text: number.to_string(),
+            key: Some(number as f64),
+        }
+    }
+
+    fn share(share: f64) -> Self {
+        Self {
+            text: format!("{:.1}%", 100. * share),
+            key: Some(share),
+        }
+    }
+}
+
+/// The column a table is sorted by, kept in temp data.
+#[derive(Clone, Copy)]
+struct TableSort {
+    column: usize,
+    descending: bool,
+}
+
+/// Where the bytes of the footer go, as a treemap and tables of its components, columns, row
+/// groups and key value metadata, followed by suggestions to make it smaller.
+pub struct FooterSizeRenderer;
+impl FooterSizeRenderer {
+    /// `id` is the one of the encoded footer, whose bytes and trace are shared.
+    pub fn render(
+        ui: &mut Ui,
+        id: Id,
+        footer_interval: ByteInterval,
+        reader_tx: &Sender<ReadRequest>,
+    ) {
+        let Some((_, trace)) =
+            ThriftRenderer::read_encoded(ui, id, footer_interval, &FILE_METADATA, reader_tx)
+        else {
+            UiHelpers::render_subheader_value(ui, "Reading...");
+            return;
+        };
+        let size_id = id.with("footer_size");
+        let size = match ui.data(|d| d.get_temp::<Arc<FooterSize>>(size_id)) {
+            Some(size) => size,
+            None => {
+                let size = Arc::new(FooterSize::new(&trace));
+                ui.data_mut(|d| d.insert_temp(size_id, size.clone()));
+                size
+            }
+        };
+
+        UiHelpers::render_subheader_labeled_value(ui, "Total", format!("{} bytes", size.total));
+        if let Some(error) = &trace.error {
+            UiHelpers::render_subheader_labeled_value(
+                ui,
+                "Decoding Stopped",
+                format!("{error}, the breakdown is partial"),
+            );
+        }
+        ui.add_space(5.);
+        Treemap::show(ui, &Self::treemap_nodes(&size));
+        ui.add_space(5.);
+
+        UiHelpers::render_subheader_collapsible(ui, "Components", |ui| {
+            let rows = size
+                .components
+                .iter()
+                .map(|(component, bytes)| {
+                    vec![
+                        SizeCell::text(component.to_string()),
+                        SizeCell::number(*bytes),
+                        SizeCell::share(size.share(*bytes)),
+                    ]
+                })
+                .collect();
+            Self::render_table(
+                ui,
+                id.with("components"),
+                &["Component", "Bytes", "Share"],
+                rows,
+            );
+        });
+        UiHelpers::render_subheader_collapsible(ui, "Columns", |ui| {
+            let rows = size
+                .columns
+                .iter()
+                .map(|column| {
+                    vec![
+                        SizeCell::text(&column.path),
+                        SizeCell::number(column.num_chunks),
+                        SizeCell::number(column.total()),
+                        SizeCell::share(size.share(column.total())),
+                        SizeCell::number(column.metadata),
+                        SizeCell::number(column.statistics),
+                        SizeCell::number(column.encoding_stats),
+                        SizeCell::number(column.size_statistics),
+                    ]
+                })
+                .collect();
+            Self::render_table(
+                ui,
+                id.with("columns"),
+                &[
+                    "Column",
+                    "Chunks",
+                    "Total",
+                    "Share",
+                    "Metadata",
+                    "Statistics",
+                    "Encoding Stats",
+                    "Size Statistics",
+                ],
+                rows,
+            );
+        });
+        UiHelpers::render_subheader_collapsible(ui, "Row Groups", |ui| {
+            let rows = size
+                .row_groups
+                .iter()
+                .enumerate()
+                .map(|(idx, bytes)| {
+                    vec![
+                        SizeCell::number(idx),
+                        SizeCell::number(*bytes),
+                        SizeCell::share(size.share(*bytes)),
+                    ]
+                })
+                .collect();
+            Self::render_table(
+                ui,
+                id.with("row_groups"),
+                &["Row Group", "Bytes", "Share"],
+                rows,
+            );
+        });
+        UiHelpers::render_subheader_collapsible(ui, "Key Value Metadata", |ui| {
+            let rows = size
+                .key_values
+                .iter()
+                .map(|(key, bytes)| {
+                    vec![
+                        SizeCell::text(key),
+                        SizeCell::number(*bytes),
+                        SizeCell::share(size.share(*bytes)),
+                    ]
+                })
+                .collect();
+            Self::render_table(ui, id.with("key_values"), &["Key", "Bytes", "Share"], rows);
+        });
+
+        ui.add_space(5.);
+        if size.suggestions.is_empty() {
+            UiHelpers::render_subheader_value(ui, "No component stands out in the footer's size");
+        }
+        for suggestion in &size.suggestions {
+            Label::new(
+                RichText::new(format!("• {suggestion}"))
+                    .monospace()
+                    .size(SUBHEADER_TABLE_TEXT_SIZE)
+                    .color(Color32::DARK_RED),
+            )
+            .ui(ui);
+        }
+    }
+
+    /// The components, with the ones made of column chunks split by column and the key value
+    /// metadata split by key.
+    fn treemap_nodes(size: &FooterSize) -> Vec<TreemapNode> {
+        let by_column = |bytes: fn(&ColumnFooterSize) -> usize| {
+            let mut columns = size
+                .columns
+                .iter()
+                .map(|column| TreemapNode {
+                    label: column.path.clone(),
+                    value: bytes(column) as u64,
+                    children: vec![],
+                })
+                .collect::<Vec<_>>();
+            columns.sort_by_key(|node| std::cmp::Reverse(node.value));
+            if columns.len() > MAX_TREEMAP_COLUMNS {
+                let rest = columns.split_off(MAX_TREEMAP_COLUMNS);
+                columns.push(TreemapNode {
+                    label: format!("{} other columns", rest.len()),
+                    value: rest.iter().map(|node| node.value).sum(),
+                    children: vec![],
+                });
+            }
+            columns
+        };
+
+        size.components
+            .iter()
+            .filter(|(_, bytes)| *bytes > 0)
+            .map(|(component, bytes)| {
+                let children = match component {
+                    FooterComponent::ColumnMetadata => by_column(|c| c.metadata),
+                    FooterComponent::Statistics => by_column(|c| c.statistics),
+                    FooterComponent::EncodingStats => by_column(|c| c.encoding_stats),
+                    FooterComponent::SizeStatistics => by_column(|c| c.size_statistics),
+                    FooterComponent::KeyValueMetadata => size
+                        .key_values
+                        .iter()
+                        .map(|(key, bytes)| TreemapNode {
+                            label: key.clone(),
+                            value: *bytes as u64,
+                            children: vec![],
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                TreemapNode {
+                    label: component.to_string(),
+                    value: *bytes as u64,
+                    children,
+                }
+            })
+            .collect()
+    }
+
+    /// A table whose rows are sorted by the column whose header was clicked last, clicking it
+    /// again reverses the order. Tables start sorted by their second column, descending.
+    fn render_table(ui: &mut Ui, id: Id, titles: &[&str], mut rows: Vec<Vec<SizeCell>>) {
+        let mut sort = ui
+            .data(|d| d.get_temp::<TableSort>(id))
+            .unwrap_or(TableSort {
+                column: 1,
+                descending: true,
+            });
+        rows.sort_by(|a, b| {
+            let (a, b) = (&a[sort.column], &b[sort.column]);
+            let ordering = match (a.key, b.key) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => a.text.cmp(&b.text),
+            };
+            if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        let text = |text: String| {
+            RichText::new(text)
+                .monospace()
+                .size(SUBHEADER_TABLE_TEXT_SIZE)
+        };
+        TableBuilder::new(ui)
+            .id_salt(id)
+            .striped(true)
+            .resizable(true)
+            .max_scroll_height(TABLE_MAX_HEIGHT)
+            .columns(Column::auto(), titles.len())
+            .header(TABLE_ROW_HEIGHT, |mut header| {
+                for (idx, title) in titles.iter().enumerate() {
+                    header.col(|ui| {
+                        let arrow = match (sort.column == idx, sort.descending) {
+                            (false, _) => "",
+                            (true, true) => " ⏷",
+                            (true, false) => " ⏶",
+                        };
+                        let response = Label::new(text(format!("{title}{arrow}")).strong())
+                            .sense(Sense::click())
+                            .selectable(false)
+                            .ui(ui);
+                        if response.clicked() {
+                            sort = TableSort {
+                                column: idx,
+                                descending: sort.column != idx || !sort.descending,
+                            };
+                        }
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(TABLE_ROW_HEIGHT, rows.len(), |mut row| {
+                    for cell in &rows[row.index()] {
+                        row.col(|ui| {
+                            Label::new(text(cell.text.clone())).ui(ui);
+                        });
+                    }
+                });
+            });
+        ui.data_mut(|d| d.insert_temp(id, sort));
+    }
+}
@@ -1,8 +1,15 @@
-use egui::{Label, RichText, Ui, Widget};
+use egui::{Align, Grid, Label, Response, RichText, ScrollArea, Stroke, Ui, Widget};
 
-use crate::views::layout::components::{CollapsibleSection, CollapsibleSectionIcon, LabeledValue};
+use crate::{
+    file::{Field, format_bytes, format_plain_value},
+    views::layout::{
+        FOCUS_HIGHLIGHT_COLOR,
+        components::{CollapsibleSection, CollapsibleSectionIcon, LabeledValue},
+        navigation::{Navigator, SectionHighlight},
+    },
+};
 
-use super::{SUBHEADER_LABEL_SIZE, SUBHEADER_VALUE_SIZE};
+use super::{SUBHEADER_LABEL_SIZE, SUBHEADER_TABLE_TEXT_SIZE, SUBHEADER_VALUE_SIZE};
 
 pub struct UiHelpers;
 impl UiHelpers {
@@ -32,18 +39,143 @@ impl UiHelpers {
         );
     }
 
+    /// Render a plain-encoded value decoded using the column's type, followed by its raw bytes.
+    pub fn render_subheader_plain_value(
+        ui: &mut Ui,
+        label: impl AsRef<str>,
+        value: Option<&[u8]>,
+        column: Option<&Field>,
+    ) {
+        let Some(value) = value else {
+            Self::render_subheader_labeled_value(ui, label, "N/A");
+            return;
+        };
+        match column.and_then(|column| format_plain_value(column, value)) {
+            Some(decoded) => LabeledValue::show_with_raw(
+                ui,
+                RichText::new(label.as_ref())
+                    .monospace()
+                    .size(SUBHEADER_LABEL_SIZE),
+                RichText::new(decoded)
+                    .monospace()
+                    .size(SUBHEADER_VALUE_SIZE),
+                RichText::new(format_bytes(value))
+                    .monospace()
+                    .size(SUBHEADER_VALUE_SIZE),
+            ),
+            None => Self::render_subheader_labeled_value(ui, label, format_bytes(value)),
+        }
+    }
+
+    /// Render rows of values in a striped grid under a bold header row.
+    pub fn render_subheader_table(
+        ui: &mut Ui,
+        id: impl std::hash::Hash,
+        header: &[&str],
+        rows: impl IntoIterator<Item = Vec<String>>,
+    ) {
+        ScrollArea::horizontal().id_salt(&id).show(ui, |ui| {
+            Grid::new(id)
+                .num_columns(header.len())
+                .spacing([10., 5.])
+                .striped(true)
+                .show(ui, |ui| {
+                    for column in header {
+                        Label::new(
+                            RichText::new(*column)
+                                .monospace()
+                                .size(SUBHEADER_TABLE_TEXT_SIZE)
+                                .strong(),
+                        )
+                        .ui(ui);
+                    }
+                    ui.end_row();
+                    for row in rows {
+                        for value in row {
+                            Label::new(
+                                RichText::new(value)
+                                    .monospace()
+                                    .size(SUBHEADER_TABLE_TEXT_SIZE),
+                            )
+                            .ui(ui);
+                        }
+                        ui.end_row();
+                    }
+                });
+            ui.add_space(5.);
+        });
+    }
+
+    /// Render an offset as a link to the section of the data that contains it.
+    pub fn render_subheader_offset_link(
+        ui: &mut Ui,
+        label: impl AsRef<str>,
+        offset: Option<i64>,
+        navigator: &mut Navigator,
+    ) {
+        match offset.and_then(|v| u64::try_from(v).ok()) {
+            Some(offset) => {
+                let clicked = LabeledValue::show_link(
+                    ui,
+                    RichText::new(label.as_ref())
+                        .monospace()
+                        .size(SUBHEADER_LABEL_SIZE),
+                    RichText::new(offset.to_string())
+                        .monospace()
+                        .size(SUBHEADER_VALUE_SIZE),
+                );
+                if clicked {
+                    navigator.request_offset(ui, offset);
+                }
+            }
+            None => Self::render_subheader_labeled_value(
+                ui,
+                label,
+                offset.map(|v| v.to_string()).unwrap_or(String::from("N/A")),
+            ),
+        }
+    }
+
     pub fn render_subheader_collapsible(
         ui: &mut Ui,
         header: impl AsRef<str>,
         content: impl FnOnce(&mut Ui),
     ) {
-        CollapsibleSection::new(
+        Self::render_subheader_highlighted_collapsible(
+            ui,
+            header,
+            SectionHighlight::default(),
+            content,
+        );
+    }
+
+    /// Collapsible that can be revealed and outlined by the navigator.
+    pub fn render_subheader_highlighted_collapsible(
+        ui: &mut Ui,
+        header: impl AsRef<str>,
+        highlight: SectionHighlight,
+        content: impl FnOnce(&mut Ui),
+    ) {
+        let response: Response = CollapsibleSection::new(
             RichText::new(header.as_ref())
                 .monospace()
                 .size(SUBHEADER_LABEL_SIZE),
             CollapsibleSectionIcon::Default,
             true,
         )
+        .open(highlight.should_reveal)
         .show(ui, content);
+
+        if highlight.is_focused {
+            ui.painter().rect_stroke(
+                response.rect.expand(1.),
+                2.,
+                Stroke::new(2., FOCUS_HIGHLIGHT_COLOR),
+                egui::StrokeKind::Outside,
+            );
+        }
+        if highlight.should_scroll {
+            response.scroll_to_me(Some(Align::Min));
+        }
     }
 }
@@ -0,0 +1,194 @@
+use std::collections::BTreeSet;
+
+use egui::{Checkbox, Color32, DragValue, Grid, RichText, ScrollArea, Sense, Ui, vec2};
+
+use crate::{
+    ParkhayFile,
+    file::{Coalescing, IoPlan},
+};
+
+use super::{components::FileMap, navigation::Navigator};
+
+const IO_PLAN_TEXT_SIZE: f32 = 13.;
+const IO_PLAN_REQUESTS_MAX_HEIGHT: f32 = 240.;
+const COLUMN_CHUNK_COLOR: Color32 = Color32::from_rgb(70, 130, 180);
+const COALESCED_GAP_COLOR: Color32 = Color32::from_rgb(240, 190, 120);
+
+/// Plans the reads needed to fetch the columns selected in the schema tree.
+#[derive(Default)]
+pub struct IoPlanner {
+    /// Leaf indexes of the selected columns
+    projection: BTreeSet<usize>,
+    is_coalescing: bool,
+    coalescing: Coalescing,
+    /// The plan and the inputs it was computed from
+    plan: Option<(BTreeSet<usize>, Option<Coalescing>, IoPlan)>,
+}
+
+impl IoPlanner {
+    pub fn projection_mut(&mut self) -> &mut BTreeSet<usize> {
+        &mut self.projection
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.projection.is_empty()
+    }
+
+    pub fn render(&mut self, ui: &mut Ui, parkhay_file: &ParkhayFile, navigator: &mut Navigator) {
+        let Some(footer) = &parkhay_file.footer else {
+            return;
+        };
+        let label = |ui: &mut Ui, text: String| {
+            ui.label(RichText::new(text).monospace().size(IO_PLAN_TEXT_SIZE));
+        };
+
+        ui.horizontal(|ui| {
+            ui.add(Checkbox::new(
+                &mut self.is_coalescing,
+                RichText::new("Coalesce")
+                    .monospace()
+                    .size(IO_PLAN_TEXT_SIZE),
+            ))
+            .on_hover_text("Merge nearby column chunks into fewer requests");
+            if ui
+                .button(
+                    RichText::new("Clear Selection")
+                        .monospace()
+                        .size(IO_PLAN_TEXT_SIZE),
+                )
+                .clicked()
+            {
+                self.projection.clear();
+            }
+        });
+        if self.is_coalescing {
+            Grid::new("I/O Plan Coalescing")
+                .num_columns(2)
+                .spacing([10., 5.])
+                .show(ui, |ui| {
+                    label(ui, String::from("Max Gap"));
+                    ui.add(
+                        DragValue::new(&mut self.coalescing.max_gap)
+                            .speed(1024)
+                            .suffix(" bytes"),
+                    );
+                    ui.end_row();
+                    label(ui, String::from("Max Request Size"));
+                    ui.add(
+                        DragValue::new(&mut self.coalescing.max_request_size)
+                            .speed(1024)
+                            .range(1..=u64::MAX)
+                            .suffix(" bytes"),
+                    );
+                    ui.end_row();
+                });
+        }
+        ui.separator();
+
+        // Only plan again when the selection or the coalescing options change
+        let coalescing = self.is_coalescing.then_some(self.coalescing);
+        let is_stale = !matches!(
+            &self.plan,
+            Some((projection, plan_coalescing, _))
+                if *projection == self.projection && *plan_coalescing == coalescing
+        );
+        if is_stale {
+            let columns = self.projection.iter().copied().collect::<Vec<_>>();
+            let plan = IoPlan::new(footer, &columns, coalescing, parkhay_file.file_size);
+            self.plan = Some((self.projection.clone(), coalescing, plan));
+        }
+        let Some((_, _, plan)) = &self.plan else {
+            return;
+        };
+
+        label(ui, format!("Columns: {}", plan.columns.join(", ")));
+        label(
+            ui,
+            format!(
+                "Column chunks: {} ({} bytes)",
+                plan.column_chunks.len(),
+                plan.needed_bytes()
+            ),
+        );
+        label(ui, format!("Requests: {}", plan.requests.len()));
+        label(ui, format!("Bytes read: {}", plan.fetched_bytes()));
+        label(
+            ui,
+            format!(
+                "Read amplification: {:.2}x the selected column chunks",
+                plan.read_amplification()
+            ),
+        );
+        label(
+            ui,
+            format!(
+                "File fraction: {:.1}% of {} bytes",
+                plan.file_fraction() * 100.,
+                plan.file_size
+            ),
+        );
+        ui.separator();
+
+        // Requests are painted first so that only the gaps merged into them remain visible
+        let ranges = plan
+            .requests
+            .iter()
+            .map(|request| (*request, COALESCED_GAP_COLOR))
+            .chain(
+                plan.column_chunks
+                    .iter()
+                    .map(|column_chunk| (*column_chunk, COLUMN_CHUNK_COLOR)),
+            )
+            .collect::<Vec<_>>();
+        if let Some(offset) = FileMap::show(ui, parkhay_file.file_size, &ranges) {
+            navigator.request_offset(ui, offset);
+        }
+        ui.horizontal(|ui| {
+            for (name, color) in [
+                ("Column Chunks", COLUMN_CHUNK_COLOR),
+                ("Coalesced Gaps", COALESCED_GAP_COLOR),
+            ] {
+                let (swatch, _) = ui.allocate_exact_size(vec2(10., 10.), Sense::hover());
+                ui.painter().rect_filled(swatch, 1., color);
+                ui.label(RichText::new(name).monospace().small());
+            }
+        });
+        ui.separator();
+
+        ScrollArea::vertical()
+            .id_salt("I/O Plan Requests")
+            .max_height(IO_PLAN_REQUESTS_MAX_HEIGHT)
+            .show(ui, |ui| {
+                Grid::new("I/O Plan Requests")
+                    .num_columns(3)
+                    .spacing([10., 5.])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for title in ["Request", "Byte Range", "Bytes"] {
+                            ui.label(
+                                RichText::new(title)
+                                    .monospace()
+                                    .size(IO_PLAN_TEXT_SIZE)
+                                    .strong(),
+                            );
+                        }
+                        ui.end_row();
+                        for (idx, (start, end)) in plan.requests.iter().enumerate() {
+                            label(ui, idx.to_string());
+                            if ui
+                                .link(
+                                    RichText::new(format!("{start}..={end}"))
+                                        .monospace()
+                                        .size(IO_PLAN_TEXT_SIZE),
+                                )
+                                .clicked()
+                            {
+                                navigator.request_offset(ui, *start);
+                            }
+                            label(ui, (end - start + 1).to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
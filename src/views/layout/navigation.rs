@@ -0,0 +1,522 @@
+use std::collections::HashSet;
+
+use egui::{Checkbox, Color32, Key, RichText, TextEdit, Ui};
+use search::SearchQuery;
+
+use crate::{
+    ParkhayFile,
+    file::{ByteInterval, ColumnChunkLocation, ParkhayDataSection, SectionMap},
+    views::RowsRequest,
+};
+
+mod search;
+
+const SEARCH_TEXT_SIZE: f32 = 13.;
+
+/// Byte intervals of a section and all of its ancestors, starting from the outermost section.
+pub type SectionPath = Vec<ByteInterval>;
+
+/// Parts of the layout that are not sections of the data tree.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LayoutRegion {
+    StartMagic,
+    Footer,
+    FooterLength,
+    EndMagic,
+}
+
+/// Column chunk (or whole row group) metadata within the footer.
+#[derive(Clone, Copy, PartialEq)]
+pub struct FooterLocation {
+    pub row_group: usize,
+    pub column: Option<usize>,
+}
+
+impl From<&ColumnChunkLocation> for FooterLocation {
+    fn from(location: &ColumnChunkLocation) -> Self {
+        Self {
+            row_group: location.row_group as usize,
+            column: Some(location.column as usize),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum FocusTarget {
+    Section(SectionPath),
+    Layout(LayoutRegion),
+    Footer(FooterLocation),
+    FooterKeyValueMetadata,
+}
+
+/// A section matching the search, or the file's key value metadata in the footer.
+#[derive(Clone)]
+enum SearchMatch {
+    Section(SectionPath),
+    FooterKeyValueMetadata,
+}
+
+/// How a section should be drawn given the current search and focus.
+#[derive(Clone, Copy, Default)]
+pub struct SectionHighlight {
+    pub is_match: bool,
+    pub is_focused: bool,
+    /// The section is the focus target, or contains it, and should be expanded
+    pub should_reveal: bool,
+    pub should_scroll: bool,
+    /// Absolute offset of the byte that should be highlighted in the section's preview
+    pub focused_byte: Option<u64>,
+    /// A reader evaluating the simulated predicate wouldn't read any of the section's bytes
+    pub is_skipped: bool,
+}
+
+struct Focus {
+    target: FocusTarget,
+    byte: Option<u64>,
+    is_pending: bool,
+}
+
+/// Tracks the search query, its matches and which section is currently focused.
+#[derive(Default)]
+pub struct Navigator {
+    query: String,
+    searched_query: Option<String>,
+    is_filtering: bool,
+    matches: Vec<SearchMatch>,
+    /// Paths of the matching sections
+    matched_paths: HashSet<SectionPath>,
+    /// Paths of the matching sections and of all of their ancestors
+    revealed_paths: HashSet<SectionPath>,
+    current_match: Option<usize>,
+    focus: Option<Focus>,
+    offset_query: String,
+    offset_error: Option<String>,
+    requested_offset: Option<u64>,
+    requested_rows: Option<RowsRequest>,
+    requested_thrift: Option<ByteInterval>,
+}
+
+impl Navigator {
+    pub fn render_toolbar(&mut self, ui: &mut Ui, parkhay_file: &ParkhayFile) {
+        // Offsets requested from links in the previous frame
+        if let Some(offset) = self.requested_offset.take() {
+            self.offset_query = offset.to_string();
+            self.offset_error = self.go_to_offset(parkhay_file, offset).err();
+        }
+
+        ui.horizontal(|ui| {
+            self.render_search_bar(ui, parkhay_file);
+            ui.separator();
+            self.render_offset_bar(ui, parkhay_file);
+        });
+    }
+
+    fn render_search_bar(&mut self, ui: &mut Ui, parkhay_file: &ParkhayFile) {
+        ui.horizontal(|ui| {
+            let response = ui
+                .add(
+                    TextEdit::singleline(&mut self.query)
+                        .hint_text("Search sections")
+                        .font(egui::FontId::monospace(SEARCH_TEXT_SIZE))
+                        .desired_width(300.),
+                )
+                .on_hover_text(SearchQuery::HELP);
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if submitted {
+                // Keep the focus in the search box so that Enter can be pressed repeatedly
+                response.request_focus();
+            }
+
+            self.update_matches(parkhay_file);
+
+            let has_matches = !self.matches.is_empty();
+            let previous_clicked = ui
+                .add_enabled(
+                    has_matches,
+                    egui::Button::new(RichText::new("◀").size(SEARCH_TEXT_SIZE)),
+                )
+                .on_hover_text("Previous match")
+                .clicked();
+            let next_clicked = ui
+                .add_enabled(
+                    has_matches,
+                    egui::Button::new(RichText::new("▶").size(SEARCH_TEXT_SIZE)),
+                )
+                .on_hover_text("Next match")
+                .clicked();
+
+            if previous_clicked {
+                self.step_match(-1);
+            } else if next_clicked || (submitted && has_matches) {
+                self.step_match(1);
+            }
+
+            ui.add(Checkbox::new(
+                &mut self.is_filtering,
+                RichText::new("Filter").monospace().size(SEARCH_TEXT_SIZE),
+            ));
+
+            if self
+                .searched_query
+                .as_deref()
+                .is_some_and(|q| !q.is_empty())
+            {
+                let position = self
+                    .current_match
+                    .map(|idx| (idx + 1).to_string())
+                    .unwrap_or(String::from("-"));
+                ui.label(
+                    RichText::new(format!("{position}/{} matches", self.matches.len()))
+                        .monospace()
+                        .size(SEARCH_TEXT_SIZE),
+                );
+            }
+        });
+    }
+
+    fn render_offset_bar(&mut self, ui: &mut Ui, parkhay_file: &ParkhayFile) {
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                TextEdit::singleline(&mut self.offset_query)
+                    .hint_text("Go to offset")
+                    .font(egui::FontId::monospace(SEARCH_TEXT_SIZE))
+                    .desired_width(140.),
+            );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            let go_clicked = ui
+                .button(RichText::new("Go").monospace().size(SEARCH_TEXT_SIZE))
+                .clicked();
+            if response.changed() {
+                self.offset_error = None;
+            }
+
+            if submitted || go_clicked {
+                self.offset_error = match Self::parse_offset(&self.offset_query) {
+                    Some(offset) => self.go_to_offset(parkhay_file, offset).err(),
+                    None => Some(String::from("Expected a decimal or 0x-prefixed offset")),
+                };
+            }
+
+            if let Some(error) = &self.offset_error {
+                ui.label(
+                    RichText::new(error)
+                        .monospace()
+                        .size(SEARCH_TEXT_SIZE)
+                        .color(Color32::DARK_RED),
+                );
+            }
+        });
+    }
+
+    /// Navigate to the section containing the offset on the next frame.
+    pub fn request_offset(&mut self, ui: &Ui, offset: u64) {
+        self.requested_offset = Some(offset);
+        ui.ctx().request_repaint();
+    }
+
+    /// Decode the rows in the rows view, once the layout has been drawn.
+    pub fn request_rows(&mut self, ui: &Ui, request: RowsRequest) {
+        self.requested_rows = Some(request);
+        ui.ctx().request_repaint();
+    }
+
+    pub fn take_rows_request(&mut self) -> Option<RowsRequest> {
+        self.requested_rows.take()
+    }
+
+    /// Decode the bytes as a thrift struct in the side panel, on the next frame.
+    pub fn request_thrift(&mut self, ui: &Ui, byte_interval: ByteInterval) {
+        self.requested_thrift = Some(byte_interval);
+        ui.ctx().request_repaint();
+    }
+
+    pub fn take_thrift_request(&mut self) -> Option<ByteInterval> {
+        self.requested_thrift.take()
+    }
+
+    /// Reveal the metadata of a row group or column chunk in the footer.
+    pub fn focus_footer(&mut self, location: FooterLocation) {
+        self.focus = Some(Focus {
+            target: FocusTarget::Footer(location),
+            byte: None,
+            is_pending: true,
+        });
+    }
+
+    pub fn parse_offset(text: &str) -> Option<u64> {
+        let text = text.trim().replace('_', "");
+        match text.strip_prefix("0x").or(text.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => text.parse().ok(),
+        }
+    }
+
+    /// Focus the innermost section containing the offset, or the layout region if the offset is outside of the data.
+    pub fn go_to_offset(&mut self, parkhay_file: &ParkhayFile, offset: u64) -> Result<(), String> {
+        if offset >= parkhay_file.file_size {
+            return Err(format!(
+                "Offset {offset} is past the end of the file ({} bytes)",
+                parkhay_file.file_size
+            ));
+        }
+
+        let target = match Self::find_section_path(&parkhay_file.data, offset) {
+            Some(path) => FocusTarget::Section(path),
+            None => FocusTarget::Layout(
+                Self::find_layout_region(parkhay_file, offset)
+                    .ok_or(format!("Offset {offset} is not referenced by any section"))?,
+            ),
+        };
+        self.focus = Some(Focus {
+            target,
+            byte: Some(offset),
+            is_pending: true,
+        });
+        Ok(())
+    }
+
+    /// Path to the innermost section containing the offset.
+    /// Page and bloom filter headers precede the interval of their section, so offsets within them
+    /// resolve to the section that follows, which highlights them in its encoded header.
+    pub fn find_section_path(data: &ParkhayDataSection, offset: u64) -> Option<SectionPath> {
+        let ParkhayDataSection::Root(sections) = data else {
+            unreachable!()
+        };
+        let mut path = SectionPath::new();
+        if Self::find_section_path_helper(sections, offset, &mut path) {
+            return Some(path);
+        }
+
+        // Otherwise the offset may be in the header of a bloom filter, which precedes its bitset
+        sections
+            .iter()
+            .find_map(|(byte_interval, section)| match section {
+                ParkhayDataSection::BloomFilter(_, _, _, (header_start, header_end), _, _) => {
+                    (*header_start..=*header_end)
+                        .contains(&offset)
+                        .then(|| vec![*byte_interval])
+                }
+                _ => None,
+            })
+    }
+
+    fn find_section_path_helper(
+        sections: &SectionMap,
+        offset: u64,
+        path: &mut SectionPath,
+    ) -> bool {
+        let containing = sections
+            .iter()
+            .find(|((start, end), _)| (*start..=*end).contains(&offset));
+        let Some((byte_interval, section)) = containing else {
+            return false;
+        };
+
+        path.push(*byte_interval);
+        match section {
+            ParkhayDataSection::ColumnChunk(_, subsections, _)
+            | ParkhayDataSection::RecoveredColumnChunk(_, subsections) => {
+                let is_in_page = Self::find_section_path_helper(subsections, offset, path);
+                // Otherwise the offset is in the header of the next page
+                let next_page = subsections.keys().find(|(start, _)| *start > offset);
+                if let (false, Some(page_interval)) = (is_in_page, next_page) {
+                    path.push(*page_interval);
+                }
+            }
+            ParkhayDataSection::RowGroup(_, subsections)
+            | ParkhayDataSection::Page(_, _, _, subsections) => {
+                Self::find_section_path_helper(subsections, offset, path);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn find_layout_region(parkhay_file: &ParkhayFile, offset: u64) -> Option<LayoutRegion> {
+        const MAGIC_LENGTH: u64 = 4;
+        const FOOTER_LENGTH_LENGTH: u64 = 4;
+
+        if offset < MAGIC_LENGTH {
+            return Some(LayoutRegion::StartMagic);
+        }
+        parkhay_file.footer.as_ref()?;
+
+        let end_magic_start = parkhay_file.file_size - MAGIC_LENGTH;
+        let footer_length_start = end_magic_start - FOOTER_LENGTH_LENGTH;
+        let footer_start = footer_length_start - parkhay_file.footer_length as u64;
+        if offset >= end_magic_start {
+            Some(LayoutRegion::EndMagic)
+        } else if offset >= footer_length_start {
+            Some(LayoutRegion::FooterLength)
+        } else if offset >= footer_start {
+            Some(LayoutRegion::Footer)
+        } else {
+            None
+        }
+    }
+
+    fn update_matches(&mut self, parkhay_file: &ParkhayFile) {
+        if self.searched_query.as_ref() == Some(&self.query) {
+            return;
+        }
+
+        let query = SearchQuery::parse(&self.query);
+        self.matches.clear();
+        if !query.is_empty() {
+            let footer = parkhay_file.footer.as_ref();
+            self.matches.extend(
+                query
+                    .find_matches(&parkhay_file.data, footer)
+                    .into_iter()
+                    .map(SearchMatch::Section),
+            );
+            // The footer is rendered after the data
+            if let Some(footer) = footer
+                && query.matches_footer(footer, &parkhay_file.footer_interval())
+            {
+                self.matches.push(SearchMatch::FooterKeyValueMetadata);
+            }
+        }
+
+        self.matched_paths.clear();
+        self.revealed_paths.clear();
+        for search_match in &self.matches {
+            if let SearchMatch::Section(path) = search_match {
+                self.matched_paths.insert(path.clone());
+                for len in 1..=path.len() {
+                    self.revealed_paths.insert(path[..len].to_vec());
+                }
+            }
+        }
+        self.current_match = None;
+        self.searched_query = Some(self.query.clone());
+    }
+
+    fn step_match(&mut self, step: isize) {
+        let count = self.matches.len() as isize;
+        let next = match self.current_match {
+            Some(idx) => (idx as isize + step).rem_euclid(count),
+            None if step < 0 => count - 1,
+            None => 0,
+        } as usize;
+        self.current_match = Some(next);
+        let target = match &self.matches[next] {
+            SearchMatch::Section(path) => FocusTarget::Section(path.clone()),
+            SearchMatch::FooterKeyValueMetadata => FocusTarget::FooterKeyValueMetadata,
+        };
+        self.focus = Some(Focus {
+            target,
+            byte: None,
+            is_pending: true,
+        });
+    }
+
+    pub fn highlight(&self, path: &[ByteInterval]) -> SectionHighlight {
+        let mut highlight = SectionHighlight {
+            is_match: self.matched_paths.contains(path),
+            ..Default::default()
+        };
+        if let Some(Focus {
+            target: FocusTarget::Section(focus_path),
+            byte,
+            is_pending,
+        }) = &self.focus
+        {
+            highlight.is_focused = focus_path == path;
+            highlight.should_reveal = *is_pending && focus_path.starts_with(path);
+            highlight.should_scroll = *is_pending && highlight.is_focused;
+            highlight.focused_byte = byte.filter(|_| highlight.is_focused);
+        }
+        highlight
+    }
+
+    /// Highlight for a row group or column chunk in the footer.
+    /// Row groups are revealed when any of their column chunks is the focus target.
+    pub fn footer_highlight(&self, location: FooterLocation) -> SectionHighlight {
+        match &self.focus {
+            Some(Focus {
+                target: FocusTarget::Footer(target),
+                is_pending,
+                ..
+            }) => {
+                let is_focused = *target == location;
+                let contains_target = target.row_group == location.row_group
+                    && (location.column.is_none() || location.column == target.column);
+                SectionHighlight {
+                    is_focused,
+                    should_reveal: *is_pending && contains_target,
+                    should_scroll: *is_pending && is_focused,
+                    ..Default::default()
+                }
+            }
+            _ => SectionHighlight::default(),
+        }
+    }
+
+    /// Highlight for the file's key value metadata in the footer.
+    pub fn footer_key_value_metadata_highlight(&self) -> SectionHighlight {
+        let is_match = self
+            .matches
+            .iter()
+            .any(|m| matches!(m, SearchMatch::FooterKeyValueMetadata));
+        match &self.focus {
+            Some(focus) if focus.target == FocusTarget::FooterKeyValueMetadata => {
+                SectionHighlight {
+                    is_match,
+                    is_focused: true,
+                    should_reveal: focus.is_pending,
+                    should_scroll: focus.is_pending,
+                    ..Default::default()
+                }
+            }
+            _ => SectionHighlight {
+                is_match,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Whether the footer's row group metadata contains the focus target and should be revealed.
+    pub fn should_reveal_footer(&self) -> bool {
+        matches!(
+            &self.focus,
+            Some(Focus {
+                target: FocusTarget::Footer(_),
+                is_pending: true,
+                ..
+            })
+        )
+    }
+
+    pub fn layout_highlight(&self, region: LayoutRegion) -> SectionHighlight {
+        match &self.focus {
+            Some(focus) if focus.target == FocusTarget::Layout(region) => SectionHighlight {
+                is_focused: true,
+                should_reveal: focus.is_pending,
+                should_scroll: focus.is_pending,
+                focused_byte: focus.byte,
+                ..Default::default()
+            },
+            _ => SectionHighlight::default(),
+        }
+    }
+
+    /// Called once the focused section has been scrolled into view.
+    pub fn complete_focus(&mut self) {
+        if let Some(focus) = &mut self.focus {
+            focus.is_pending = false;
+        }
+    }
+
+    /// Whether the section should be rendered given the current filter.
+    /// Matches, their ancestors and their descendants stay visible.
+    pub fn is_visible(&self, path: &[ByteInterval]) -> bool {
+        if !self.is_filtering || self.searched_query.as_deref().is_none_or(str::is_empty) {
+            return true;
+        }
+
+        // Descendants of a match have it as a prefix
+        self.revealed_paths.contains(path)
+            || (1..path.len()).any(|len| self.matched_paths.contains(&path[..len]))
+    }
+}
@@ -0,0 +1,303 @@
+use crate::file::{
+    ByteInterval, ColumnChunkLocation, Field, ParkhayDataSection, ParkhayFooter, SectionMap,
+    format_bytes, format_plain_value,
+};
+
+use super::SectionPath;
+
+#[derive(Clone, Copy, PartialEq)]
+enum SearchField {
+    RowGroup,
+    Column,
+    Type,
+    Encoding,
+    Codec,
+    Offset,
+    KeyValue,
+    Statistics,
+}
+
+impl SearchField {
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "rg" => Some(Self::RowGroup),
+            "col" => Some(Self::Column),
+            "type" => Some(Self::Type),
+            "enc" => Some(Self::Encoding),
+            "codec" => Some(Self::Codec),
+            "offset" => Some(Self::Offset),
+            "kv" => Some(Self::KeyValue),
+            "stat" => Some(Self::Statistics),
+            _ => None,
+        }
+    }
+}
+
+struct SearchTerm {
+    field: Option<SearchField>,
+    text: String,
+}
+
+/// Whitespace-separated terms that must all match the same section.
+/// Each term can be restricted to one field with a `field:` prefix, eg. `codec:snappy`.
+pub struct SearchQuery(Vec<SearchTerm>);
+
+impl SearchQuery {
+    pub const HELP: &str = "Terms can be restricted with a prefix: rg:, col:, type:, enc:, codec:, offset:, kv:, stat:";
+
+    pub fn parse(query: &str) -> Self {
+        Self(
+            query
+                .split_whitespace()
+                .map(|term| {
+                    let lowercase = term.to_lowercase();
+                    match lowercase.split_once(':') {
+                        Some((prefix, text)) if SearchField::from_prefix(prefix).is_some() => {
+                            SearchTerm {
+                                field: SearchField::from_prefix(prefix),
+                                text: text.to_string(),
+                            }
+                        }
+                        _ => SearchTerm {
+                            field: None,
+                            text: lowercase,
+                        },
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Collect the paths of all matching sections, in the order they are rendered.
+    pub fn find_matches(
+        &self,
+        data: &ParkhayDataSection,
+        footer: Option<&ParkhayFooter>,
+    ) -> Vec<SectionPath> {
+        let mut matches = vec![];
+        if let ParkhayDataSection::Root(sections) = data {
+            let leaves = footer.map(|footer| footer.leaves()).unwrap_or_default();
+            self.find_matches_helper(sections, &mut vec![], None, leaves, footer, &mut matches);
+        }
+        matches
+    }
+
+    /// Whether the file's key value metadata, which is only part of the footer, matches.
+    pub fn matches_footer(&self, footer: &ParkhayFooter, footer_interval: &ByteInterval) -> bool {
+        let Some(kv_metadata) = &footer.key_value_metadata else {
+            return false;
+        };
+        let mut fields = vec![(SearchField::Type, String::from("Key Value Metadata"))];
+        push_key_value_fields(&mut fields, kv_metadata);
+        self.matches(&fields, footer_interval)
+    }
+
+    fn find_matches_helper(
+        &self,
+        sections: &SectionMap,
+        path: &mut SectionPath,
+        column: Option<&Field>,
+        leaves: &[Field],
+        footer: Option<&ParkhayFooter>,
+        matches: &mut Vec<SectionPath>,
+    ) {
+        for (byte_interval, section) in sections {
+            path.push(*byte_interval);
+
+            let fields = section_fields(section, byte_interval, column, leaves, footer);
+            if self.matches(&fields, byte_interval) {
+                matches.push(path.clone());
+            }
+
+            match section {
+                ParkhayDataSection::RowGroup(_, subsections)
+                | ParkhayDataSection::RecoveredColumnChunk(_, subsections) => {
+                    self.find_matches_helper(subsections, path, None, leaves, footer, matches)
+                }
+                ParkhayDataSection::ColumnChunk(_, subsections, schema) => self
+                    .find_matches_helper(subsections, path, Some(schema), leaves, footer, matches),
+                ParkhayDataSection::Page(_, _, _, subsections) => {
+                    self.find_matches_helper(subsections, path, column, leaves, footer, matches)
+                }
+                _ => {}
+            }
+
+            path.pop();
+        }
+    }
+
+    fn matches(&self, fields: &[(SearchField, String)], byte_interval: &ByteInterval) -> bool {
+        self.0.iter().all(|term| match term.field {
+            // Offsets match every section that contains them
+            Some(SearchField::Offset) => term
+                .text
+                .parse::<u64>()
+                .is_ok_and(|offset| (byte_interval.0..=byte_interval.1).contains(&offset)),
+            Some(SearchField::RowGroup) => fields
+                .iter()
+                .any(|(f, value)| *f == SearchField::RowGroup && *value == term.text),
+            Some(field) => fields
+                .iter()
+                .any(|(f, value)| *f == field && value.to_lowercase().contains(&term.text)),
+            None => fields
+                .iter()
+                .any(|(_, value)| value.to_lowercase().contains(&term.text)),
+        })
+    }
+}
+
+/// Searchable values of a single section, excluding those of its subsections.
+fn section_fields(
+    section: &ParkhayDataSection,
+    byte_interval: &ByteInterval,
+    column: Option<&Field>,
+    leaves: &[Field],
+    footer: Option<&ParkhayFooter>,
+) -> Vec<(SearchField, String)> {
+    let mut fields = vec![
+        (SearchField::Offset, byte_interval.0.to_string()),
+        (SearchField::Offset, byte_interval.1.to_string()),
+    ];
+
+    match section {
+        ParkhayDataSection::Root(_) => unreachable!(),
+        ParkhayDataSection::RowGroup(idx, _) => {
+            fields.push((SearchField::Type, String::from("Row Group")));
+            fields.push((SearchField::RowGroup, idx.to_string()));
+        }
+        ParkhayDataSection::ColumnChunk(location, _, schema) => {
+            fields.push((SearchField::Type, String::from("Column Chunk")));
+            fields.push((SearchField::Column, schema.name().to_string()));
+            push_location_fields(&mut fields, location);
+
+            let metadata = footer.and_then(|footer| {
+                footer.row_groups[location.row_group as usize].columns[location.column as usize]
+                    .meta_data
+                    .as_ref()
+            });
+            if let Some(metadata) = metadata {
+                if let Ok(codec) = parquet::basic::Compression::try_from(metadata.codec) {
+                    fields.push((SearchField::Codec, codec.to_string()));
+                }
+                for encoding in &metadata.encodings {
+                    if let Ok(encoding) = parquet::basic::Encoding::try_from(*encoding) {
+                        fields.push((SearchField::Encoding, encoding.to_string()));
+                    }
+                }
+                if let Some(kv_metadata) = &metadata.key_value_metadata {
+                    push_key_value_fields(&mut fields, kv_metadata);
+                }
+                if let Some(statistics) = &metadata.statistics {
+                    push_statistics_fields(&mut fields, statistics, Some(schema));
+                }
+            }
+        }
+        ParkhayDataSection::RecoveredColumnChunk(_, _) => {
+            fields.push((SearchField::Type, String::from("Column Chunk")));
+        }
+        ParkhayDataSection::Page(_, header, _, _) => {
+            let header = &header.header;
+            if let Ok(page_type) = parquet::basic::PageType::try_from(header.type_) {
+                fields.push((SearchField::Type, page_type.to_string()));
+            }
+            let mut encodings = vec![];
+            if let Some(h) = &header.data_page_header {
+                encodings.extend([
+                    h.encoding,
+                    h.definition_level_encoding,
+                    h.repetition_level_encoding,
+                ]);
+                if let Some(statistics) = &h.statistics {
+                    push_statistics_fields(&mut fields, statistics, column);
+                }
+            }
+            if let Some(h) = &header.data_page_header_v2 {
+                encodings.push(h.encoding);
+                if let Some(statistics) = &h.statistics {
+                    push_statistics_fields(&mut fields, statistics, column);
+                }
+            }
+            if let Some(h) = &header.dictionary_page_header {
+                encodings.push(h.encoding);
+            }
+            for encoding in encodings {
+                if let Ok(encoding) = parquet::basic::Encoding::try_from(encoding) {
+                    fields.push((SearchField::Encoding, encoding.to_string()));
+                }
+            }
+        }
+        ParkhayDataSection::PageSegment(segment, _) => {
+            fields.push((SearchField::Type, segment.to_string()));
+        }
+        ParkhayDataSection::ColumnIndex(location, column_index, _) => {
+            fields.push((SearchField::Type, String::from("Column Index")));
+            push_location_fields(&mut fields, location);
+            for value in column_index
+                .min_values
+                .iter()
+                .chain(column_index.max_values.iter())
+            {
+                push_value_field(&mut fields, value, leaves.get(location.column as usize));
+            }
+        }
+        ParkhayDataSection::OffsetIndex(location, _, _) => {
+            fields.push((SearchField::Type, String::from("Offset Index")));
+            push_location_fields(&mut fields, location);
+        }
+        ParkhayDataSection::BloomFilter(_, location, _, _, _, _) => {
+            fields.push((SearchField::Type, String::from("Bloom Filter")));
+            if let Some(location) = location {
+                push_location_fields(&mut fields, location);
+            }
+        }
+    }
+
+    fields
+}
+
+fn push_location_fields(fields: &mut Vec<(SearchField, String)>, location: &ColumnChunkLocation) {
+    fields.push((SearchField::RowGroup, location.row_group.to_string()));
+    fields.push((SearchField::Column, location.path.string()));
+}
+
+fn push_key_value_fields(
+    fields: &mut Vec<(SearchField, String)>,
+    kv_metadata: &[parquet::format::KeyValue],
+) {
+    for kv in kv_metadata {
+        fields.push((SearchField::KeyValue, kv.key.clone()));
+        if let Some(value) = &kv.value {
+            fields.push((SearchField::KeyValue, value.clone()));
+        }
+    }
+}
+
+fn push_statistics_fields(
+    fields: &mut Vec<(SearchField, String)>,
+    statistics: &parquet::format::Statistics,
+    column: Option<&Field>,
+) {
+    for value in [
+        &statistics.min_value,
+        &statistics.max_value,
+        &statistics.min,
+        &statistics.max,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        push_value_field(fields, value, column);
+    }
+}
+
+/// Statistics are searched by their decoded value, falling back to their raw bytes.
+fn push_value_field(fields: &mut Vec<(SearchField, String)>, value: &[u8], column: Option<&Field>) {
+    let value = column
+        .and_then(|column| format_plain_value(column, value))
+        .unwrap_or_else(|| format_bytes(value));
+    fields.push((SearchField::Statistics, value));
+}
@@ -0,0 +1,354 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use egui::{Color32, Grid, Key, RichText, ScrollArea, Sense, TextEdit, Ui, vec2};
+
+use crate::{
+    ParkhayFile,
+    file::{Predicate, PruningReport, ReadKind},
+};
+
+use super::{components::FileMap, navigation::Navigator};
+
+const PRUNING_TEXT_SIZE: f32 = 13.;
+const READ_DATA_COLOR: Color32 = Color32::from_rgb(70, 130, 180);
+const READ_INDEX_COLOR: Color32 = Color32::from_rgb(150, 110, 200);
+
+type SimulationResult = Arc<Mutex<Option<Result<PruningReport, String>>>>;
+
+/// Evaluates a predicate against the file's statistics, page indexes and bloom filters to show
+/// what a reader pushing it down could skip.
+pub struct PruningSimulator {
+    query: String,
+    project_all_columns: bool,
+    simulated_query: Option<String>,
+    report: Option<PruningReport>,
+    error: Option<String>,
+    /// The query being simulated on a worker thread, as bloom filters are read from the file
+    simulation: Option<(String, SimulationResult)>,
+}
+
+impl Default for PruningSimulator {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            project_all_columns: true,
+            simulated_query: None,
+            report: None,
+            error: None,
+            simulation: None,
+        }
+    }
+}
+
+impl PruningSimulator {
+    pub fn report(&self) -> Option<&PruningReport> {
+        self.report.as_ref()
+    }
+
+    pub fn render_toolbar(&mut self, ui: &mut Ui, parkhay_file: &Arc<ParkhayFile>) {
+        self.take_simulation();
+        ui.add_enabled_ui(parkhay_file.footer.is_some(), |ui| {
+            ui.horizontal(|ui| {
+                let response = ui
+                    .add(
+                        TextEdit::singleline(&mut self.query)
+                            .hint_text("Simulate predicate, e.g. id BETWEEN 10 AND 20")
+                            .font(egui::FontId::monospace(PRUNING_TEXT_SIZE))
+                            .desired_width(300.),
+                    )
+                    .on_hover_text(Predicate::HELP);
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+                if response.changed() {
+                    self.error = None;
+                }
+
+                let projection_changed = ui
+                    .checkbox(
+                        &mut self.project_all_columns,
+                        RichText::new("All columns")
+                            .monospace()
+                            .size(PRUNING_TEXT_SIZE),
+                    )
+                    .on_hover_text(
+                        "Read every column of the selected rows, rather than only the predicate column",
+                    )
+                    .changed();
+                let simulate_clicked = ui
+                    .button(RichText::new("Simulate").monospace().size(PRUNING_TEXT_SIZE))
+                    .clicked();
+                if submitted || simulate_clicked || (projection_changed && self.report.is_some()) {
+                    self.simulate(ui, parkhay_file);
+                }
+
+                if self.simulation.is_some() {
+                    ui.label(
+                        RichText::new("Simulating...")
+                            .monospace()
+                            .size(PRUNING_TEXT_SIZE),
+                    );
+                }
+
+                if (self.report.is_some() || self.simulation.is_some())
+                    && ui
+                        .button(RichText::new("Clear").monospace().size(PRUNING_TEXT_SIZE))
+                        .clicked()
+                {
+                    self.report = None;
+                    self.simulated_query = None;
+                    self.simulation = None;
+                }
+
+                if let Some(error) = &self.error {
+                    ui.label(
+                        RichText::new(error)
+                            .monospace()
+                            .size(PRUNING_TEXT_SIZE)
+                            .color(Color32::DARK_RED),
+                    );
+                }
+            });
+        });
+    }
+
+    /// Parse the predicate, then simulate it on a worker thread.
+    fn simulate(&mut self, ui: &Ui, parkhay_file: &Arc<ParkhayFile>) {
+        let Some(footer) = &parkhay_file.footer else {
+            return;
+        };
+        let predicate = match Predicate::parse(&self.query, footer) {
+            Ok(predicate) => predicate,
+            Err(e) => {
+                self.report = None;
+                self.simulated_query = None;
+                self.simulation = None;
+                self.error = Some(format!("{e:#}"));
+                return;
+            }
+        };
+
+        let result: SimulationResult = Arc::new(Mutex::new(None));
+        self.simulation = Some((self.query.trim().to_string(), result.clone()));
+        let parkhay_file = parkhay_file.clone();
+        let project_all_columns = self.project_all_columns;
+        let ctx = ui.ctx().clone();
+        thread::spawn(move || {
+            let report = PruningReport::new(&parkhay_file, &predicate, project_all_columns)
+                .map_err(|e| format!("{e:#}"));
+            if let Ok(mut result) = result.lock() {
+                *result = Some(report);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// Show the report of the simulation once the worker thread has finished it.
+    fn take_simulation(&mut self) {
+        let Some((query, result)) = &self.simulation else {
+            return;
+        };
+        let Some(result) = result.lock().ok().and_then(|mut result| result.take()) else {
+            return;
+        };
+        match result {
+            Ok(report) => {
+                self.report = Some(report);
+                self.simulated_query = Some(query.clone());
+                self.error = None;
+            }
+            Err(e) => {
+                self.report = None;
+                self.simulated_query = None;
+                self.error = Some(e);
+            }
+        }
+        self.simulation = None;
+    }
+
+    /// Summary of the simulation, the byte ranges left to read and a breakdown per row group.
+    pub fn render_report(
+        &self,
+        ui: &mut Ui,
+        parkhay_file: &ParkhayFile,
+        navigator: &mut Navigator,
+    ) {
+        let (Some(report), Some(query)) = (&self.report, &self.simulated_query) else {
+            return;
+        };
+        let label = |ui: &mut Ui, text: String| {
+            ui.label(RichText::new(text).monospace().size(PRUNING_TEXT_SIZE));
+        };
+
+        ui.label(
+            RichText::new(format!("WHERE {query}"))
+                .monospace()
+                .size(PRUNING_TEXT_SIZE)
+                .strong(),
+        );
+        ui.separator();
+
+        let num_row_groups = report.row_groups.len();
+        let num_skipped_row_groups = report
+            .row_groups
+            .iter()
+            .filter(|rg| rg.skipped_by.is_some())
+            .count();
+        label(
+            ui,
+            format!("Row groups skipped: {num_skipped_row_groups} of {num_row_groups}"),
+        );
+        let (skipped_pages, total_pages) = report
+            .row_groups
+            .iter()
+            .filter_map(|rg| rg.skipped_pages)
+            .fold((0, 0), |(skipped, total), (s, t)| (skipped + s, total + t));
+        if total_pages > 0 {
+            label(
+                ui,
+                format!("Predicate pages skipped: {skipped_pages} of {total_pages}"),
+            );
+        }
+        let num_rows = report.row_groups.iter().map(|rg| rg.num_rows).sum::<i64>();
+        let selected_rows = report
+            .row_groups
+            .iter()
+            .map(|rg| rg.selected_rows)
+            .sum::<i64>();
+        label(ui, format!("Rows to decode: {selected_rows} of {num_rows}"));
+        let total_bytes = report.total_bytes();
+        let data_bytes = report.read_bytes(ReadKind::Data);
+        label(
+            ui,
+            format!(
+                "Data bytes read: {data_bytes} of {total_bytes} ({:.1}%)",
+                Self::percentage(data_bytes, total_bytes)
+            ),
+        );
+        let index_bytes = report.read_bytes(ReadKind::Index);
+        label(ui, format!("Index bytes read: {index_bytes}"));
+        label(
+            ui,
+            format!(
+                "Requests: {}, {:.1}% of the file",
+                report.reads.len(),
+                Self::percentage(data_bytes + index_bytes, parkhay_file.file_size)
+            ),
+        );
+        ui.separator();
+
+        let ranges = report
+            .reads
+            .iter()
+            .map(|(byte_interval, kind)| {
+                let color = match kind {
+                    ReadKind::Data => READ_DATA_COLOR,
+                    ReadKind::Index => READ_INDEX_COLOR,
+                };
+                (*byte_interval, color)
+            })
+            .collect::<Vec<_>>();
+        if let Some(offset) = FileMap::show(ui, parkhay_file.file_size, &ranges) {
+            navigator.request_offset(ui, offset);
+        }
+        ui.horizontal(|ui| {
+            for (name, color) in [("Data", READ_DATA_COLOR), ("Index", READ_INDEX_COLOR)] {
+                let (swatch, _) = ui.allocate_exact_size(vec2(10., 10.), Sense::hover());
+                ui.painter().rect_filled(swatch, 1., color);
+                ui.label(RichText::new(name).monospace().small());
+            }
+        });
+        ui.separator();
+
+        Self::render_row_groups(ui, report, parkhay_file, navigator);
+    }
+
+    fn render_row_groups(
+        ui: &mut Ui,
+        report: &PruningReport,
+        parkhay_file: &ParkhayFile,
+        navigator: &mut Navigator,
+    ) {
+        let cell = |ui: &mut Ui, text: String| {
+            ui.label(RichText::new(text).monospace().size(PRUNING_TEXT_SIZE));
+        };
+        ScrollArea::both().show(ui, |ui| {
+            Grid::new("Pruning Row Groups")
+                .num_columns(5)
+                .spacing([10., 5.])
+                .striped(true)
+                .show(ui, |ui| {
+                    for title in [
+                        "Row Group",
+                        "Rows",
+                        "Pages Skipped",
+                        "Bytes Read",
+                        "Skipped By",
+                    ] {
+                        ui.label(
+                            RichText::new(title)
+                                .monospace()
+                                .size(PRUNING_TEXT_SIZE)
+                                .strong(),
+                        );
+                    }
+                    ui.end_row();
+
+                    for rg in &report.row_groups {
+                        let start = Self::row_group_start(parkhay_file, rg.row_group as usize);
+                        let clicked = ui
+                            .link(
+                                RichText::new(rg.row_group.to_string())
+                                    .monospace()
+                                    .size(PRUNING_TEXT_SIZE),
+                            )
+                            .clicked();
+                        if let (true, Some(start)) = (clicked, start) {
+                            navigator.request_offset(ui, start);
+                        }
+                        cell(ui, format!("{} of {}", rg.selected_rows, rg.num_rows));
+                        cell(
+                            ui,
+                            rg.skipped_pages
+                                .map(|(skipped, total)| format!("{skipped} of {total}"))
+                                .unwrap_or(String::from("N/A")),
+                        );
+                        cell(ui, format!("{} of {}", rg.read_bytes, rg.total_bytes));
+                        cell(
+                            ui,
+                            rg.skipped_by
+                                .map(|reason| reason.to_string())
+                                .unwrap_or(String::from("-")),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn row_group_start(parkhay_file: &ParkhayFile, row_group: usize) -> Option<u64> {
+        let metadata = parkhay_file
+            .footer
+            .as_ref()?
+            .row_groups
+            .get(row_group)?
+            .columns
+            .first()?
+            .meta_data
+            .as_ref()?;
+        Some(
+            metadata
+                .dictionary_page_offset
+                .unwrap_or(metadata.data_page_offset) as u64,
+        )
+    }
+
+    fn percentage(part: u64, total: u64) -> f64 {
+        if total == 0 {
+            0.
+        } else {
+            part as f64 / total as f64 * 100.
+        }
+    }
+}
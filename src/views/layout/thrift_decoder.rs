@@ -0,0 +1,218 @@
+use std::sync::{Arc, Mutex, mpsc::Sender};
+
+use anyhow::Context;
+use egui::{Color32, Key, RichText, TextEdit, Ui};
+
+use crate::{
+    ParkhayFile,
+    file::{ByteInterval, ReadRequest, ThriftTrace},
+};
+
+use super::{navigation::Navigator, thrift_renderer::ThriftRenderer};
+
+const THRIFT_DECODER_TEXT_SIZE: f32 = 13.;
+/// Bytes read for ranges without an end, which is enough for all but the largest structs
+const OPEN_RANGE_LENGTH: u64 = 64 * 1024;
+/// Ranges larger than this aren't read, to keep the hex view and the tree responsive
+const MAX_RANGE_LENGTH: u64 = 16 * 1024 * 1024;
+
+/// A byte range being decoded, along with its bytes and their trace once they are read.
+struct ThriftDecoding {
+    byte_interval: ByteInterval,
+    bytes: Option<Arc<Mutex<Option<Vec<u8>>>>>,
+    trace: Option<ThriftTrace>,
+}
+
+/// Decodes any byte range of the file as a thrift compact-encoded struct without a schema, to
+/// check whether unreferenced or unrecognized bytes hold thrift.
+#[derive(Default)]
+pub struct ThriftDecoder {
+    query: String,
+    decoding: Option<ThriftDecoding>,
+    error: Option<String>,
+}
+
+impl ThriftDecoder {
+    pub fn is_active(&self) -> bool {
+        self.decoding.is_some()
+    }
+
+    /// Decode the bytes of `byte_interval`, replacing the range being decoded. Ranges larger than
+    /// the maximum are cut short, since a struct is unlikely to span them.
+    pub fn decode(&mut self, byte_interval: ByteInterval) {
+        let byte_interval = (
+            byte_interval.0,
+            byte_interval
+                .1
+                .min(byte_interval.0.saturating_add(MAX_RANGE_LENGTH - 1)),
+        );
+        self.query = format!("{}..={}", byte_interval.0, byte_interval.1);
+        self.error = None;
+        self.decoding = Some(ThriftDecoding {
+            byte_interval,
+            bytes: None,
+            trace: None,
+        });
+    }
+
+    pub fn render_toolbar(&mut self, ui: &mut Ui, parkhay_file: &ParkhayFile) {
+        ui.horizontal(|ui| {
+            let response = ui
+                .add(
+                    TextEdit::singleline(&mut self.query)
+                        .hint_text("Decode as thrift, e.g. 1024..=2047")
+                        .font(egui::FontId::monospace(THRIFT_DECODER_TEXT_SIZE))
+                        .desired_width(220.),
+                )
+                .on_hover_text(format!(
+                    "A byte range written as start..=end, start..end, start+length or start.., \
+                     which reads up to {OPEN_RANGE_LENGTH} bytes. Offsets are decimal or \
+                     0x-prefixed."
+                ));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if response.changed() {
+                self.error = None;
+            }
+            let decode_clicked = ui
+                .button(
+                    RichText::new("Decode")
+                        .monospace()
+                        .size(THRIFT_DECODER_TEXT_SIZE),
+                )
+                .clicked();
+            if submitted || decode_clicked {
+                match Self::parse_range(&self.query, parkhay_file.file_size) {
+                    Ok(byte_interval) => self.decode(byte_interval),
+                    Err(e) => self.error = Some(e),
+                }
+            }
+
+            if self.decoding.is_some()
+                && ui
+                    .button(
+                        RichText::new("Clear")
+                            .monospace()
+                            .size(THRIFT_DECODER_TEXT_SIZE),
+                    )
+                    .clicked()
+            {
+                self.decoding = None;
+            }
+
+            if let Some(error) = &self.error {
+                ui.label(
+                    RichText::new(error)
+                        .monospace()
+                        .size(THRIFT_DECODER_TEXT_SIZE)
+                        .color(Color32::DARK_RED),
+                );
+            }
+        });
+    }
+
+    /// The fields the range decodes to, once its bytes have been read.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        reader_tx: &Sender<ReadRequest>,
+        navigator: &mut Navigator,
+    ) {
+        let Some(decoding) = &mut self.decoding else {
+            return;
+        };
+        let (start, end) = decoding.byte_interval;
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!("Bytes {start}..={end} ({} bytes)", end - start + 1))
+                    .monospace()
+                    .size(THRIFT_DECODER_TEXT_SIZE)
+                    .strong(),
+            );
+            if ui
+                .small_button(RichText::new("Go").monospace())
+                .on_hover_text("Go to the section holding the first byte")
+                .clicked()
+            {
+                navigator.request_offset(ui, start);
+            }
+        });
+        ui.separator();
+
+        let bytes = decoding.bytes.get_or_insert_with(|| {
+            let bytes = Arc::new(Mutex::new(None));
+            reader_tx
+                .send(ReadRequest(decoding.byte_interval, bytes.clone()))
+                .context("Couldn't send message to reader thread")
+                .unwrap();
+            bytes
+        });
+        let Ok(bytes) = bytes.lock() else {
+            panic!("Can't get lock on thrift bytes");
+        };
+        let Some(bytes) = bytes.as_deref() else {
+            ui.label(
+                RichText::new("Reading...")
+                    .monospace()
+                    .size(THRIFT_DECODER_TEXT_SIZE),
+            );
+            return;
+        };
+        let trace = decoding
+            .trace
+            .get_or_insert_with(|| ThriftTrace::decode(bytes, None));
+        ThriftRenderer::render(
+            ui,
+            ui.make_persistent_id(("thrift_decoder", decoding.byte_interval)),
+            bytes,
+            start,
+            trace,
+            None,
+            navigator,
+        );
+    }
+
+    fn parse_range(text: &str, file_size: u64) -> Result<ByteInterval, String> {
+        let offset = |text: &str| {
+            Navigator::parse_offset(text)
+                .ok_or_else(|| String::from("Expected a decimal or 0x-prefixed offset"))
+        };
+        // The end is exclusive until the range is checked
+        let (start, end) = if let Some((start, end)) = text.split_once("..=") {
+            (offset(start)?, offset(end)?.saturating_add(1))
+        } else if let Some((start, end)) = text.split_once("..") {
+            let start = offset(start)?;
+            match end.trim() {
+                "" => (
+                    start,
+                    start.saturating_add(OPEN_RANGE_LENGTH).min(file_size),
+                ),
+                end => (start, offset(end)?),
+            }
+        } else if let Some((start, length)) = text.split_once('+') {
+            let start = offset(start)?;
+            (start, start.saturating_add(offset(length)?))
+        } else {
+            return Err(String::from(
+                "Expected a range like 1024..=2047, 1024..2048, 1024+1024 or 1024..",
+            ));
+        };
+
+        if start >= file_size {
+            return Err(format!(
+                "Offset {start} is past the end of the file ({file_size} bytes)"
+            ));
+        }
+        if end <= start {
+            return Err(String::from("The range is empty"));
+        }
+        if end > file_size {
+            return Err(format!(
+                "The range ends past the end of the file ({file_size} bytes)"
+            ));
+        }
+        if end - start > MAX_RANGE_LENGTH {
+            return Err(format!("The range is larger than {MAX_RANGE_LENGTH} bytes"));
+        }
+        Ok((start, end - 1))
+    }
+}
@@ -0,0 +1,483 @@
+use std::{
+    ops::Range,
+    sync::{Arc, Mutex, mpsc::Sender},
+};
+
+use anyhow::Context;
+use egui::{
+    Align, CollapsingHeader, Color32, FontId, Id, Label, RichText, ScrollArea, Sense, Ui, Widget,
+};
+
+use crate::file::{
+    ByteInterval, ReadRequest, ThriftField, ThriftSchema, ThriftTrace, ThriftValue, UnknownField,
+};
+
+use super::{FOCUS_HIGHLIGHT_COLOR, navigation::Navigator};
+
+const THRIFT_TEXT_SIZE: f32 = 13.;
+const BYTES_PER_ROW: usize = 16;
+const HEX_MAX_HEIGHT: f32 = 240.;
+const TREE_MAX_HEIGHT: f32 = 360.;
+/// Elements of a list shown in the tree, past which only the selected element is added
+const MAX_LIST_ELEMENTS: usize = 1000;
+/// Bytes of a binary value shown as hex when it's hovered
+const MAX_HOVER_BYTES: usize = 256;
+/// Background of the bytes of each field, picked by the field's id so that siblings differ
+const FIELD_COLORS: [Color32; 6] = [
+    Color32::from_rgb(200, 225, 250),
+    Color32::from_rgb(250, 220, 190),
+    Color32::from_rgb(205, 240, 205),
+    Color32::from_rgb(240, 210, 240),
+    Color32::from_rgb(250, 245, 180),
+    Color32::from_rgb(210, 235, 235),
+];
+
+type ThriftBytes = Arc<Mutex<Option<Vec<u8>>>>;
+
+/// The field selected in the tree or the hex view, shared by both.
+#[derive(Clone, Default)]
+struct ThriftSelection {
+    bytes: Option<Range<usize>>,
+    /// Set when a byte is clicked, to open the tree down to its field
+    reveal_field: bool,
+    /// Set when a field is clicked, to scroll the hex view to its bytes
+    reveal_bytes: bool,
+    /// Position of the byte navigated to, whose field is selected once
+    focused_byte: Option<usize>,
+    /// Set when a binary value's bytes should be decoded as a struct of their own
+    decode_bytes: Option<Range<usize>>,
+}
+
+/// Thrift compact-encoded bytes as a tree of the fields they decode to, next to a hex view of the
+/// bytes. Selecting a field highlights its bytes, and clicking a byte selects its field.
+pub struct ThriftRenderer;
+impl ThriftRenderer {
+    /// Read the struct stored at `byte_interval` the first time it's shown, then render it.
+    pub fn render_encoded(
+        ui: &mut Ui,
+        id: Id,
+        byte_interval: ByteInterval,
+        schema: &'static ThriftSchema,
+        focused_byte: Option<usize>,
+        reader_tx: &Sender<ReadRequest>,
+        navigator: &mut Navigator,
+    ) {
+        let Some((bytes, trace)) = Self::read_encoded(ui, id, byte_interval, schema, reader_tx)
+        else {
+            ui.label(Self::text("Reading..."));
+            return;
+        };
+        let Ok(bytes) = bytes.lock() else {
+            panic!("Can't get lock on thrift bytes");
+        };
+        let bytes = bytes.as_deref().unwrap_or_default();
+        ui.label(Self::text(format!(
+            "{} ({} bytes at {}..={})",
+            schema.name,
+            bytes.len(),
+            byte_interval.0,
+            byte_interval.1
+        )));
+        Self::render(
+            ui,
+            id,
+            bytes,
+            byte_interval.0,
+            &trace,
+            focused_byte,
+            navigator,
+        );
+    }
+
+    /// The bytes of the struct stored at `byte_interval` and their trace, once they have been
+    /// read. The bytes are requested from the reader the first time this is called for `id`.
+    pub fn read_encoded(
+        ui: &Ui,
+        id: Id,
+        byte_interval: ByteInterval,
+        schema: &'static ThriftSchema,
+        reader_tx: &Sender<ReadRequest>,
+    ) -> Option<(ThriftBytes, Arc<ThriftTrace>)> {
+        let bytes_id = id.with("thrift_bytes");
+        let bytes = match ui.data(|d| d.get_temp::<ThriftBytes>(bytes_id)) {
+            Some(bytes) => bytes,
+            None => {
+                let bytes = Arc::new(Mutex::new(None));
+                reader_tx
+                    .send(ReadRequest(byte_interval, bytes.clone()))
+                    .context("Couldn't send message to reader thread")
+                    .unwrap();
+                ui.data_mut(|d| d.insert_temp(bytes_id, bytes.clone()));
+                bytes
+            }
+        };
+
+        let trace_id = id.with("thrift_trace");
+        let trace = match ui.data(|d| d.get_temp::<Arc<ThriftTrace>>(trace_id)) {
+            Some(trace) => trace,
+            None => {
+                let Ok(read) = bytes.lock() else {
+                    panic!("Can't get lock on thrift bytes");
+                };
+                let trace = Arc::new(ThriftTrace::decode(read.as_ref()?, Some(schema)));
+                ui.data_mut(|d| d.insert_temp(trace_id, trace.clone()));
+                trace
+            }
+        };
+        Some((bytes, trace))
+    }
+
+    /// Render the bytes, which start at `start` in the file, and their trace. When `focused_byte`
+    /// changes, the field it belongs to is selected and revealed in both views.
+    pub fn render(
+        ui: &mut Ui,
+        id: Id,
+        bytes: &[u8],
+        start: u64,
+        trace: &ThriftTrace,
+        focused_byte: Option<usize>,
+        navigator: &mut Navigator,
+    ) {
+        let selection_id = id.with("thrift_selection");
+        let mut selection: ThriftSelection =
+            ui.data(|d| d.get_temp(selection_id)).unwrap_or_default();
+        if selection.focused_byte != focused_byte {
+            selection.focused_byte = focused_byte;
+            if let Some(pos) = focused_byte {
+                // Bytes outside of any field, like the stop byte, are selected on their own
+                selection.bytes = Some(
+                    trace
+                        .field_at(pos)
+                        .map_or(pos..pos + 1, |(_, field)| field.bytes()),
+                );
+                selection.reveal_field = true;
+                selection.reveal_bytes = true;
+            }
+        }
+
+        Self::render_hex(ui, id, bytes, trace, &mut selection);
+        if let Some(error) = &trace.error {
+            ui.label(Self::text(format!("Decoding stopped: {error}")).color(Color32::DARK_RED));
+        } else if trace.bytes.end < bytes.len() {
+            ui.label(
+                Self::text(format!(
+                    "The struct ends after {} bytes, {} bytes are left over",
+                    trace.bytes.end,
+                    bytes.len() - trace.bytes.end
+                ))
+                .color(Color32::DARK_RED),
+            );
+        }
+        ui.separator();
+        ScrollArea::vertical()
+            .id_salt(id.with("thrift_tree"))
+            .max_height(TREE_MAX_HEIGHT)
+            .auto_shrink([false, true])
+            .show(ui, |ui| {
+                Self::render_fields(ui, id, &trace.fields, &mut selection);
+            });
+        selection.reveal_field = false;
+        if let Some(bytes) = selection.decode_bytes.take() {
+            navigator.request_thrift(
+                ui,
+                (start + bytes.start as u64, start + bytes.end as u64 - 1),
+            );
+        }
+
+        ui.data_mut(|d| d.insert_temp(selection_id, selection));
+    }
+
+    /// Hex view of the bytes, colored by the innermost field each byte belongs to, with the
+    /// selected field's bytes highlighted.
+    fn render_hex(
+        ui: &mut Ui,
+        id: Id,
+        bytes: &[u8],
+        trace: &ThriftTrace,
+        selection: &mut ThriftSelection,
+    ) {
+        let font_id = FontId::monospace(THRIFT_TEXT_SIZE);
+        let row_height = ui.fonts(|fonts| fonts.row_height(&font_id));
+        let mut scroll_area = ScrollArea::vertical()
+            .id_salt(id.with("thrift_hex"))
+            .max_height(HEX_MAX_HEIGHT)
+            .auto_shrink([false, true]);
+        if let (true, Some(selected)) = (selection.reveal_bytes, &selection.bytes) {
+            let row = (selected.start / BYTES_PER_ROW).saturating_sub(1);
+            scroll_area = scroll_area
+                .vertical_scroll_offset(row as f32 * (row_height + ui.spacing().item_spacing.y));
+        }
+        selection.reveal_bytes = false;
+
+        let num_rows = bytes.len().div_ceil(BYTES_PER_ROW);
+        scroll_area.show_rows(ui, row_height, num_rows, |ui, rows| {
+            for row in rows {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 4.;
+                    let row_start = row * BYTES_PER_ROW;
+                    ui.label(Self::text(format!("{row_start:06x}")).weak());
+                    let row_end = (row_start + BYTES_PER_ROW).min(bytes.len());
+                    for (pos, byte) in (row_start..row_end).zip(&bytes[row_start..row_end]) {
+                        let field = trace.field_at(pos);
+                        let is_selected = selection
+                            .bytes
+                            .as_ref()
+                            .is_some_and(|selected| selected.contains(&pos));
+                        let mut text = Self::text(format!("{byte:02x}"));
+                        if is_selected {
+                            text = text.background_color(FOCUS_HIGHLIGHT_COLOR);
+                        } else if let Some((_, field)) = &field {
+                            let color =
+                                FIELD_COLORS[field.id.unsigned_abs() as usize % FIELD_COLORS.len()];
+                            text = text.background_color(color);
+                        }
+                        if let Some((_, field)) = &field
+                            && field.header.contains(&pos)
+                        {
+                            text = text.underline();
+                        }
+                        // Only described when hovered, since most bytes never are
+                        let response =
+                            Label::new(text)
+                                .sense(Sense::click())
+                                .ui(ui)
+                                .on_hover_ui(|ui| {
+                                    let hover = match &field {
+                                        Some((path, field)) => Self::describe(path, field, pos),
+                                        None if trace.bytes.contains(&pos) => {
+                                            String::from("Struct stop byte")
+                                        }
+                                        None => String::from("Not part of the struct"),
+                                    };
+                                    ui.label(format!("Byte {pos}\n{hover}"));
+                                });
+                        if let (true, Some((_, field))) = (response.clicked(), field) {
+                            selection.bytes = Some(field.bytes());
+                            selection.reveal_field = true;
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    fn render_fields(ui: &mut Ui, id: Id, fields: &[ThriftField], selection: &mut ThriftSelection) {
+        for field in fields {
+            let label = format!("{} ({}): {}", field.label(), field.id, field.type_label());
+            Self::render_node(ui, id, label, field.bytes(), &field.value.value, selection);
+        }
+    }
+
+    /// A field, list element or map entry, which can be expanded if it holds other values.
+    fn render_node(
+        ui: &mut Ui,
+        id: Id,
+        label: String,
+        bytes: Range<usize>,
+        value: &ThriftValue,
+        selection: &mut ThriftSelection,
+    ) {
+        let is_selected = selection.bytes.as_ref() == Some(&bytes);
+        let mut text = Self::text(format!("{label} = {}", Self::value(value)));
+        let has_children = match value {
+            ThriftValue::Struct(fields) => !fields.is_empty(),
+            ThriftValue::List(_, elements) => !elements.is_empty(),
+            ThriftValue::Map(_, _, entries) => !entries.is_empty(),
+            _ => false,
+        };
+
+        let response = if has_children {
+            if is_selected {
+                text = text.background_color(FOCUS_HIGHLIGHT_COLOR);
+            }
+            let holds_selection = selection.bytes.as_ref().is_some_and(|selected| {
+                bytes.start <= selected.start && selected.end <= bytes.end && *selected != bytes
+            });
+            let mut header = CollapsingHeader::new(text).id_salt(id.with((
+                "thrift_node",
+                bytes.start,
+                bytes.end,
+            )));
+            if selection.reveal_field && holds_selection {
+                header = header.open(Some(true));
+            }
+            header
+                .show(ui, |ui| match value {
+                    ThriftValue::Struct(fields) => Self::render_fields(ui, id, fields, selection),
+                    ThriftValue::List(_, elements) => {
+                        for (idx, element) in elements.iter().enumerate() {
+                            let is_shown = idx < MAX_LIST_ELEMENTS
+                                || selection.bytes.as_ref().is_some_and(|selected| {
+                                    element.bytes.start <= selected.start
+                                        && selected.end <= element.bytes.end
+                                });
+                            if is_shown {
+                                Self::render_node(
+                                    ui,
+                                    id,
+                                    format!("[{idx}]"),
+                                    element.bytes.clone(),
+                                    &element.value,
+                                    selection,
+                                );
+                            }
+                        }
+                        if elements.len() > MAX_LIST_ELEMENTS {
+                            ui.label(
+                                Self::text(format!(
+                                    "{} more elements, select their bytes to show them",
+                                    elements.len() - MAX_LIST_ELEMENTS
+                                ))
+                                .italics(),
+                            );
+                        }
+                    }
+                    ThriftValue::Map(_, _, entries) => {
+                        for (key, value) in entries {
+                            Self::render_node(
+                                ui,
+                                id,
+                                format!("[{}]", Self::value(&key.value)),
+                                key.bytes.start..value.bytes.end,
+                                &value.value,
+                                selection,
+                            );
+                        }
+                    }
+                    _ => {}
+                })
+                .header_response
+        } else if let ThriftValue::Binary(value) = value
+            && !value.is_empty()
+        {
+            ui.horizontal(|ui| {
+                let response = ui.selectable_label(is_selected, text);
+                if ui
+                    .small_button(RichText::new("Decode").monospace())
+                    .on_hover_text("Decode the value as a thrift struct")
+                    .clicked()
+                {
+                    // The value's bytes follow its length
+                    selection.decode_bytes = Some(bytes.end - value.len()..bytes.end);
+                }
+                response
+            })
+            .inner
+        } else {
+            ui.selectable_label(is_selected, text)
+        };
+
+        if response.clicked() {
+            selection.bytes = Some(bytes);
+            selection.reveal_bytes = true;
+        } else if is_selected && selection.reveal_field {
+            response.scroll_to_me(Some(Align::Center));
+        }
+    }
+
+    /// Fields missing from the schema of their struct, as trees of the values they decode to.
+    pub fn render_unknown_fields(ui: &mut Ui, id: Id, unknown_fields: &[UnknownField]) {
+        for field in unknown_fields {
+            let name = match field.path.as_str() {
+                "" => format!("field {}", field.id),
+                path => format!("{path}.field {}", field.id),
+            };
+            let label = format!(
+                "{name}: {} at {}..={}",
+                field.field_type, field.byte_interval.0, field.byte_interval.1
+            );
+            Self::render_value(ui, id.with(field.byte_interval), label, &field.value);
+        }
+    }
+
+    /// A value and the values it holds, for values whose bytes aren't shown.
+    fn render_value(ui: &mut Ui, id: Id, label: String, value: &ThriftValue) {
+        let text = Self::text(format!("{label} = {}", Self::value(value)));
+        match value {
+            ThriftValue::Struct(fields) if !fields.is_empty() => {
+                CollapsingHeader::new(text).id_salt(id).show(ui, |ui| {
+                    for field in fields {
+                        let label =
+                            format!("{} ({}): {}", field.label(), field.id, field.type_label());
+                        Self::render_value(ui, id.with(field.id), label, &field.value.value);
+                    }
+                });
+            }
+            ThriftValue::List(_, elements) if !elements.is_empty() => {
+                CollapsingHeader::new(text).id_salt(id).show(ui, |ui| {
+                    for (idx, element) in elements.iter().enumerate().take(MAX_LIST_ELEMENTS) {
+                        Self::render_value(ui, id.with(idx), format!("[{idx}]"), &element.value);
+                    }
+                    if elements.len() > MAX_LIST_ELEMENTS {
+                        ui.label(
+                            Self::text(format!(
+                                "{} more elements",
+                                elements.len() - MAX_LIST_ELEMENTS
+                            ))
+                            .italics(),
+                        );
+                    }
+                });
+            }
+            ThriftValue::Map(_, _, entries) if !entries.is_empty() => {
+                CollapsingHeader::new(text).id_salt(id).show(ui, |ui| {
+                    for (idx, (key, value)) in entries.iter().enumerate() {
+                        let label = format!("[{}]", Self::value(&key.value));
+                        Self::render_value(ui, id.with(idx), label, &value.value);
+                    }
+                });
+            }
+            ThriftValue::Binary(bytes) => {
+                ui.label(text).on_hover_ui(|ui| {
+                    let mut hex = bytes
+                        .iter()
+                        .take(MAX_HOVER_BYTES)
+                        .map(|b| format!("{b:02x}"))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    if bytes.len() > MAX_HOVER_BYTES {
+                        hex += &format!(" … ({} bytes)", bytes.len());
+                    }
+                    ui.label(hex);
+                });
+            }
+            _ => {
+                ui.label(text);
+            }
+        }
+    }
+
+    fn describe(path: &str, field: &ThriftField, pos: usize) -> String {
+        let part = if field.header.contains(&pos) {
+            "field header"
+        } else {
+            "value"
+        };
+        format!(
+            "{path} (id {}, {}), {part}\n= {}",
+            field.id,
+            field.type_label(),
+            Self::value(&field.value.value)
+        )
+    }
+
+    /// The value, with short binary values that aren't text shown as hex.
+    fn value(value: &ThriftValue) -> String {
+        match value {
+            ThriftValue::Binary(bytes)
+                if bytes.len() <= 32 && std::str::from_utf8(bytes).is_err() =>
+            {
+                bytes
+                    .iter()
+                    .map(|b| format!("{b:02x}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+            value => value.to_string(),
+        }
+    }
+
+    fn text(text: impl Into<String>) -> RichText {
+        RichText::new(text).monospace().size(THRIFT_TEXT_SIZE)
+    }
+}
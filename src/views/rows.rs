@@ -0,0 +1,543 @@
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use anyhow::{Context, Result};
+use arrow_array::{Array, ArrayRef, RecordBatch, cast::AsArray};
+use arrow_cast::display::{ArrayFormatter, FormatOptions};
+use egui::{
+    CentralPanel, Color32, ComboBox, DragValue, Label, RichText, ScrollArea, SidePanel,
+    TopBottomPanel, Ui, Widget,
+};
+use egui_extras::{Column, TableBuilder};
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReaderBuilder, RowSelection, RowSelector};
+use shredding_renderer::ShreddingRenderer;
+
+use super::{RowsRequest, View};
+use crate::{ParkhayFile, file::RecordShredding};
+
+mod shredding_renderer;
+
+const ROWS_TEXT_SIZE: f32 = 13.;
+const TABLE_ROW_HEIGHT: f32 = 18.;
+const CELL_MAX_WIDTH: f32 = 320.;
+const ELEMENTS_MAX_HEIGHT: f32 = 400.;
+const SHREDDING_PANEL_WIDTH: f32 = 520.;
+/// Rows decoded at once, to bound memory use for large row groups
+const MAX_DECODED_ROWS: i64 = 100_000;
+
+/// Rows decoded from a row group, as a single record batch.
+struct DecodedRows {
+    row_group: usize,
+    first_row: i64,
+    batch: RecordBatch,
+}
+
+type DecodeResult = Arc<Mutex<Option<Result<DecodedRows, String>>>>;
+type ShreddingResult = Arc<Mutex<Option<Result<RecordShredding, String>>>>;
+
+/// A column of the table. Expanded struct columns are followed by their children.
+struct TableColumn {
+    path: String,
+    array: ArrayRef,
+    /// Struct arrays containing the column, whose nulls also null the column
+    parents: Vec<ArrayRef>,
+    is_struct: bool,
+    is_expanded: bool,
+}
+
+/// Logical records of a row group, decoded to Arrow by the `parquet` crate's reader.
+pub struct RowsView {
+    parkhay_file: Arc<ParkhayFile>,
+    /// Number of rows in each row group, empty if the footer couldn't be read
+    row_group_rows: Vec<i64>,
+    row_group: usize,
+    first_row: i64,
+    end_row: i64,
+    decoded: Option<DecodeResult>,
+    /// Paths of the struct columns whose children are shown
+    expanded: BTreeSet<String>,
+    /// Row of the record to shred, within the row group
+    record: i64,
+    /// The shredded record's row group and row, and the levels it was shredded into
+    shredding: Option<((usize, i64), ShreddingResult)>,
+}
+
+impl RowsView {
+    pub fn new(parkhay_file: Arc<ParkhayFile>) -> Self {
+        let row_group_rows = parkhay_file
+            .footer
+            .as_ref()
+            .map(|footer| {
+                footer
+                    .row_groups
+                    .iter()
+                    .map(|rg| rg.num_rows)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let end_row = row_group_rows
+            .first()
+            .copied()
+            .unwrap_or(0)
+            .min(MAX_DECODED_ROWS);
+        Self {
+            parkhay_file,
+            row_group_rows,
+            row_group: 0,
+            first_row: 0,
+            end_row,
+            decoded: None,
+            expanded: BTreeSet::new(),
+            record: 0,
+            shredding: None,
+        }
+    }
+
+    /// Decode the requested rows, up to `MAX_DECODED_ROWS` of them.
+    pub fn show_rows(&mut self, request: RowsRequest, ctx: &egui::Context) {
+        self.row_group = request.row_group;
+        let (first_row, end_row) = request.rows.unwrap_or((0, self.num_rows()));
+        self.first_row = first_row.clamp(0, self.num_rows());
+        self.end_row = end_row.clamp(self.first_row, self.first_row + MAX_DECODED_ROWS);
+        self.decode(ctx);
+    }
+
+    fn num_rows(&self) -> i64 {
+        self.row_group_rows
+            .get(self.row_group)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn decode(&mut self, ctx: &egui::Context) {
+        let decoded: DecodeResult = Arc::new(Mutex::new(None));
+        self.decoded = Some(decoded.clone());
+
+        let path = self.parkhay_file.path.clone();
+        let (row_group, first_row, end_row) = (self.row_group, self.first_row, self.end_row);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let result = Self::read_rows(&path, row_group, first_row, end_row)
+                .map(|batch| DecodedRows {
+                    row_group,
+                    first_row,
+                    batch,
+                })
+                .map_err(|e| format!("{e:#}"));
+            if let Ok(mut decoded) = decoded.lock() {
+                *decoded = Some(result);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn shred(&mut self, ctx: &egui::Context) {
+        let shredding: ShreddingResult = Arc::new(Mutex::new(None));
+        self.shredding = Some(((self.row_group, self.record), shredding.clone()));
+
+        let parkhay_file = self.parkhay_file.clone();
+        let (row_group, record) = (self.row_group, self.record);
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let result = RecordShredding::new(&parkhay_file, row_group, record)
+                .map_err(|e| format!("{e:#}"));
+            if let Ok(mut shredding) = shredding.lock() {
+                *shredding = Some(result);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    fn read_rows(
+        path: &str,
+        row_group: usize,
+        first_row: i64,
+        end_row: i64,
+    ) -> Result<RecordBatch> {
+        let file = File::open(path).with_context(|| format!("Couldn't open `{path}`"))?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)
+            .context("Couldn't read the file metadata")?;
+        let schema = builder.schema().clone();
+        let num_rows = (end_row - first_row).max(0) as usize;
+        let reader = builder
+            .with_row_groups(vec![row_group])
+            .with_row_selection(RowSelection::from(vec![
+                RowSelector::skip(first_row as usize),
+                RowSelector::select(num_rows),
+            ]))
+            .with_batch_size(num_rows.max(1))
+            .build()
+            .context("Couldn't create the record batch reader")?;
+        let batches = reader
+            .collect::<Result<Vec<_>, _>>()
+            .context("Couldn't decode the rows")?;
+        arrow_select::concat::concat_batches(&schema, &batches)
+            .context("Couldn't combine the record batches")
+    }
+
+    fn render_toolbar(&mut self, ui: &mut Ui) {
+        if self.row_group_rows.is_empty() {
+            ui.label(
+                RichText::new("Rows can't be decoded without the footer")
+                    .monospace()
+                    .size(ROWS_TEXT_SIZE),
+            );
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            let previous_row_group = self.row_group;
+            ComboBox::from_id_salt("rows_row_group")
+                .selected_text(
+                    RichText::new(format!("Row Group {}", self.row_group))
+                        .monospace()
+                        .size(ROWS_TEXT_SIZE),
+                )
+                .show_ui(ui, |ui| {
+                    for (row_group, num_rows) in self.row_group_rows.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut self.row_group,
+                            row_group,
+                            RichText::new(format!("Row Group {row_group} ({num_rows} rows)"))
+                                .monospace()
+                                .size(ROWS_TEXT_SIZE),
+                        );
+                    }
+                });
+            if self.row_group != previous_row_group {
+                self.first_row = 0;
+                self.end_row = self.num_rows().min(MAX_DECODED_ROWS);
+                self.record = 0;
+            }
+
+            let num_rows = self.num_rows();
+            ui.label(RichText::new("Rows").monospace().size(ROWS_TEXT_SIZE));
+            ui.add(DragValue::new(&mut self.first_row).range(0..=num_rows));
+            ui.label(RichText::new("..").monospace().size(ROWS_TEXT_SIZE));
+            ui.add(
+                DragValue::new(&mut self.end_row)
+                    .range(self.first_row..=num_rows.min(self.first_row + MAX_DECODED_ROWS)),
+            )
+            .on_hover_text(format!(
+                "At most {MAX_DECODED_ROWS} rows are decoded at once"
+            ));
+            ui.label(
+                RichText::new(format!("of {num_rows}"))
+                    .monospace()
+                    .size(ROWS_TEXT_SIZE),
+            );
+
+            if ui
+                .button(RichText::new("Decode").monospace().size(ROWS_TEXT_SIZE))
+                .clicked()
+            {
+                self.decode(ui.ctx());
+            }
+
+            ui.separator();
+            ui.label(RichText::new("Record").monospace().size(ROWS_TEXT_SIZE));
+            ui.add(DragValue::new(&mut self.record).range(0..=(num_rows - 1).max(0)));
+            if ui
+                .button(
+                    RichText::new("Explain Shredding")
+                        .monospace()
+                        .size(ROWS_TEXT_SIZE),
+                )
+                .on_hover_text("Show the levels and values the record was shredded into")
+                .clicked()
+            {
+                self.shred(ui.ctx());
+            }
+        });
+    }
+
+    /// Render the decoded rows, returning the row group and row of a record picked for shredding.
+    fn render_rows(
+        ui: &mut Ui,
+        decoded: Option<&DecodeResult>,
+        expanded: &mut BTreeSet<String>,
+        shredded: Option<(usize, i64)>,
+    ) -> Option<(usize, i64)> {
+        let Some(decoded) = decoded else {
+            ui.label(
+                RichText::new("Pick a row group and a range of rows to decode")
+                    .monospace()
+                    .size(ROWS_TEXT_SIZE),
+            );
+            return None;
+        };
+        let decoded = decoded.lock().expect("Can't get lock on decoded rows");
+        match decoded.as_ref() {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(
+                        RichText::new("Decoding...")
+                            .monospace()
+                            .size(ROWS_TEXT_SIZE),
+                    );
+                });
+                None
+            }
+            Some(Err(e)) => {
+                ui.label(
+                    RichText::new(e)
+                        .monospace()
+                        .size(ROWS_TEXT_SIZE)
+                        .color(Color32::DARK_RED),
+                );
+                None
+            }
+            Some(Ok(rows)) => {
+                ui.label(
+                    RichText::new(format!(
+                        "Row Group {}, rows {}..{}",
+                        rows.row_group,
+                        rows.first_row,
+                        rows.first_row + rows.batch.num_rows() as i64
+                    ))
+                    .monospace()
+                    .size(ROWS_TEXT_SIZE)
+                    .strong(),
+                );
+                ui.separator();
+                let shredded_row = shredded
+                    .filter(|(row_group, _)| *row_group == rows.row_group)
+                    .map(|(_, row)| row);
+                Self::render_table(ui, rows, expanded, shredded_row)
+                    .map(|row| (rows.row_group, row))
+            }
+        }
+    }
+
+    fn render_table(
+        ui: &mut Ui,
+        rows: &DecodedRows,
+        expanded: &mut BTreeSet<String>,
+        shredded_row: Option<i64>,
+    ) -> Option<i64> {
+        let columns = Self::table_columns(&rows.batch, expanded);
+        let options = FormatOptions::default().with_null("null");
+        let formatters = columns
+            .iter()
+            .map(|column| ArrayFormatter::try_new(column.array.as_ref(), &options).ok())
+            .collect::<Vec<_>>();
+        let text = |text: String| RichText::new(text).monospace().size(ROWS_TEXT_SIZE);
+
+        let mut toggled = None;
+        let mut picked = None;
+        ScrollArea::horizontal().show(ui, |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .column(Column::auto())
+                .columns(
+                    Column::auto().at_most(CELL_MAX_WIDTH).clip(true),
+                    columns.len(),
+                )
+                .header(TABLE_ROW_HEIGHT, |mut header| {
+                    header.col(|ui| {
+                        Label::new(text(String::from("Row")).strong()).ui(ui);
+                    });
+                    for column in &columns {
+                        header.col(|ui| {
+                            if column.is_struct {
+                                let icon = if column.is_expanded { "⏷" } else { "⏵" };
+                                if ui
+                                    .link(text(format!("{icon} {}", column.path)).strong())
+                                    .on_hover_text("Show or hide the struct's fields")
+                                    .clicked()
+                                {
+                                    toggled = Some(column.path.clone());
+                                }
+                            } else {
+                                Label::new(text(column.path.clone()).strong()).ui(ui);
+                            }
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(TABLE_ROW_HEIGHT, rows.batch.num_rows(), |mut row| {
+                        let idx = row.index();
+                        let row_number = rows.first_row + idx as i64;
+                        row.col(|ui| {
+                            if ui
+                                .selectable_label(
+                                    shredded_row == Some(row_number),
+                                    text(row_number.to_string()),
+                                )
+                                .on_hover_text("Explain how the record was shredded")
+                                .clicked()
+                            {
+                                picked = Some(row_number);
+                            }
+                        });
+                        for (column, formatter) in columns.iter().zip(&formatters) {
+                            row.col(|ui| {
+                                let is_null = column.array.is_null(idx)
+                                    || column.parents.iter().any(|parent| parent.is_null(idx));
+                                let value = match (is_null, formatter) {
+                                    (true, _) => String::from("null"),
+                                    // The fields of an expanded struct are shown in their own columns
+                                    (false, _) if column.is_expanded => String::new(),
+                                    (false, Some(formatter)) => formatter.value(idx).to_string(),
+                                    (false, None) => String::from("?"),
+                                };
+                                match Self::elements(&column.array, idx).filter(|_| !is_null) {
+                                    Some(elements) => {
+                                        ui.horizontal(|ui| {
+                                            let len = elements.first().map_or(0, |e| e.len());
+                                            ui.menu_button(text(format!("[{len}]")), |ui| {
+                                                Self::render_elements(ui, &elements, &options)
+                                            })
+                                            .response
+                                            .on_hover_text("Show the elements");
+                                            Label::new(text(value)).truncate().ui(ui);
+                                        });
+                                    }
+                                    None => {
+                                        Label::new(text(value)).truncate().ui(ui);
+                                    }
+                                }
+                            });
+                        }
+                    });
+                });
+        });
+
+        if let Some(path) = toggled
+            && !expanded.remove(&path)
+        {
+            expanded.insert(path);
+        }
+        picked
+    }
+
+    /// The elements of a list, or the keys and values of a map's entries, in a row.
+    fn elements(array: &ArrayRef, idx: usize) -> Option<Vec<ArrayRef>> {
+        if let Some(list) = array.as_list_opt::<i32>() {
+            Some(vec![list.value(idx)])
+        } else if let Some(list) = array.as_list_opt::<i64>() {
+            Some(vec![list.value(idx)])
+        } else {
+            let entries = array.as_map_opt()?.value(idx);
+            Some(vec![entries.column(0).clone(), entries.column(1).clone()])
+        }
+    }
+
+    /// Each element of a list cell on its own line, with map entries shown as `key → value`.
+    fn render_elements(ui: &mut Ui, elements: &[ArrayRef], options: &FormatOptions) {
+        let formatters = elements
+            .iter()
+            .map(|element| ArrayFormatter::try_new(element.as_ref(), options).ok())
+            .collect::<Vec<_>>();
+        let len = elements.first().map_or(0, |e| e.len());
+        ui.set_max_width(2. * CELL_MAX_WIDTH);
+        ScrollArea::vertical()
+            .max_height(ELEMENTS_MAX_HEIGHT)
+            .show_rows(ui, TABLE_ROW_HEIGHT, len, |ui, range| {
+                for idx in range {
+                    let value = formatters
+                        .iter()
+                        .map(|formatter| match formatter {
+                            Some(formatter) => formatter.value(idx).to_string(),
+                            None => String::from("?"),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" → ");
+                    Label::new(
+                        RichText::new(format!("{idx}: {value}"))
+                            .monospace()
+                            .size(ROWS_TEXT_SIZE),
+                    )
+                    .truncate()
+                    .ui(ui);
+                }
+            });
+    }
+
+    fn table_columns(batch: &RecordBatch, expanded: &BTreeSet<String>) -> Vec<TableColumn> {
+        let mut columns = vec![];
+        for (field, array) in batch.schema().fields().iter().zip(batch.columns()) {
+            Self::push_table_columns(
+                field.name().clone(),
+                array.clone(),
+                vec![],
+                expanded,
+                &mut columns,
+            );
+        }
+        columns
+    }
+
+    fn push_table_columns(
+        path: String,
+        array: ArrayRef,
+        parents: Vec<ArrayRef>,
+        expanded: &BTreeSet<String>,
+        columns: &mut Vec<TableColumn>,
+    ) {
+        let is_expanded = expanded.contains(&path);
+        let children = array.as_struct_opt().filter(|_| is_expanded).map(|array| {
+            array
+                .fields()
+                .iter()
+                .zip(array.columns())
+                .map(|(field, child)| (format!("{path}.{}", field.name()), child.clone()))
+                .collect::<Vec<_>>()
+        });
+        columns.push(TableColumn {
+            path,
+            is_struct: array.as_struct_opt().is_some(),
+            is_expanded,
+            array: array.clone(),
+            parents: parents.clone(),
+        });
+
+        if let Some(children) = children {
+            let mut parents = parents;
+            parents.push(array);
+            for (path, child) in children {
+                Self::push_table_columns(path, child, parents.clone(), expanded, columns);
+            }
+        }
+    }
+}
+
+impl View for RowsView {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        TopBottomPanel::top("rows_toolbar").show(ctx, |ui| {
+            ui.add_space(4.);
+            self.render_toolbar(ui);
+            ui.add_space(4.);
+        });
+
+        if let Some((_, shredding)) = &self.shredding {
+            let mut is_open = true;
+            SidePanel::right("shredding")
+                .resizable(true)
+                .default_width(SHREDDING_PANEL_WIDTH)
+                .show(ctx, |ui| {
+                    let shredding = shredding.lock().expect("Can't get lock on shredding");
+                    ShreddingRenderer::render(ui, shredding.as_ref(), &mut is_open);
+                });
+            if !is_open {
+                self.shredding = None;
+            }
+        }
+
+        CentralPanel::default().show(ctx, |ui| {
+            let shredded = self.shredding.as_ref().map(|(record, _)| *record);
+            let picked = Self::render_rows(ui, self.decoded.as_ref(), &mut self.expanded, shredded);
+            if let Some((row_group, row)) = picked {
+                self.row_group = row_group;
+                self.record = row;
+                self.shred(ctx);
+            }
+        });
+    }
+}
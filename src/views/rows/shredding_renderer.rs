@@ -0,0 +1,122 @@
+use egui::{CollapsingHeader, Color32, Grid, RichText, ScrollArea, Ui};
+use parquet::basic::Repetition;
+
+use crate::file::{RecordShredding, ShreddedColumn};
+
+const SHREDDING_TEXT_SIZE: f32 = 13.;
+
+/// The repetition levels, definition levels and values a record was shredded into, per leaf.
+pub struct ShreddingRenderer;
+impl ShreddingRenderer {
+    pub fn render(
+        ui: &mut Ui,
+        shredding: Option<&Result<RecordShredding, String>>,
+        is_open: &mut bool,
+    ) {
+        let text = |text: String| RichText::new(text).monospace().size(SHREDDING_TEXT_SIZE);
+
+        ui.horizontal(|ui| {
+            ui.label(text(String::from("Record Shredding")).strong());
+            if ui.button(text(String::from("Close"))).clicked() {
+                *is_open = false;
+            }
+        });
+        ui.separator();
+
+        let shredding = match shredding {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(text(String::from("Shredding...")));
+                });
+                return;
+            }
+            Some(Err(e)) => {
+                ui.label(text(e.clone()).color(Color32::DARK_RED));
+                return;
+            }
+            Some(Ok(shredding)) => shredding,
+        };
+        ui.label(text(format!(
+            "Row Group {}, record {}",
+            shredding.row_group, shredding.row
+        )));
+        ui.label(
+            text(String::from(
+                "R: repetition level, the repeated field a value starts a new element of. \
+                 D: definition level, how many optional or repeated fields on the path are present.",
+            ))
+            .weak(),
+        );
+        ui.separator();
+
+        ScrollArea::vertical().show(ui, |ui| {
+            for column in &shredding.columns {
+                Self::render_column(ui, column);
+            }
+        });
+    }
+
+    fn render_column(ui: &mut Ui, column: &ShreddedColumn) {
+        let text = |text: String| RichText::new(text).monospace().size(SHREDDING_TEXT_SIZE);
+
+        CollapsingHeader::new(text(column.path.clone()).strong())
+            .id_salt(("shredded_column", &column.path))
+            .default_open(true)
+            .show(ui, |ui| {
+                let path = column
+                    .nodes
+                    .iter()
+                    .map(|node| match (node.repetition, node.repetition_level) {
+                        (Repetition::REQUIRED, _) => format!("{} (REQUIRED)", node.name),
+                        (repetition, Some(repetition_level)) => format!(
+                            "{} ({repetition}, D {}, R {repetition_level})",
+                            node.name, node.definition_level
+                        ),
+                        (repetition, None) => {
+                            format!("{} ({repetition}, D {})", node.name, node.definition_level)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" → ");
+                ui.label(text(path));
+                ui.label(text(format!(
+                    "Max definition level {}, max repetition level {}",
+                    column.max_definition_level, column.max_repetition_level
+                )));
+
+                Grid::new(("shredded_levels", &column.path))
+                    .num_columns(5)
+                    .spacing([10., 4.])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for title in ["R", "D", "Value", "Page", "Meaning"] {
+                            ui.label(text(String::from(title)).strong());
+                        }
+                        ui.end_row();
+                        for entry in &column.entries {
+                            ui.label(text(entry.repetition_level.to_string()));
+                            ui.label(text(entry.definition_level.to_string()));
+                            match &entry.value {
+                                Some(value) => ui.label(text(value.clone())),
+                                None => ui.label(text(String::from("-")).weak()),
+                            };
+                            match entry.page {
+                                Some(page) => ui
+                                    .label(text(format!("Page {}", page.index)))
+                                    .on_hover_text(format!(
+                                        "Page data at bytes {}..={}",
+                                        page.byte_interval.0, page.byte_interval.1
+                                    )),
+                                None => ui.label(text(String::from("N/A"))),
+                            };
+                            ui.label(text(column.explain(entry)));
+                            ui.end_row();
+                        }
+                    });
+                if column.entries.is_empty() {
+                    ui.label(text(String::from("The record has no levels in this column")).weak());
+                }
+            });
+    }
+}